@@ -1,10 +1,13 @@
 use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+use anchor_lang::AnchorDeserialize;
 use anyhow::Result;
 use fixed::types::I80F48;
 use itertools::Itertools;
 use openbook_v2::state::{
-    Market, Orderbook, Side, DROP_EXPIRED_ORDER_LIMIT, FILL_EVENT_REMAINING_LIMIT,
+    Market, Orderbook, Side, TakeQuote, DROP_EXPIRED_ORDER_LIMIT, FILL_EVENT_REMAINING_LIMIT,
 };
+use openbook_v2::L2Depth;
 use std::collections::HashSet;
 
 pub const MAXIMUM_TAKEN_ORDERS: u8 = 45;
@@ -17,12 +20,17 @@ pub struct Amounts {
     pub not_enough_liquidity: bool,
 }
 
+/// Makers to pass along with a take order so their fills are processed right away
+///
+/// When the event heap is full, fills of makers that aren't passed make the take order fail, so
+/// as many makers as the program processes are returned.
 pub fn remaining_accounts_to_crank(
     book: Orderbook,
     side: Side,
     market: &Market,
     oracle_price: Option<I80F48>,
     now_ts: u64,
+    event_heap_full: bool,
 ) -> Result<Vec<Pubkey>> {
     let oracle_price_lots = if let Some(oracle_price) = oracle_price {
         Some(market.native_price_to_lot(oracle_price)?)
@@ -30,12 +38,18 @@ pub fn remaining_accounts_to_crank(
         None
     };
 
+    let maximum_remaining_accounts = if event_heap_full {
+        FILL_EVENT_REMAINING_LIMIT
+    } else {
+        MAXIMUM_REMAINING_ACCOUNTS
+    };
+
     let mut remaining_accounts = HashSet::new();
     let opposing_bookside = book.bookside(side.invert_side());
     for order in opposing_bookside.iter_valid(now_ts, oracle_price_lots) {
         remaining_accounts.insert(order.node.owner);
 
-        if remaining_accounts.len() >= MAXIMUM_REMAINING_ACCOUNTS {
+        if remaining_accounts.len() >= maximum_remaining_accounts {
             break;
         }
     }
@@ -43,6 +57,25 @@ pub fn remaining_accounts_to_crank(
     Ok(remaining_accounts.into_iter().collect_vec())
 }
 
+/// Decode the return data of an instruction
+///
+/// The runtime trims trailing zero bytes off return data, so they are restored before decoding.
+pub fn decode_return_data<T: AnchorDeserialize>(return_data: &[u8]) -> Result<T> {
+    let mut data = return_data.to_vec();
+    data.resize(MAX_RETURN_DATA.max(return_data.len()), 0);
+    Ok(T::deserialize(&mut &data[..])?)
+}
+
+/// Decode the return data of the `get_l2_depth` instruction
+pub fn decode_l2_depth(return_data: &[u8]) -> Result<L2Depth> {
+    decode_return_data(return_data)
+}
+
+/// Decode the return data of the `quote_take_order` instruction
+pub fn decode_take_quote(return_data: &[u8]) -> Result<TakeQuote> {
+    decode_return_data(return_data)
+}
+
 pub fn amounts_from_book(
     book: Orderbook,
     side: Side,
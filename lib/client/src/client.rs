@@ -13,12 +13,16 @@ use itertools::Itertools;
 
 use openbook_v2::state::OracleConfigParams;
 use openbook_v2::{
-    state::{Market, OpenOrdersAccount, PlaceOrderType, SelfTradeBehavior, Side},
-    PlaceMultipleOrdersArgs, PlaceOrderArgs, PlaceOrderPeggedArgs,
+    state::{
+        Market, OpenOrdersAccount, OpenOrdersIndexer, PlaceOrderType, SelfTradeBehavior, Side,
+        TakeQuote, MAX_OPEN_ORDERS,
+    },
+    L2Depth, PlaceMultipleOrdersArgs, PlaceOrderArgs, PlaceOrderPeggedArgs, PlaceTakeOrderArgs,
 };
 
 use solana_client::nonblocking::rpc_client::RpcClient as RpcClientAsync;
 use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_client::rpc_response::RpcSimulateTransactionResult;
 use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
 use solana_sdk::commitment_config::CommitmentLevel;
 use solana_sdk::hash::Hash;
@@ -26,10 +30,11 @@ use solana_sdk::signer::keypair;
 use solana_sdk::transaction::TransactionError;
 
 use crate::account_fetcher::*;
+use crate::book::{decode_l2_depth, decode_take_quote};
 use crate::gpa::{fetch_anchor_account, fetch_openbook_accounts};
 
 use anyhow::Context;
-use solana_sdk::instruction::Instruction;
+use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::signature::{Keypair, Signature};
 use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signer::Signer};
 
@@ -93,6 +98,10 @@ pub struct OpenBookClient {
     pub owner: Arc<Keypair>,
     pub open_orders_account: Pubkey,
 
+    /// Signs order placement and cancellation instead of the owner when set, see
+    /// `set_session_delegate`
+    pub session_key: Option<Arc<Keypair>>,
+
     pub http_client: reqwest::Client,
 }
 
@@ -109,6 +118,24 @@ impl OpenBookClient {
         fetch_openbook_accounts(&client.rpc_async(), openbook_v2::ID, owner.pubkey()).await
     }
 
+    /// The owner's open orders accounts on `market`, as recorded by their indexer
+    pub async fn find_accounts_for_market(
+        client: &Client,
+        owner: &Pubkey,
+        market: &Pubkey,
+    ) -> anyhow::Result<Vec<Pubkey>> {
+        let open_orders_indexer = Pubkey::find_program_address(
+            &[b"OpenOrdersIndexer".as_ref(), owner.as_ref()],
+            &openbook_v2::id(),
+        )
+        .0;
+        let indexer: OpenOrdersIndexer = client.rpc_anchor_account(&open_orders_indexer).await?;
+        Ok(indexer
+            .open_orders_accounts_for_market(market)
+            .copied()
+            .collect())
+    }
+
     pub async fn find_or_create_account(
         client: &Client,
         owner: &Keypair,
@@ -139,6 +166,7 @@ impl OpenBookClient {
                 None,
                 account_num,
                 openbook_account_name,
+                MAX_OPEN_ORDERS as u16,
             )
             .await
             .context("Failed to create account...")?;
@@ -192,6 +220,110 @@ impl OpenBookClient {
         Ok((open_orders_indexer, txsig))
     }
 
+    /// Backfill the markets of an indexer created before it recorded them
+    pub async fn migrate_open_orders_indexer(
+        client: &Client,
+        owner: &Pubkey,
+        open_orders_accounts: &[Pubkey], // in the order of the indexer
+        payer: &Keypair,                 // pays the SOL for the grown account
+    ) -> anyhow::Result<Signature> {
+        let open_orders_indexer = Pubkey::find_program_address(
+            &[b"OpenOrdersIndexer".as_ref(), owner.as_ref()],
+            &openbook_v2::id(),
+        )
+        .0;
+
+        let mut accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &openbook_v2::accounts::MigrateOpenOrdersIndexer {
+                payer: payer.pubkey(),
+                owner: *owner,
+                open_orders_indexer,
+                system_program: System::id(),
+            },
+            None,
+        );
+        accounts.extend(
+            open_orders_accounts
+                .iter()
+                .map(|pubkey| AccountMeta::new_readonly(*pubkey, false)),
+        );
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts,
+            data: anchor_lang::InstructionData::data(
+                &openbook_v2::instruction::MigrateOpenOrdersIndexer {},
+            ),
+        };
+
+        TransactionBuilder {
+            instructions: vec![ix],
+            address_lookup_tables: vec![],
+            payer: payer.pubkey(),
+            signers: vec![payer],
+            config: client.transaction_builder_config,
+        }
+        .send_and_confirm(client)
+        .await
+    }
+
+    /// Settle several open orders accounts of the owner's indexer in one transaction.
+    ///
+    /// Each entry is an open orders account with the base and quote token accounts receiving
+    /// its funds.
+    pub async fn settle_funds_batch(
+        client: &Client,
+        owner: &Keypair,
+        settlements: &[(Pubkey, Pubkey, Pubkey)],
+    ) -> anyhow::Result<Signature> {
+        let open_orders_indexer = Pubkey::find_program_address(
+            &[b"OpenOrdersIndexer".as_ref(), owner.pubkey().as_ref()],
+            &openbook_v2::id(),
+        )
+        .0;
+
+        let mut accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &openbook_v2::accounts::SettleFundsBatch {
+                owner: owner.pubkey(),
+                penalty_payer: owner.pubkey(),
+                open_orders_indexer,
+                token_program: Token::id(),
+                system_program: System::id(),
+            },
+            None,
+        );
+        for (open_orders_account, user_base_account, user_quote_account) in settlements {
+            let account: OpenOrdersAccount = client.rpc_anchor_account(open_orders_account).await?;
+            let market: Market = client.rpc_anchor_account(&account.market).await?;
+            accounts.extend([
+                AccountMeta::new(*open_orders_account, false),
+                AccountMeta::new(account.market, false),
+                AccountMeta::new_readonly(market.market_authority, false),
+                AccountMeta::new(market.market_base_vault, false),
+                AccountMeta::new(market.market_quote_vault, false),
+                AccountMeta::new(*user_base_account, false),
+                AccountMeta::new(*user_quote_account, false),
+            ]);
+        }
+
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts,
+            data: anchor_lang::InstructionData::data(
+                &openbook_v2::instruction::SettleFundsBatch {},
+            ),
+        };
+
+        TransactionBuilder {
+            instructions: vec![ix],
+            address_lookup_tables: vec![],
+            payer: owner.pubkey(),
+            signers: vec![owner],
+            config: client.transaction_builder_config,
+        }
+        .send_and_confirm(client)
+        .await
+    }
+
     pub async fn create_open_orders_account(
         client: &Client,
         market: Pubkey,
@@ -200,6 +332,7 @@ impl OpenBookClient {
         delegate: Option<Pubkey>,
         account_num: u32,
         name: &str,
+        capacity: u16,
     ) -> anyhow::Result<(Pubkey, Signature)> {
         let open_orders_indexer = Pubkey::find_program_address(
             &[b"OpenOrdersIndexer".as_ref(), owner.pubkey().as_ref()],
@@ -234,6 +367,7 @@ impl OpenBookClient {
             data: anchor_lang::InstructionData::data(
                 &openbook_v2::instruction::CreateOpenOrdersAccount {
                     name: name.to_string(),
+                    capacity,
                 },
             ),
         };
@@ -287,14 +421,29 @@ impl OpenBookClient {
             account_fetcher,
             owner,
             open_orders_account: account,
+            session_key: None,
             http_client: reqwest::Client::new(),
         })
     }
 
+    pub fn with_session_key(self, session_key: Arc<Keypair>) -> Self {
+        Self {
+            session_key: Some(session_key),
+            ..self
+        }
+    }
+
     pub fn owner(&self) -> Pubkey {
         self.owner.pubkey()
     }
 
+    /// The session key if set, otherwise the owner
+    pub fn signer(&self) -> Pubkey {
+        self.session_key
+            .as_ref()
+            .map_or_else(|| self.owner(), |session_key| session_key.pubkey())
+    }
+
     pub async fn openorders_account(&self) -> anyhow::Result<OpenOrdersAccount> {
         account_fetcher_fetch_openorders_account(&*self.account_fetcher, &self.open_orders_account)
             .await
@@ -324,6 +473,10 @@ impl OpenBookClient {
         maker_fee: i64,
         taker_fee: i64,
         time_expiry: i64,
+        book_side_capacity: u32,
+        event_heap_capacity: u16,
+        penalty_event_heap: u64,
+        crank_reward: u64,
     ) -> anyhow::Result<Signature> {
         let ix = Instruction {
             program_id: openbook_v2::id(),
@@ -371,6 +524,10 @@ impl OpenBookClient {
                 maker_fee,
                 taker_fee,
                 time_expiry,
+                book_side_capacity,
+                event_heap_capacity,
+                penalty_event_heap,
+                crank_reward,
             }),
         };
         self.send_and_confirm_owner_tx(vec![ix]).await
@@ -400,7 +557,7 @@ impl OpenBookClient {
                     &openbook_v2::accounts::PlaceOrder {
                         open_orders_account: self.open_orders_account,
                         open_orders_admin: None,
-                        signer: self.owner(),
+                        signer: self.signer(),
                         market: market_address,
                         bids: market.bids,
                         asks: market.asks,
@@ -428,7 +585,7 @@ impl OpenBookClient {
                 },
             }),
         };
-        self.send_and_confirm_owner_tx(vec![ix]).await
+        self.send_and_confirm_signer_tx(vec![ix]).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -456,7 +613,7 @@ impl OpenBookClient {
                     &openbook_v2::accounts::PlaceOrder {
                         open_orders_account: self.open_orders_account,
                         open_orders_admin: None,
-                        signer: self.owner(),
+                        signer: self.signer(),
                         market: market_address,
                         bids: market.bids,
                         asks: market.asks,
@@ -485,7 +642,7 @@ impl OpenBookClient {
                 },
             }),
         };
-        self.send_and_confirm_owner_tx(vec![ix]).await
+        self.send_and_confirm_signer_tx(vec![ix]).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -501,7 +658,7 @@ impl OpenBookClient {
                 anchor_lang::ToAccountMetas::to_account_metas(
                     &openbook_v2::accounts::CancelOrder {
                         open_orders_account: self.open_orders_account,
-                        signer: self.owner(),
+                        signer: self.signer(),
                         market: market_address,
                         bids: market.bids,
                         asks: market.asks,
@@ -513,7 +670,7 @@ impl OpenBookClient {
                 order_id,
             }),
         };
-        self.send_and_confirm_owner_tx(vec![ix]).await
+        self.send_and_confirm_signer_tx(vec![ix]).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -530,7 +687,7 @@ impl OpenBookClient {
                 anchor_lang::ToAccountMetas::to_account_metas(
                     &openbook_v2::accounts::CancelOrder {
                         open_orders_account: self.open_orders_account,
-                        signer: self.owner(),
+                        signer: self.signer(),
                         market: market_address,
                         bids: market.bids,
                         asks: market.asks,
@@ -543,7 +700,7 @@ impl OpenBookClient {
                 limit,
             }),
         };
-        self.send_and_confirm_owner_tx(vec![ix]).await
+        self.send_and_confirm_signer_tx(vec![ix]).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -564,7 +721,7 @@ impl OpenBookClient {
                 anchor_lang::ToAccountMetas::to_account_metas(
                     &openbook_v2::accounts::CancelAllAndPlaceOrders {
                         open_orders_account: self.open_orders_account,
-                        signer: self.owner(),
+                        signer: self.signer(),
                         open_orders_admin: market.open_orders_admin.into(),
                         user_quote_account: user_quote_account,
                         user_base_account: user_base_account,
@@ -590,7 +747,7 @@ impl OpenBookClient {
                 },
             ),
         };
-        self.send_and_confirm_owner_tx(vec![ix]).await
+        self.send_and_confirm_signer_tx(vec![ix]).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -667,6 +824,159 @@ impl OpenBookClient {
     }
 
     #[allow(clippy::too_many_arguments)]
+    pub async fn withdraw(
+        &self,
+        market: Market,
+        market_address: Pubkey,
+        base_amount: u64,
+        quote_amount: u64,
+        user_base_account: Pubkey,
+        user_quote_account: Pubkey,
+    ) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::Withdraw {
+                        owner: self.owner(),
+                        open_orders_account: self.open_orders_account,
+                        market: market_address,
+                        market_authority: market.market_authority,
+                        market_base_vault: market.market_base_vault,
+                        market_quote_vault: market.market_quote_vault,
+                        user_base_account,
+                        user_quote_account,
+                        token_program: Token::id(),
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::Withdraw {
+                base_amount,
+                quote_amount,
+            }),
+        };
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
+    pub async fn set_auto_settle(&self, enabled: bool) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::SetAutoSettle {
+                        owner: self.owner(),
+                        open_orders_account: self.open_orders_account,
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::SetAutoSettle {
+                enabled,
+            }),
+        };
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
+    /// Let anyone cancel the account's orders once no heartbeat was recorded for
+    /// `timeout_seconds`, 0 disables it
+    pub async fn set_heartbeat_timeout(&self, timeout_seconds: u16) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::SetHeartbeatTimeout {
+                        owner: self.owner(),
+                        open_orders_account: self.open_orders_account,
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(
+                &openbook_v2::instruction::SetHeartbeatTimeout { timeout_seconds },
+            ),
+        };
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
+    pub async fn heartbeat(&self) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::Heartbeat {
+                        signer: self.signer(),
+                        open_orders_account: self.open_orders_account,
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::Heartbeat {}),
+        };
+        self.send_and_confirm_signer_tx(vec![ix]).await
+    }
+
+    /// Limit the orders of the account, 0 disables a limit
+    pub async fn set_risk_limits(
+        &self,
+        max_base_lots_per_side: i64,
+        max_order_quote_native: u64,
+        max_taker_quote_native_per_window: u64,
+        window_seconds: u32,
+    ) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::SetRiskLimits {
+                        payer: self.owner(),
+                        owner: self.owner(),
+                        open_orders_account: self.open_orders_account,
+                        system_program: System::id(),
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::SetRiskLimits {
+                max_base_lots_per_side,
+                max_order_quote_native,
+                max_taker_quote_native_per_window,
+                window_seconds,
+            }),
+        };
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    /// Let `session_key` sign for the account until `expiry_timestamp`, 0 revokes it
+    pub async fn set_session_delegate(
+        &self,
+        session_key: Pubkey,
+        expiry_timestamp: u64,
+    ) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::SetSessionDelegate {
+                        payer: self.owner(),
+                        authority: self.owner(),
+                        open_orders_account: self.open_orders_account,
+                        system_program: System::id(),
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(
+                &openbook_v2::instruction::SetSessionDelegate {
+                    session_key,
+                    expiry_timestamp,
+                },
+            ),
+        };
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
     pub async fn consume_events(
         &self,
         market: Market,
@@ -681,6 +991,7 @@ impl OpenBookClient {
                         consume_events_admin: market.consume_events_admin.into(),
                         market: market_address,
                         event_heap: market.event_heap,
+                        crank_reward_receiver: Some(self.owner()),
                     },
                     None,
                 )
@@ -692,6 +1003,127 @@ impl OpenBookClient {
         self.send_and_confirm_owner_tx(vec![ix]).await
     }
 
+    pub async fn prune_expired_orders(
+        &self,
+        market: Market,
+        market_address: Pubkey,
+        limit: u8,
+    ) -> anyhow::Result<Signature> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::PruneExpiredOrders {
+                        penalty_payer: self.owner(),
+                        market: market_address,
+                        bids: market.bids,
+                        asks: market.asks,
+                        event_heap: market.event_heap,
+                        crank_reward_receiver: Some(self.owner()),
+                        system_program: System::id(),
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(
+                &openbook_v2::instruction::PruneExpiredOrders { limit },
+            ),
+        };
+        self.send_and_confirm_owner_tx(vec![ix]).await
+    }
+
+    /// Simulate `get_l2_depth` and decode the returned price levels of the book
+    pub async fn l2_depth(
+        &self,
+        market: Market,
+        market_address: Pubkey,
+        max_levels: u8,
+    ) -> anyhow::Result<L2Depth> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::GetL2Depth {
+                        market: market_address,
+                        bids: market.bids,
+                        asks: market.asks,
+                        oracle_a: market.oracle_a.into(),
+                        oracle_b: market.oracle_b.into(),
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::GetL2Depth {
+                max_levels,
+            }),
+        };
+        decode_l2_depth(&self.simulate_return_data(ix).await?)
+    }
+
+    /// Simulate `quote_take_order` and decode the expected outcome of the take order
+    #[allow(clippy::too_many_arguments)]
+    pub async fn quote_take_order(
+        &self,
+        market: Market,
+        market_address: Pubkey,
+        side: Side,
+        price_lots: i64,
+        max_base_lots: i64,
+        max_quote_lots_including_fees: i64,
+        order_type: PlaceOrderType,
+        limit: u8,
+    ) -> anyhow::Result<TakeQuote> {
+        let ix = Instruction {
+            program_id: openbook_v2::id(),
+            accounts: {
+                anchor_lang::ToAccountMetas::to_account_metas(
+                    &openbook_v2::accounts::QuoteTakeOrder {
+                        market: market_address,
+                        bids: market.bids,
+                        asks: market.asks,
+                        event_heap: market.event_heap,
+                        oracle_a: market.oracle_a.into(),
+                        oracle_b: market.oracle_b.into(),
+                    },
+                    None,
+                )
+            },
+            data: anchor_lang::InstructionData::data(&openbook_v2::instruction::QuoteTakeOrder {
+                args: PlaceTakeOrderArgs {
+                    side,
+                    price_lots,
+                    max_base_lots,
+                    max_quote_lots_including_fees,
+                    order_type,
+                    limit,
+                },
+            }),
+        };
+        decode_take_quote(&self.simulate_return_data(ix).await?)
+    }
+
+    /// Simulate a permissionless instruction and return the data it returns
+    async fn simulate_return_data(&self, ix: Instruction) -> anyhow::Result<Vec<u8>> {
+        let result = TransactionBuilder {
+            instructions: vec![ix],
+            address_lookup_tables: vec![],
+            payer: self.client.fee_payer.pubkey(),
+            signers: vec![&*self.client.fee_payer],
+            config: self.client.transaction_builder_config,
+        }
+        .simulate(&self.client)
+        .await?;
+        // all zero return data is trimmed away entirely
+        let Some(return_data) = result.return_data else {
+            return Ok(vec![]);
+        };
+        anyhow::ensure!(
+            return_data.program_id == openbook_v2::id().to_string(),
+            "return data not set by openbook"
+        );
+        Ok(base64::decode(return_data.data.0)?)
+    }
+
     pub async fn send_and_confirm_owner_tx(
         &self,
         instructions: Vec<Instruction>,
@@ -707,6 +1139,23 @@ impl OpenBookClient {
         .await
     }
 
+    /// Like `send_and_confirm_owner_tx`, but signed by the session key if set
+    pub async fn send_and_confirm_signer_tx(
+        &self,
+        instructions: Vec<Instruction>,
+    ) -> anyhow::Result<Signature> {
+        let signer = self.session_key.as_ref().unwrap_or(&self.owner);
+        TransactionBuilder {
+            instructions,
+            address_lookup_tables: vec![],
+            payer: self.client.fee_payer.pubkey(),
+            signers: vec![&**signer, &*self.client.fee_payer],
+            config: self.client.transaction_builder_config,
+        }
+        .send_and_confirm(&self.client)
+        .await
+    }
+
     pub async fn send_and_confirm_permissionless_tx(
         &self,
         instructions: Vec<Instruction>,
@@ -786,6 +1235,24 @@ impl<'a> TransactionBuilder<'a> {
         Ok(tx)
     }
 
+    pub async fn simulate(self, client: &Client) -> anyhow::Result<RpcSimulateTransactionResult> {
+        let rpc = client.rpc_async();
+        let tx = self.transaction(&rpc).await?;
+        let result = rpc
+            .simulate_transaction(&tx)
+            .await
+            .map_err(prettify_solana_client_error)?
+            .value;
+        if result.err.is_some() {
+            return Err(OpenBookClientError::SendTransactionPreflightFailure {
+                err: result.err,
+                logs: result.logs.unwrap_or_default(),
+            }
+            .into());
+        }
+        Ok(result)
+    }
+
     // These two send() functions don't really belong into the transaction builder!
 
     pub async fn send(self, client: &Client) -> anyhow::Result<Signature> {
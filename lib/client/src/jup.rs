@@ -1,13 +1,12 @@
-use anchor_lang::__private::bytemuck::Zeroable;
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
 use anyhow::Result;
 use fixed::types::I80F48;
 use openbook_v2::{
     accounts::PlaceTakeOrder,
-    accounts_zerocopy,
+    accounts_zerocopy::{self, LoadDynamicZeroCopy},
     pubkey_option::NonZeroPubkeyOption,
-    state::{BookSide, EventHeap, Market, Orderbook, Side},
+    state::{BookSide, BookSideRefMut, EventHeap, EventHeapView, Market, Orderbook, Side},
 };
 
 use crate::{
@@ -21,14 +20,14 @@ use jupiter_amm_interface::{
 };
 /// An abstraction in order to share reserve mints and necessary data
 use solana_sdk::{pubkey::Pubkey, sysvar::clock};
-use std::cell::RefCell;
+use std::cell::{RefCell, RefMut};
 
 #[derive(Clone)]
 pub struct OpenBookMarket {
     market: Market,
-    event_heap: EventHeap,
-    bids: BookSide,
-    asks: BookSide,
+    event_heap: Vec<u8>,
+    bids: Vec<u8>,
+    asks: Vec<u8>,
     timestamp: u64,
     key: Pubkey,
     label: String,
@@ -83,9 +82,9 @@ impl Amm for OpenBookMarket {
             label: market.name().to_string(),
             related_accounts,
             reserve_mints: [market.base_mint, market.quote_mint],
-            event_heap: EventHeap::zeroed(),
-            bids: BookSide::zeroed(),
-            asks: BookSide::zeroed(),
+            event_heap: vec![],
+            bids: vec![],
+            asks: vec![],
             oracle_price: None,
             timestamp: 0,
             is_permissioned,
@@ -97,15 +96,18 @@ impl Amm for OpenBookMarket {
             return Ok(());
         }
 
+        // book sides and event heaps can have any capacity, keep their data and split it on use
         let bids_data = account_map.get(&self.market.bids).unwrap();
-        self.bids = BookSide::try_deserialize_from_slice(&mut bids_data.data.as_slice()).unwrap();
+        bids_data.load_dynamic::<BookSide>().unwrap();
+        self.bids = bids_data.data.clone();
 
         let asks_data = account_map.get(&self.market.asks).unwrap();
-        self.asks = BookSide::try_deserialize_from_slice(&mut asks_data.data.as_slice()).unwrap();
+        asks_data.load_dynamic::<BookSide>().unwrap();
+        self.asks = asks_data.data.clone();
 
         let event_heap_data = account_map.get(&self.market.event_heap).unwrap();
-        self.event_heap =
-            EventHeap::try_deserialize_from_slice(&mut event_heap_data.data.as_slice()).unwrap();
+        event_heap_data.load_dynamic::<EventHeap>().unwrap();
+        self.event_heap = event_heap_data.data.clone();
 
         let clock_data = account_map.get(&clock::ID).unwrap();
         let clock: Clock = bincode::deserialize(clock_data.data.as_slice())?;
@@ -120,7 +122,7 @@ impl Amm for OpenBookMarket {
         self.oracle_price = self.market.oracle_price(
             oracle_acc(self.market.oracle_a).as_ref(),
             oracle_acc(self.market.oracle_b).as_ref(),
-            clock.slot,
+            &clock,
         )?;
 
         self.timestamp = clock.unix_timestamp.try_into().unwrap();
@@ -157,11 +159,11 @@ impl Amm for OpenBookMarket {
             ),
         };
 
-        let bids_ref = RefCell::new(self.bids);
-        let asks_ref = RefCell::new(self.asks);
+        let bids_ref = RefCell::new(self.bids.clone());
+        let asks_ref = RefCell::new(self.asks.clone());
         let book = Orderbook {
-            bids: bids_ref.borrow_mut(),
-            asks: asks_ref.borrow_mut(),
+            bids: book_side_ref_mut(&bids_ref)?,
+            asks: book_side_ref_mut(&asks_ref)?,
         };
 
         let order_amounts: Amounts = amounts_from_book(
@@ -245,19 +247,23 @@ impl Amm for OpenBookMarket {
 
             let mut account_metas = accounts.to_account_metas(None);
 
-            let bids_ref = RefCell::new(self.bids);
-            let asks_ref = RefCell::new(self.asks);
+            let bids_ref = RefCell::new(self.bids.clone());
+            let asks_ref = RefCell::new(self.asks.clone());
             let book = Orderbook {
-                bids: bids_ref.borrow_mut(),
-                asks: asks_ref.borrow_mut(),
+                bids: book_side_ref_mut(&bids_ref)?,
+                asks: book_side_ref_mut(&asks_ref)?,
             };
 
+            let event_heap: EventHeapView<_, _> =
+                accounts_zerocopy::split_dynamic_data::<EventHeap>(&self.event_heap)?.into();
+
             let remaining_accounts = remaining_accounts_to_crank(
                 book,
                 side,
                 &self.market,
                 self.oracle_price,
                 self.timestamp,
+                event_heap.is_full(),
             )?;
 
             let remaining_accounts: Vec<AccountMeta> = remaining_accounts
@@ -278,6 +284,11 @@ impl Amm for OpenBookMarket {
     }
 }
 
+fn book_side_ref_mut(data: &RefCell<Vec<u8>>) -> Result<BookSideRefMut> {
+    let data = RefMut::map(data.borrow_mut(), |data| data.as_mut_slice());
+    Ok(accounts_zerocopy::split_dynamic_ref_mut::<BookSide>(data)?.into())
+}
+
 #[cfg(all(test, feature = "enable-gpl"))]
 mod test {
     use super::*;
@@ -298,7 +298,7 @@ fn run_fuzz(fuzz_data: FuzzData) -> Corpus {
                 .state
                 .get_account::<openbook_v2::state::EventHeap>(&ctx.event_heap)
                 .unwrap();
-            event_heap.len()
+            event_heap.view().len()
         };
 
         for _ in (0..event_heap_len(&ctx)).step_by(MAX_EVENTS_CONSUME) {
@@ -380,7 +380,7 @@ fn run_fuzz(fuzz_data: FuzzData) -> Corpus {
                 .state
                 .get_account::<openbook_v2::state::BookSide>(pubkey)
                 .unwrap();
-            book_side.is_empty()
+            book_side.view().is_empty()
         };
 
         assert!(is_empty(&ctx.asks));
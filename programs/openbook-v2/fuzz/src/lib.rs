@@ -238,6 +238,7 @@ impl FuzzContext {
             };
             let data = openbook_v2::instruction::CreateOpenOrdersAccount {
                 name: "fuzz test".to_string(),
+                capacity: MAX_OPEN_ORDERS as u16,
             };
             process_instruction(&mut self.state, &data, &accounts, &[]).unwrap();
 
@@ -289,6 +290,14 @@ impl FuzzContext {
     }
 
     pub fn create_market(&mut self, data: openbook_v2::instruction::CreateMarket) -> ProgramResult {
+        // book sides and event heap are created with the default size
+        let data = openbook_v2::instruction::CreateMarket {
+            book_side_capacity: MAX_ORDERTREE_NODES as u32,
+            event_heap_capacity: MAX_NUM_EVENTS,
+            penalty_event_heap: PENALTY_EVENT_HEAP,
+            crank_reward: CRANK_REWARD,
+            ..data
+        };
         let accounts = openbook_v2::accounts::CreateMarket {
             market: self.market,
             market_authority: self.market_authority,
@@ -710,6 +719,7 @@ impl FuzzContext {
             consume_events_admin: None,
             market: self.market,
             event_heap: self.event_heap,
+            crank_reward_receiver: None,
         };
 
         let remaining = user_ids
@@ -734,6 +744,7 @@ impl FuzzContext {
             consume_events_admin: None,
             market: self.market,
             event_heap: self.event_heap,
+            crank_reward_receiver: None,
         };
 
         let remaining = user_ids
@@ -1,5 +1,5 @@
 use crate::error::OpenBookError;
-use crate::state::{BookSide, Market, OpenOrdersAccount};
+use crate::state::{BookSide, Market, OpenOrdersAccount, OpenOrdersLoader, DELEGATE_CANCEL_ORDERS};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -8,7 +8,7 @@ pub struct CancelOrder<'info> {
     #[account(
         mut,
         has_one = market,
-        constraint = open_orders_account.load()?.is_owner_or_delegate(signer.key()) @ OpenBookError::NoOwnerOrDelegate
+        constraint = open_orders_account.is_owner_or_any_delegate(signer.key(), DELEGATE_CANCEL_ORDERS)? @ OpenBookError::NoOwnerOrDelegate
     )]
     pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
     #[account(
@@ -8,7 +8,7 @@ pub struct CloseOpenOrdersAccount<'info> {
         mut,
         seeds = [b"OpenOrdersIndexer".as_ref(), owner.key().as_ref()],
         bump = open_orders_indexer.bump,
-        realloc = OpenOrdersIndexer::space(open_orders_indexer.addresses.len()-1),
+        realloc = open_orders_indexer.space_after_remove(),
         realloc::payer = sol_destination,
         realloc::zero = false,
     )]
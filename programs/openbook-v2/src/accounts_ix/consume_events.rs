@@ -14,4 +14,7 @@ pub struct ConsumeEvents<'info> {
     pub market: AccountLoader<'info, Market>,
     #[account(mut)]
     pub event_heap: AccountLoader<'info, EventHeap>,
+    /// CHECK: receives the crank reward, none leaves it in the penalty pool
+    #[account(mut)]
+    pub crank_reward_receiver: Option<UncheckedAccount<'info>>,
 }
@@ -1,7 +1,11 @@
-use crate::state::{Market, OpenOrdersAccount, OpenOrdersIndexer};
+use crate::error::OpenBookError;
+use crate::state::{
+    Market, OpenOrdersAccount, OpenOrdersIndexer, MAX_INDEXED_OPEN_ORDERS_ACCOUNTS,
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
+#[instruction(name: String, capacity: u16)]
 pub struct CreateOpenOrdersAccount<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -15,7 +19,7 @@ pub struct CreateOpenOrdersAccount<'info> {
         realloc = OpenOrdersIndexer::space(open_orders_indexer.addresses.len()+1),
         realloc::payer = payer,
         realloc::zero = false,
-        constraint = open_orders_indexer.addresses.len() < 256,
+        constraint = open_orders_indexer.addresses.len() < MAX_INDEXED_OPEN_ORDERS_ACCOUNTS @ OpenBookError::OpenOrdersIndexerFull,
     )]
     pub open_orders_indexer: Account<'info, OpenOrdersIndexer>,
     #[account(
@@ -23,7 +27,7 @@ pub struct CreateOpenOrdersAccount<'info> {
         seeds = [b"OpenOrders".as_ref(), owner.key().as_ref(), &(open_orders_indexer.created_counter + 1).to_le_bytes()],
         bump,
         payer = payer,
-        space = OpenOrdersAccount::space(),
+        space = OpenOrdersAccount::space_with_capacity(capacity as usize),
     )]
     pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
     pub market: AccountLoader<'info, Market>,
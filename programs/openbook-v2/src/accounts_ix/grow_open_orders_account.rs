@@ -0,0 +1,15 @@
+use crate::state::OpenOrdersAccount;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct GrowOpenOrdersAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,13 @@
+use crate::error::OpenBookError;
+use crate::state::{OpenOrdersAccount, OpenOrdersLoader, DELEGATE_PLACE_ORDERS};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = open_orders_account.is_owner_or_any_delegate(signer.key(), DELEGATE_PLACE_ORDERS)? @ OpenBookError::NoOwnerOrDelegate
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+}
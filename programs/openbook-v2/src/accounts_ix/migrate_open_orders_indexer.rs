@@ -0,0 +1,20 @@
+use crate::state::OpenOrdersIndexer;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MigrateOpenOrdersIndexer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: only used to derive the indexer address
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"OpenOrdersIndexer".as_ref(), owner.key().as_ref()],
+        bump = open_orders_indexer.bump,
+        realloc = OpenOrdersIndexer::space(open_orders_indexer.addresses.len()),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub open_orders_indexer: Account<'info, OpenOrdersIndexer>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,4 +1,5 @@
 pub use cancel_all_and_place_orders::*;
+pub use cancel_on_missed_heartbeat::*;
 pub use cancel_order::*;
 pub use close_market::*;
 pub use close_open_orders_account::*;
@@ -8,19 +9,37 @@ pub use create_market::*;
 pub use create_open_orders_account::*;
 pub use create_open_orders_indexer::*;
 pub use deposit::*;
+pub use get_l2_depth::*;
+pub use grow_open_orders_account::*;
+pub use heartbeat::*;
+pub use migrate_open_orders_indexer::*;
 pub use place_order::*;
 pub use place_take_order::*;
+pub use place_take_order_two_hop::*;
+pub use prune_expired_orders::*;
 pub use prune_orders::*;
+pub use quote_take_order::*;
+pub use set_auto_settle::*;
+pub use set_crank_reward::*;
 pub use set_delegate::*;
+pub use set_delegate_permissions::*;
+pub use set_heartbeat_timeout::*;
 pub use set_market_expired::*;
+pub use set_risk_limits::*;
+pub use set_session_delegate::*;
+pub use set_stp_group::*;
 pub use settle_funds::*;
+pub use settle_funds_batch::*;
 pub use settle_funds_expired::*;
 pub use stub_oracle_close::*;
 pub use stub_oracle_create::*;
 pub use stub_oracle_set::*;
 pub use sweep_fees::*;
+pub use transfer_open_orders_account::*;
+pub use withdraw::*;
 
 mod cancel_all_and_place_orders;
+mod cancel_on_missed_heartbeat;
 mod cancel_order;
 mod close_market;
 mod close_open_orders_account;
@@ -30,14 +49,31 @@ mod create_market;
 mod create_open_orders_account;
 mod create_open_orders_indexer;
 mod deposit;
+mod get_l2_depth;
+mod grow_open_orders_account;
+mod heartbeat;
+mod migrate_open_orders_indexer;
 mod place_order;
 mod place_take_order;
+mod place_take_order_two_hop;
+mod prune_expired_orders;
 mod prune_orders;
+mod quote_take_order;
+mod set_auto_settle;
+mod set_crank_reward;
 mod set_delegate;
+mod set_delegate_permissions;
+mod set_heartbeat_timeout;
 mod set_market_expired;
+mod set_risk_limits;
+mod set_session_delegate;
+mod set_stp_group;
 mod settle_funds;
+mod settle_funds_batch;
 mod settle_funds_expired;
 mod stub_oracle_close;
 mod stub_oracle_create;
 mod stub_oracle_set;
 mod sweep_fees;
+mod transfer_open_orders_account;
+mod withdraw;
@@ -11,7 +11,7 @@ pub struct PlaceOrder<'info> {
     #[account(
         mut,
         has_one = market,
-        constraint = open_orders_account.load()?.is_owner_or_delegate(signer.key()) @ OpenBookError::NoOwnerOrDelegate
+        constraint = open_orders_account.is_owner_or_any_delegate(signer.key(), DELEGATE_PLACE_ORDERS)? @ OpenBookError::NoOwnerOrDelegate
     )]
     pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
     pub open_orders_admin: Option<Signer<'info>>,
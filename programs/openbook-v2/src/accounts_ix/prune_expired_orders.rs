@@ -0,0 +1,26 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PruneExpiredOrders<'info> {
+    /// Pays the event heap penalty for the orders refunded through the event heap
+    #[account(mut)]
+    pub penalty_payer: Signer<'info>,
+    #[account(
+        mut,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_heap,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    #[account(mut)]
+    pub bids: AccountLoader<'info, BookSide>,
+    #[account(mut)]
+    pub asks: AccountLoader<'info, BookSide>,
+    #[account(mut)]
+    pub event_heap: AccountLoader<'info, EventHeap>,
+    /// CHECK: receives the crank reward, none leaves it in the penalty pool
+    #[account(mut)]
+    pub crank_reward_receiver: Option<UncheckedAccount<'info>>,
+    pub system_program: Program<'info, System>,
+}
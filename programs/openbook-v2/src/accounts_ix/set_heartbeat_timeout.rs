@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+use crate::state::OpenOrdersAccount;
+
+#[derive(Accounts)]
+pub struct SetHeartbeatTimeout<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+}
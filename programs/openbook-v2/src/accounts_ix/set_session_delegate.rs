@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::error::OpenBookError;
+use crate::state::{OpenOrdersAccount, DELEGATE_ALL_PERMISSIONS};
+
+#[derive(Accounts)]
+pub struct SetSessionDelegate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = open_orders_account.load()?.is_owner_or_delegate(authority.key(), DELEGATE_ALL_PERMISSIONS) @ OpenBookError::NoOwnerOrDelegate
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    pub system_program: Program<'info, System>,
+}
@@ -12,7 +12,7 @@ pub struct SettleFunds<'info> {
     #[account(
         mut,
         has_one = market,
-        constraint = open_orders_account.load()?.is_owner_or_delegate(owner.key()) @ OpenBookError::NoOwnerOrDelegate
+        constraint = open_orders_account.is_owner_or_any_delegate(owner.key(), DELEGATE_SETTLE_FUNDS)? @ OpenBookError::NoOwnerOrDelegate
     )]
     pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
     #[account(
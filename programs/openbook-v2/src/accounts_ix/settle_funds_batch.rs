@@ -0,0 +1,104 @@
+use crate::accounts_ix::SettleFunds;
+use crate::error::OpenBookError;
+use crate::state::*;
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+/// Number of remaining accounts describing each open orders account to settle
+pub const SETTLE_FUNDS_BATCH_GROUP_LEN: usize = 7;
+
+#[derive(Accounts)]
+pub struct SettleFundsBatch<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub penalty_payer: Signer<'info>,
+    #[account(
+        seeds = [b"OpenOrdersIndexer".as_ref(), owner.key().as_ref()],
+        bump = open_orders_indexer.bump,
+    )]
+    pub open_orders_indexer: Account<'info, OpenOrdersIndexer>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleFundsBatch<'info> {
+    /// Build the `SettleFunds` accounts for a group of remaining accounts: the open orders
+    /// account, its market, the market authority, the base and quote vaults and the user's base
+    /// and quote token accounts. Applies the same checks as `SettleFunds`.
+    pub fn to_settle_funds(
+        &self,
+        group: &'info [AccountInfo<'info>],
+    ) -> Result<SettleFunds<'info>> {
+        require_eq!(
+            group.len(),
+            SETTLE_FUNDS_BATCH_GROUP_LEN,
+            OpenBookError::InvalidSettleBatchAccounts
+        );
+
+        let accounts = SettleFunds {
+            owner: self.owner.clone(),
+            penalty_payer: self.penalty_payer.clone(),
+            open_orders_account: AccountLoader::try_from(&group[0])?,
+            market: AccountLoader::try_from(&group[1])?,
+            market_authority: UncheckedAccount::try_from(&group[2]),
+            market_base_vault: Account::try_from(&group[3])?,
+            market_quote_vault: Account::try_from(&group[4])?,
+            user_base_account: Account::try_from(&group[5])?,
+            user_quote_account: Account::try_from(&group[6])?,
+            referrer_account: None,
+            token_program: self.token_program.clone(),
+            system_program: self.system_program.clone(),
+        };
+
+        require!(
+            self.open_orders_indexer
+                .addresses
+                .contains(&accounts.open_orders_account.key()),
+            OpenBookError::InvalidSettleBatchAccounts
+        );
+
+        let open_orders_account = accounts.open_orders_account.load()?;
+        require_keys_eq!(
+            open_orders_account.owner,
+            self.owner.key(),
+            OpenBookError::NoOwner
+        );
+        require_keys_eq!(
+            open_orders_account.market,
+            accounts.market.key(),
+            ErrorCode::ConstraintHasOne
+        );
+
+        let market = accounts.market.load()?;
+        require_keys_eq!(
+            market.market_authority,
+            accounts.market_authority.key(),
+            ErrorCode::ConstraintHasOne
+        );
+        require_keys_eq!(
+            market.market_base_vault,
+            accounts.market_base_vault.key(),
+            ErrorCode::ConstraintHasOne
+        );
+        require_keys_eq!(
+            market.market_quote_vault,
+            accounts.market_quote_vault.key(),
+            ErrorCode::ConstraintHasOne
+        );
+        require_keys_eq!(
+            accounts.user_base_account.mint,
+            accounts.market_base_vault.mint,
+            ErrorCode::ConstraintTokenMint
+        );
+        require_keys_eq!(
+            accounts.user_quote_account.mint,
+            accounts.market_quote_vault.mint,
+            ErrorCode::ConstraintTokenMint
+        );
+
+        drop(market);
+        drop(open_orders_account);
+        Ok(accounts)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct TransferOpenOrdersAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(constraint = new_owner.key() != owner.key())]
+    pub new_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"OpenOrdersIndexer".as_ref(), owner.key().as_ref()],
+        bump = open_orders_indexer.bump,
+        realloc = open_orders_indexer.space_after_remove(),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub open_orders_indexer: Account<'info, OpenOrdersIndexer>,
+    /// CHECK: the new owner's indexer, created if it doesn't exist yet, is parsed in the
+    /// instruction
+    #[account(
+        mut,
+        seeds = [b"OpenOrdersIndexer".as_ref(), new_owner.key().as_ref()],
+        bump,
+    )]
+    pub new_open_orders_indexer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub open_orders_account: AccountLoader<'info, OpenOrdersAccount>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::ZeroCopy;
 use arrayref::array_ref;
+use bytemuck::Pod;
 use std::cell::RefMut;
 use std::{cell::Ref, mem};
 
@@ -322,3 +323,212 @@ impl<'info> LoadMutZeroCopyRef for AccountInfo<'info> {
         }))
     }
 }
+
+//
+// Accounts with a fixed-size header followed by a variable number of items.
+//
+
+/// A zero-copy account that ends in a variable number of items
+///
+/// The account type itself describes the layout at the default capacity, but accounts may be
+/// created with any capacity. They must be accessed through the header and the item slice
+/// instead of casting the whole account data to the account type.
+pub trait DynamicAccount: ZeroCopy + Owner {
+    type Header: Pod;
+    type Item: Pod;
+
+    /// Bytes reserved after the last item
+    const TRAILER_SIZE: usize = 0;
+
+    /// Account size, including the discriminator, for an account holding `capacity` items
+    fn space(capacity: usize) -> usize {
+        8 + mem::size_of::<Self::Header>()
+            + capacity * mem::size_of::<Self::Item>()
+            + Self::TRAILER_SIZE
+    }
+
+    /// Number of items held by an account of `space` bytes, if that is a valid size
+    fn capacity(space: usize) -> Option<usize> {
+        let items_size = space.checked_sub(Self::space(0))?;
+        let item_size = mem::size_of::<Self::Item>();
+        (items_size % item_size == 0).then_some(items_size / item_size)
+    }
+
+    /// Header and items of an account at the default capacity
+    fn split(&self) -> (&Self::Header, &[Self::Item]) {
+        let data = bytemuck::bytes_of(self);
+        let items_end = data.len() - Self::TRAILER_SIZE;
+        let (header, items) = data[..items_end].split_at(mem::size_of::<Self::Header>());
+        (bytemuck::from_bytes(header), bytemuck::cast_slice(items))
+    }
+
+    /// Same as split(), but mut
+    fn split_mut(&mut self) -> (&mut Self::Header, &mut [Self::Item]) {
+        let data = bytemuck::bytes_of_mut(self);
+        let items_end = data.len() - Self::TRAILER_SIZE;
+        let (header, items) = data[..items_end].split_at_mut(mem::size_of::<Self::Header>());
+        (
+            bytemuck::from_bytes_mut(header),
+            bytemuck::cast_slice_mut(items),
+        )
+    }
+}
+
+/// Returns the end of the items in the data of a dynamic account, discriminator included
+fn dynamic_items_end<T: DynamicAccount>(data_len: usize) -> Result<usize> {
+    let capacity = T::capacity(data_len).ok_or(ErrorCode::AccountDidNotDeserialize)?;
+    Ok(T::space(capacity) - T::TRAILER_SIZE)
+}
+
+fn check_discriminator<T: DynamicAccount>(data: &[u8]) -> Result<()> {
+    if data.len() < 8 {
+        return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+    }
+    let disc_bytes = array_ref![data, 0, 8];
+    if disc_bytes != &T::discriminator() {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    Ok(())
+}
+
+/// Splits the data of a dynamic account, discriminator included, into header and items
+pub fn split_dynamic_data<T: DynamicAccount>(data: &[u8]) -> Result<(&T::Header, &[T::Item])> {
+    let items_end = dynamic_items_end::<T>(data.len())?;
+    let (header, items) = data[8..items_end].split_at(mem::size_of::<T::Header>());
+    Ok((bytemuck::from_bytes(header), bytemuck::cast_slice(items)))
+}
+
+/// Same as split_dynamic_data(), but mut
+pub fn split_dynamic_data_mut<T: DynamicAccount>(
+    data: &mut [u8],
+) -> Result<(&mut T::Header, &mut [T::Item])> {
+    let items_end = dynamic_items_end::<T>(data.len())?;
+    let (header, items) = data[8..items_end].split_at_mut(mem::size_of::<T::Header>());
+    Ok((
+        bytemuck::from_bytes_mut(header),
+        bytemuck::cast_slice_mut(items),
+    ))
+}
+
+/// Same as split_dynamic_data_mut(), but keeps the RefMut of the borrowed account data
+pub fn split_dynamic_ref_mut<'a, T: DynamicAccount>(
+    data: RefMut<'a, [u8]>,
+) -> Result<(RefMut<'a, T::Header>, RefMut<'a, [T::Item]>)> {
+    let items_end = dynamic_items_end::<T>(data.len())?;
+    Ok(RefMut::map_split(data, |data| {
+        let (header, items) = data[8..items_end].split_at_mut(mem::size_of::<T::Header>());
+        (
+            bytemuck::from_bytes_mut(header),
+            bytemuck::cast_slice_mut(items),
+        )
+    }))
+}
+
+pub trait LoadDynamicZeroCopy {
+    /// Like LoadZeroCopy::load(), but for accounts of any capacity.
+    /// It checks the account owner, discriminator and size, then splits the data.
+    fn load_dynamic<T: DynamicAccount>(&self) -> Result<(&T::Header, &[T::Item])>;
+}
+
+pub trait LoadDynamicMutZeroCopyRef {
+    /// Like LoadMutZeroCopyRef::load_mut(), but for accounts of any capacity.
+    /// It checks the account owner, discriminator and size, then splits the data.
+    fn load_dynamic_mut<T: DynamicAccount>(&self)
+        -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)>;
+
+    /// Same as load_dynamic_mut(), but requires a zeroed discriminator, for initialization.
+    fn load_dynamic_init<T: DynamicAccount>(
+        &self,
+    ) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)>;
+
+    /// Same as load_dynamic_mut(), but doesn't require the account to be writable. Only for
+    /// dry runs that leave the data untouched, the runtime fails the transaction if a
+    /// read-only account is changed.
+    fn load_dynamic_dry_run<T: DynamicAccount>(
+        &self,
+    ) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)>;
+}
+
+impl<A: AccountReader> LoadDynamicZeroCopy for A {
+    fn load_dynamic<T: DynamicAccount>(&self) -> Result<(&T::Header, &[T::Item])> {
+        if self.owner() != &T::owner() {
+            return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+
+        let data = self.data();
+        check_discriminator::<T>(data)?;
+        split_dynamic_data::<T>(data)
+    }
+}
+
+impl<'info> LoadDynamicMutZeroCopyRef for AccountInfo<'info> {
+    fn load_dynamic_mut<T: DynamicAccount>(
+        &self,
+    ) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)> {
+        if self.owner != &T::owner() {
+            return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+        if !self.is_writable {
+            return Err(ErrorCode::AccountNotMutable.into());
+        }
+
+        let data = self.try_borrow_mut_data()?;
+        check_discriminator::<T>(&data)?;
+        split_dynamic_ref_mut::<T>(RefMut::map(data, |data| &mut **data))
+    }
+
+    fn load_dynamic_init<T: DynamicAccount>(
+        &self,
+    ) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)> {
+        if self.owner != &T::owner() {
+            return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+        if !self.is_writable {
+            return Err(ErrorCode::AccountNotMutable.into());
+        }
+
+        let data = self.try_borrow_mut_data()?;
+        if data.len() < 8 {
+            return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+        }
+        if array_ref![data, 0, 8] != &[0; 8] {
+            return Err(ErrorCode::AccountDiscriminatorAlreadySet.into());
+        }
+        split_dynamic_ref_mut::<T>(RefMut::map(data, |data| &mut **data))
+    }
+
+    fn load_dynamic_dry_run<T: DynamicAccount>(
+        &self,
+    ) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)> {
+        if self.owner != &T::owner() {
+            return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+
+        let data = self.try_borrow_mut_data()?;
+        check_discriminator::<T>(&data)?;
+        split_dynamic_ref_mut::<T>(RefMut::map(data, |data| &mut **data))
+    }
+}
+
+/// Like LoadDynamicMutZeroCopyRef, but for the account type of an AccountLoader
+pub trait DynamicAccountLoader<T: DynamicAccount> {
+    fn load_dynamic_mut(&self) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)>;
+
+    fn load_dynamic_init(&self) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)>;
+
+    fn load_dynamic_dry_run(&self) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)>;
+}
+
+impl<'info, T: DynamicAccount> DynamicAccountLoader<T> for AccountLoader<'info, T> {
+    fn load_dynamic_mut(&self) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)> {
+        self.as_ref().load_dynamic_mut::<T>()
+    }
+
+    fn load_dynamic_init(&self) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)> {
+        self.as_ref().load_dynamic_init::<T>()
+    }
+
+    fn load_dynamic_dry_run(&self) -> Result<(RefMut<T::Header>, RefMut<[T::Item]>)> {
+        self.as_ref().load_dynamic_dry_run::<T>()
+    }
+}
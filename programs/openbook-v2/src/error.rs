@@ -95,6 +95,62 @@ pub enum OpenBookError {
     NonEmptyOpenOrdersPosition,
     #[msg("Fill-Or-Kill order would generate a partial execution")]
     WouldExecutePartially,
+    #[msg("The markets and token accounts don't form a valid two hop route")]
+    InvalidTwoHopRoute,
+    #[msg("The two hop swap would receive less than the minimum amount out")]
+    TwoHopAmountOutBelowMinimum,
+    #[msg("Invalid open orders account capacity")]
+    InvalidOpenOrdersCapacity,
+    #[msg("Invalid book side capacity")]
+    InvalidBookSideCapacity,
+    #[msg("Invalid event heap capacity")]
+    InvalidEventHeapCapacity,
+    #[msg("Depth levels above return data limit")]
+    InvalidInputDepthLevels,
+    #[msg("Amount exceeds the free funds of the position")]
+    InsufficientFreeFunds,
+    #[msg("The remaining accounts don't describe open orders accounts of the owner's indexer")]
+    InvalidSettleBatchAccounts,
+    #[msg("The event heap is full and the maker's open orders account wasn't passed")]
+    EventHeapFull,
+    #[msg("Delegate permissions have unknown bits set")]
+    InvalidDelegatePermissions,
+    #[msg("Order value exceeds the delegate's limit")]
+    DelegateOrderNotionalExceeded,
+    #[msg(
+        "Session expiry must be in the future and within the max session lifetime, or 0 to revoke"
+    )]
+    InvalidInputSessionExpiry,
+    #[msg("No session delegate slot left")]
+    SessionDelegatesFull,
+    #[msg("Risk limits can't be negative and a taker volume limit needs a window")]
+    InvalidInputRiskLimits,
+    #[msg("Open orders on this side exceed the account's base lots limit")]
+    RiskLimitOpenBaseLotsExceeded,
+    #[msg("Order value exceeds the account's limit")]
+    RiskLimitOrderNotionalExceeded,
+    #[msg("Taker volume exceeds the account's limit for the current window")]
+    RiskLimitTakerVolumeExceeded,
+    #[msg("The account's heartbeat timeout hasn't elapsed")]
+    HeartbeatNotMissed,
+    #[msg("The Pyth price update isn't fully verified")]
+    InvalidOracleVerificationLevel,
+    #[msg("The oracle doesn't provide an EMA price")]
+    OracleEmaPriceUnavailable,
+    #[msg("Falling back to oracle b requires a second oracle")]
+    InvalidOracleFallback,
+    #[msg("The crank reward can't exceed the event heap penalty")]
+    InvalidCrankReward,
+    #[msg("The open orders account isn't part of the indexer")]
+    InvalidOpenOrdersIndexerMigration,
+    #[msg("The tokens of the oracle pool don't match the market")]
+    InvalidOraclePoolMints,
+    #[msg("The open orders account has no delegate")]
+    NoDelegate,
+    #[msg("The open orders indexer holds the maximum number of accounts")]
+    OpenOrdersIndexerFull,
+    #[msg("The Pyth price update is for a different feed")]
+    InvalidOracleFeed,
 }
 
 impl From<OpenBookError> for ProgramError {
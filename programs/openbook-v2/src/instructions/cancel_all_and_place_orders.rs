@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use std::cmp;
 
 use crate::accounts_ix::*;
-use crate::accounts_zerocopy::AccountInfoRef;
+use crate::accounts_zerocopy::{AccountInfoRef, DynamicAccountLoader};
 use crate::error::*;
 use crate::state::*;
 use crate::token_utils::*;
@@ -14,7 +14,7 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
     mut orders: Vec<Order>,
     limit: u8,
 ) -> Result<Vec<Option<u128>>> {
-    let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
+    let mut open_orders_account = ctx.accounts.open_orders_account.load_full_mut()?;
     let open_orders_account_pk = ctx.accounts.open_orders_account.key();
 
     let clock = Clock::get()?;
@@ -26,10 +26,10 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
     );
 
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dynamic_mut()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_mut()?.into(),
     };
-    let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut event_heap: EventHeapRefMut = ctx.accounts.event_heap.load_dynamic_mut()?.into();
     let event_heap_size_before = event_heap.len();
 
     let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
@@ -37,10 +37,15 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
     let oracle_price_lots = market.oracle_price_lots(
         AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
         AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
-        clock.slot,
+        &clock,
     )?;
 
+    let signer = ctx.accounts.signer.key();
     if cancel {
+        require!(
+            open_orders_account.is_owner_or_any_delegate(signer, DELEGATE_CANCEL_ORDERS, now_ts),
+            OpenBookError::NoOwnerOrDelegate
+        );
         book.cancel_all_orders(&mut open_orders_account, *market, u8::MAX, None, None)?;
     }
 
@@ -97,6 +102,16 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
             limit,
             ctx.remaining_accounts,
         )?;
+        open_orders_account.check_delegate_order_notional(
+            signer,
+            total_quote_taken_native + posted_quote_native,
+        )?;
+        open_orders_account.check_risk_limits(
+            order.side,
+            total_quote_taken_native + posted_quote_native,
+            total_quote_taken_native,
+            now_ts,
+        )?;
 
         match order.side {
             Side::Bid => {
@@ -117,6 +132,7 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
     }
 
     let position = &mut open_orders_account.position;
+    position.record_heartbeat(now_ts);
 
     let free_base_to_lock = cmp::min(base_amount, position.base_free_native);
     let free_quote_to_lock = cmp::min(quote_amount, position.quote_free_native);
@@ -130,9 +146,7 @@ pub fn cancel_all_and_place_orders<'c: 'info, 'info>(
     market.base_deposit_total += deposit_base_amount;
     market.quote_deposit_total += deposit_quote_amount;
 
-    if event_heap.len() > event_heap_size_before {
-        position.penalty_heap_count += 1;
-    }
+    position.penalty_heap_count += (event_heap.len() - event_heap_size_before) as u64;
 
     token_transfer(
         deposit_quote_amount,
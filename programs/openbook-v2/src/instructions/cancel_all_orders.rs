@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::accounts_ix::*;
+use crate::accounts_zerocopy::DynamicAccountLoader;
 use crate::state::*;
 
 pub fn cancel_all_orders(
@@ -8,12 +9,14 @@ pub fn cancel_all_orders(
     side_option: Option<Side>,
     limit: u8,
 ) -> Result<()> {
-    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    let mut account = ctx.accounts.open_orders_account.load_full_mut()?;
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    account.position.record_heartbeat(now_ts);
 
     let market = ctx.accounts.market.load()?;
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dynamic_mut()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_mut()?.into(),
     };
 
     book.cancel_all_orders(&mut account, *market, limit, side_option, None)?;
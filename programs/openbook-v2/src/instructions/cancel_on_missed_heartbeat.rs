@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::DynamicAccountLoader;
+use crate::error::*;
+use crate::logs::{emit_stack, CancelOnMissedHeartbeatLog};
+use crate::state::*;
+
+pub fn cancel_on_missed_heartbeat(ctx: Context<CancelOnMissedHeartbeat>, limit: u8) -> Result<()> {
+    let mut account = ctx.accounts.open_orders_account.load_full_mut()?;
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    require!(
+        account.position.is_heartbeat_missed(now_ts),
+        OpenBookError::HeartbeatNotMissed
+    );
+
+    let market = ctx.accounts.market.load()?;
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_dynamic_mut()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_mut()?.into(),
+    };
+
+    book.cancel_all_orders(&mut account, *market, limit, None, None)?;
+
+    emit_stack(CancelOnMissedHeartbeatLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        heartbeat_timestamp: account.position.heartbeat_timestamp,
+    });
+
+    Ok(())
+}
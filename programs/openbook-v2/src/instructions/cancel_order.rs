@@ -1,13 +1,16 @@
 use anchor_lang::prelude::*;
 
 use crate::accounts_ix::*;
+use crate::accounts_zerocopy::DynamicAccountLoader;
 use crate::error::*;
 use crate::state::*;
 
 pub fn cancel_order(ctx: Context<CancelOrder>, order_id: u128) -> Result<()> {
     require_gt!(order_id, 0, OpenBookError::InvalidInputOrderId);
 
-    let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
+    let mut open_orders_account = ctx.accounts.open_orders_account.load_full_mut()?;
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    open_orders_account.position.record_heartbeat(now_ts);
     let oo = open_orders_account
         .find_order_with_order_id(order_id)
         .ok_or_else(|| {
@@ -19,8 +22,8 @@ pub fn cancel_order(ctx: Context<CancelOrder>, order_id: u128) -> Result<()> {
 
     let market = ctx.accounts.market.load()?;
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dynamic_mut()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_mut()?.into(),
     };
 
     book.cancel_order(
@@ -1,4 +1,5 @@
 use crate::accounts_ix::*;
+use crate::accounts_zerocopy::DynamicAccountLoader;
 use crate::error::OpenBookError;
 use crate::state::*;
 use anchor_lang::prelude::*;
@@ -12,12 +13,12 @@ pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
     require!(market.is_empty(), OpenBookError::NonEmptyMarket);
 
     let book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dynamic_mut()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_mut()?.into(),
     };
     require!(book.is_empty(), OpenBookError::BookContainsElements);
 
-    let event_heap = ctx.accounts.event_heap.load()?;
+    let event_heap: EventHeapRefMut = ctx.accounts.event_heap.load_dynamic_mut()?.into();
     require!(
         event_heap.is_empty(),
         OpenBookError::EventHeapContainsElements
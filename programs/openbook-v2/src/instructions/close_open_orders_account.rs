@@ -12,13 +12,10 @@ pub fn close_open_orders_account(ctx: Context<CloseOpenOrdersAccount>) -> Result
         OpenBookError::NonEmptyOpenOrdersPosition
     );
 
-    let indexer = &mut ctx.accounts.open_orders_indexer;
-    let index = indexer
-        .addresses
-        .iter()
-        .position(|x| *x == ctx.accounts.open_orders_account.key())
-        .unwrap();
-    indexer.addresses.remove(index);
+    assert!(ctx
+        .accounts
+        .open_orders_indexer
+        .remove(&ctx.accounts.open_orders_account.key()));
 
     Ok(())
 }
@@ -4,8 +4,10 @@ use itertools::Itertools;
 
 use crate::error::OpenBookError;
 use crate::state::*;
+use crate::token_utils::*;
 
 use crate::accounts_ix::*;
+use crate::accounts_zerocopy::DynamicAccountLoader;
 
 // Max events to consume per ix.
 pub const MAX_EVENTS_CONSUME: usize = 8;
@@ -31,7 +33,7 @@ macro_rules! load_open_orders_account {
                 ooa
             }
         };
-        let mut $name = loader.load_mut()?;
+        let mut $name = loader.load_full_mut()?;
     };
 }
 
@@ -43,7 +45,7 @@ pub fn consume_events<'c: 'info, 'info>(
     let limit = std::cmp::min(limit, MAX_EVENTS_CONSUME);
 
     let mut market = ctx.accounts.market.load_mut()?;
-    let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut event_heap: EventHeapRefMut = ctx.accounts.event_heap.load_dynamic_mut()?.into();
     let remaining_accs = &ctx.remaining_accounts;
 
     let slots_to_consume = slots
@@ -55,6 +57,7 @@ pub fn consume_events<'c: 'info, 'info>(
         .take(limit)
         .collect_vec();
 
+    let mut consumed_events: u64 = 0;
     for slot in slots_to_consume {
         let event = event_heap.at_slot(slot).unwrap();
 
@@ -62,17 +65,33 @@ pub fn consume_events<'c: 'info, 'info>(
             EventType::Fill => {
                 let fill: &FillEvent = cast_ref(event);
                 load_open_orders_account!(maker, fill.maker, remaining_accs);
-                maker.execute_maker(&mut market, fill);
+                execute_maker_fill(
+                    &mut market,
+                    &ctx.accounts.market.key(),
+                    &mut maker,
+                    fill,
+                    remaining_accs,
+                )?;
             }
             EventType::Out => {
                 let out: &OutEvent = cast_ref(event);
                 load_open_orders_account!(owner, out.owner, remaining_accs);
-                owner.cancel_order(out.owner_slot as usize, out.quantity, *market);
+                owner.cancel_order(out.owner_slot_index(), out.quantity, *market);
             }
         }
 
         // consume this event
         event_heap.delete_slot(slot)?;
+        consumed_events += 1;
+    }
+
+    if let Some(crank_reward_receiver) = &ctx.accounts.crank_reward_receiver {
+        pay_crank_reward(
+            &mut market,
+            consumed_events,
+            &ctx.accounts.market,
+            crank_reward_receiver,
+        )?;
     }
 
     Ok(())
@@ -18,6 +18,10 @@ pub fn create_market(
     maker_fee: i64,
     taker_fee: i64,
     time_expiry: i64,
+    book_side_capacity: u32,
+    event_heap_capacity: u16,
+    penalty_event_heap: u64,
+    crank_reward: u64,
 ) -> Result<()> {
     let registration_time = Clock::get()?.unix_timestamp;
 
@@ -44,22 +48,67 @@ pub fn create_market(
 
     let oracle_a = ctx.accounts.oracle_a.non_zero_key();
     let oracle_b = ctx.accounts.oracle_b.non_zero_key();
+    let market_mints = (ctx.accounts.base_mint.key(), ctx.accounts.quote_mint.key());
 
-    if oracle_a.is_some() && oracle_b.is_some() {
+    let oracle_type = if oracle_a.is_some() && oracle_b.is_some() {
         let oracle_a = AccountInfoRef::borrow(ctx.accounts.oracle_a.as_ref().unwrap())?;
         let oracle_b = AccountInfoRef::borrow(ctx.accounts.oracle_b.as_ref().unwrap())?;
 
         require_keys_neq!(*oracle_a.key, *oracle_b.key);
+        let oracle_type = oracle::determine_oracle_type(&oracle_a)?;
         require!(
-            oracle::determine_oracle_type(&oracle_a)? == oracle::determine_oracle_type(&oracle_b)?,
+            oracle_type == oracle::determine_oracle_type(&oracle_b)?,
             OpenBookError::InvalidOracleTypes
         );
+
+        // pools of the same type, either each pricing base in quote when falling back or
+        // pricing base and quote in the same token when dividing their prices
+        let pool_mints = (
+            oracle::oracle_pool_mints(&oracle_a)?,
+            oracle::oracle_pool_mints(&oracle_b)?,
+        );
+        if let (Some(mints_a), Some(mints_b)) = pool_mints {
+            let valid_mints = if oracle_config.fallback_to_oracle_b {
+                mints_a == market_mints && mints_b == market_mints
+            } else {
+                mints_a.0 == market_mints.0 && mints_b.0 == market_mints.1 && mints_a.1 == mints_b.1
+            };
+            require!(valid_mints, OpenBookError::InvalidOraclePoolMints);
+        }
+        Some(oracle_type)
     } else if oracle_a.is_some() {
         let oracle_a = AccountInfoRef::borrow(ctx.accounts.oracle_a.as_ref().unwrap())?;
-        oracle::determine_oracle_type(&oracle_a)?;
+        if let Some(mints_a) = oracle::oracle_pool_mints(&oracle_a)? {
+            require!(
+                mints_a == market_mints,
+                OpenBookError::InvalidOraclePoolMints
+            );
+        }
+        Some(oracle::determine_oracle_type(&oracle_a)?)
     } else if oracle_b.is_some() {
         return Err(OpenBookError::InvalidSecondOracle.into());
-    }
+    } else {
+        None
+    };
+
+    require!(
+        !oracle_config.use_ema_price || oracle_type.map_or(true, |t| t.has_ema_price()),
+        OpenBookError::OracleEmaPriceUnavailable
+    );
+    require!(
+        !oracle_config.fallback_to_oracle_b || oracle_b.is_some(),
+        OpenBookError::InvalidOracleFallback
+    );
+
+    // Pyth pull price updates can be posted for any feed, remember the ones the market uses
+    let oracle_a_feed_id = match ctx.accounts.oracle_a.as_ref() {
+        Some(acc) => oracle::pyth_pull_feed_id(&AccountInfoRef::borrow(acc)?)?,
+        None => [0; 32],
+    };
+    let oracle_b_feed_id = match ctx.accounts.oracle_b.as_ref() {
+        Some(acc) => oracle::pyth_pull_feed_id(&AccountInfoRef::borrow(acc)?)?,
+        None => [0; 32],
+    };
 
     let mut openbook_market = ctx.accounts.market.load_init()?;
     *openbook_market = Market {
@@ -98,17 +147,45 @@ pub fn create_market(
         quote_deposit_total: 0,
         fees_available: 0,
         referrer_rebates_accrued: 0,
+        penalty_event_heap,
+        crank_reward,
+        penalty_pool: 0,
 
-        reserved: [0; 128],
+        oracle_a_feed_id,
+        oracle_b_feed_id,
+
+        reserved: [0; 40],
     };
 
+    // Rewards can't outgrow the penalties that fund them
+    require_gte!(
+        openbook_market.heap_penalty_per_event(),
+        crank_reward,
+        OpenBookError::InvalidCrankReward
+    );
+
     let mut orderbook = Orderbook {
-        bids: ctx.accounts.bids.load_init()?,
-        asks: ctx.accounts.asks.load_init()?,
+        bids: ctx.accounts.bids.load_dynamic_init()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_init()?.into(),
     };
+    require_eq!(
+        orderbook.bids.capacity(),
+        book_side_capacity as usize,
+        OpenBookError::InvalidBookSideCapacity
+    );
+    require_eq!(
+        orderbook.asks.capacity(),
+        book_side_capacity as usize,
+        OpenBookError::InvalidBookSideCapacity
+    );
     orderbook.init();
 
-    let mut event_heap = ctx.accounts.event_heap.load_init()?;
+    let mut event_heap: EventHeapRefMut = ctx.accounts.event_heap.load_dynamic_init()?.into();
+    require_eq!(
+        event_heap.capacity(),
+        event_heap_capacity as usize,
+        OpenBookError::InvalidEventHeapCapacity
+    );
     event_heap.init();
 
     emit_cpi!(MarketMetaDataLog {
@@ -120,6 +197,8 @@ pub fn create_market(
         quote_decimals: ctx.accounts.quote_mint.decimals,
         base_lot_size,
         quote_lot_size,
+        use_ema_price: oracle_config.use_ema_price,
+        fallback_to_oracle_b: oracle_config.fallback_to_oracle_b,
     });
 
     Ok(())
@@ -8,11 +8,12 @@ pub fn create_open_orders_account(
     ctx: Context<CreateOpenOrdersAccount>,
     name: String,
 ) -> Result<()> {
-    let mut account = ctx.accounts.open_orders_account.load_init()?;
+    let mut account = ctx.accounts.open_orders_account.load_full_init()?;
     let indexer = &mut ctx.accounts.open_orders_indexer;
-    indexer
-        .addresses
-        .push(ctx.accounts.open_orders_account.key());
+    indexer.add(
+        ctx.accounts.market.key(),
+        ctx.accounts.open_orders_account.key(),
+    );
     indexer.created_counter += 1;
 
     account.name = fill_from_str(&name)?;
@@ -23,6 +24,7 @@ pub fn create_open_orders_account(
     account.delegate = ctx.accounts.delegate_account.non_zero_key();
     account.version = 1;
     account.open_orders = [OpenOrder::default(); MAX_OPEN_ORDERS];
+    account.extra_orders.fill(OpenOrder::default());
 
     Ok(())
 }
@@ -1,6 +1,6 @@
 use crate::accounts_ix::*;
 use crate::error::*;
-use crate::state::Order;
+use crate::state::{OpenOrdersLoader, Order, DELEGATE_CANCEL_ORDERS};
 use anchor_lang::prelude::*;
 
 pub fn edit_order<'c: 'info, 'info>(
@@ -16,6 +16,13 @@ pub fn edit_order<'c: 'info, 'info>(
         OpenBookError::InvalidInputCancelSize
     );
 
+    require!(
+        ctx.accounts
+            .open_orders_account
+            .is_owner_or_any_delegate(ctx.accounts.signer.key(), DELEGATE_CANCEL_ORDERS)?,
+        OpenBookError::NoOwnerOrDelegate
+    );
+
     let leaf_node_quantity = crate::instructions::cancel_order_by_client_order_id(
         Context::new(
             ctx.program_id,
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::{AccountInfoRef, LoadDynamicZeroCopy};
+use crate::state::*;
+use crate::{L2Depth, L2Level};
+
+pub fn get_l2_depth(ctx: Context<GetL2Depth>, max_levels: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
+
+    let market = ctx.accounts.market.load()?;
+    let oracle_price_lots = market.oracle_price_lots(
+        AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
+        AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
+        &clock,
+    )?;
+
+    let levels = |book_side: &AccountLoader<BookSide>| -> Result<Vec<L2Level>> {
+        let account = AccountInfoRef::borrow(book_side.as_ref())?;
+        let book_side: BookSideView<_, _> = account.load_dynamic::<BookSide>()?.into();
+        Ok(book_side
+            .price_levels(now_ts, oracle_price_lots, max_levels.into())
+            .into_iter()
+            .map(|(price_lots, base_lots)| L2Level {
+                price_lots,
+                base_lots,
+            })
+            .collect())
+    };
+
+    let depth = L2Depth {
+        bids: levels(&ctx.accounts.bids)?,
+        asks: levels(&ctx.accounts.asks)?,
+    };
+    set_return_data(&depth.try_to_vec()?);
+
+    Ok(())
+}
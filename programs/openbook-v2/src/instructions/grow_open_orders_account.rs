@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn grow_open_orders_account(ctx: Context<GrowOpenOrdersAccount>, capacity: u16) -> Result<()> {
+    let account_info = ctx.accounts.open_orders_account.to_account_info();
+    // risk limits and session delegates are stored at the end and need to be moved past the
+    // new slots
+    let (old_capacity, trailing_space, risk_limits, session_delegates) = {
+        let account = ctx.accounts.open_orders_account.load_full_mut()?;
+        (
+            account.capacity(),
+            account.trailing_space(),
+            account.risk_limits.to_vec(),
+            account.session_delegates.to_vec(),
+        )
+    };
+    let new_capacity = capacity as usize;
+    require_gt!(
+        new_capacity,
+        old_capacity,
+        OpenBookError::InvalidOpenOrdersCapacity
+    );
+    require_gte!(
+        old_capacity + MAX_OPEN_ORDERS_GROWTH,
+        new_capacity,
+        OpenBookError::InvalidOpenOrdersCapacity
+    );
+
+    let new_space = OpenOrdersAccount::space_with_capacity(new_capacity) + trailing_space;
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_space)
+        .saturating_sub(account_info.lamports());
+    system_program_transfer(
+        rent_needed,
+        &ctx.accounts.system_program,
+        &ctx.accounts.payer,
+        &ctx.accounts.open_orders_account,
+    )?;
+    account_info.realloc(new_space, false)?;
+
+    let mut account = ctx.accounts.open_orders_account.load_full_mut()?;
+    for slot in old_capacity..new_capacity {
+        *account.open_order_mut_by_raw_index(slot) = OpenOrder::default();
+    }
+    account.risk_limits.copy_from_slice(&risk_limits);
+    account
+        .session_delegates
+        .copy_from_slice(&session_delegates);
+
+    Ok(())
+}
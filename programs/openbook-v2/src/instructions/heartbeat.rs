@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+
+pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    account.position.record_heartbeat(now_ts);
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::state::*;
+
+pub fn migrate_open_orders_indexer<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, MigrateOpenOrdersIndexer<'info>>,
+) -> Result<()> {
+    let indexer = &mut ctx.accounts.open_orders_indexer;
+    indexer.record_markets();
+
+    for ai in ctx.remaining_accounts.iter() {
+        let loader = AccountLoader::<OpenOrdersAccount>::try_from(ai)?;
+        let market = loader.load()?.market;
+        require!(
+            indexer.backfill_market(ai.key, market),
+            OpenBookError::InvalidOpenOrdersIndexerMigration
+        );
+    }
+
+    Ok(())
+}
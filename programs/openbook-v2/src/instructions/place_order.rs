@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use std::cmp;
 
 use crate::accounts_ix::*;
-use crate::accounts_zerocopy::AccountInfoRef;
+use crate::accounts_zerocopy::{AccountInfoRef, DynamicAccountLoader};
 use crate::error::*;
 use crate::state::*;
 use crate::token_utils::*;
@@ -20,7 +20,7 @@ pub fn place_order<'c: 'info, 'info>(
         OpenBookError::InvalidInputLots
     );
 
-    let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
+    let mut open_orders_account = ctx.accounts.open_orders_account.load_full_mut()?;
     let open_orders_account_pk = ctx.accounts.open_orders_account.key();
 
     let clock = Clock::get()?;
@@ -37,10 +37,10 @@ pub fn place_order<'c: 'info, 'info>(
     );
 
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dynamic_mut()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_mut()?.into(),
     };
-    let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut event_heap: EventHeapRefMut = ctx.accounts.event_heap.load_dynamic_mut()?.into();
     let event_heap_size_before = event_heap.len();
 
     let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
@@ -48,7 +48,7 @@ pub fn place_order<'c: 'info, 'info>(
     let oracle_price_lots = market.oracle_price_lots(
         AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
         AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
-        clock.slot,
+        &clock,
     )?;
 
     let OrderWithAmounts {
@@ -72,8 +72,19 @@ pub fn place_order<'c: 'info, 'info>(
         limit,
         ctx.remaining_accounts,
     )?;
+    open_orders_account.check_delegate_order_notional(
+        ctx.accounts.signer.key(),
+        total_quote_taken_native + posted_quote_native,
+    )?;
+    open_orders_account.check_risk_limits(
+        order.side,
+        total_quote_taken_native + posted_quote_native,
+        total_quote_taken_native,
+        now_ts,
+    )?;
 
     let position = &mut open_orders_account.position;
+    position.record_heartbeat(now_ts);
     let deposit_amount = match order.side {
         Side::Bid => {
             let free_quote = position.quote_free_native;
@@ -105,9 +116,7 @@ pub fn place_order<'c: 'info, 'info>(
         }
     };
 
-    if event_heap.len() > event_heap_size_before {
-        position.penalty_heap_count += 1;
-    }
+    position.penalty_heap_count += (event_heap.len() - event_heap_size_before) as u64;
 
     token_transfer(
         deposit_amount,
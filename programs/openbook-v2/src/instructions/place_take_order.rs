@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::accounts_ix::*;
-use crate::accounts_zerocopy::AccountInfoRef;
+use crate::accounts_zerocopy::{AccountInfoRef, DynamicAccountLoader};
 use crate::error::*;
 use crate::state::*;
 use crate::token_utils::*;
@@ -28,11 +28,11 @@ pub fn place_take_order<'c: 'info, 'info>(
     );
 
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dynamic_mut()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_mut()?.into(),
     };
 
-    let mut event_heap = ctx.accounts.event_heap.load_mut()?;
+    let mut event_heap: EventHeapRefMut = ctx.accounts.event_heap.load_dynamic_mut()?.into();
     let event_heap_size_before = event_heap.len();
 
     let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
@@ -40,7 +40,7 @@ pub fn place_take_order<'c: 'info, 'info>(
     let oracle_price_lots = market.oracle_price_lots(
         AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
         AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
-        clock.slot,
+        &clock,
     )?;
 
     let side = order.side;
@@ -82,18 +82,18 @@ pub fn place_take_order<'c: 'info, 'info>(
         }
     };
 
+    let penalty = market.collect_heap_penalty((event_heap.len() - event_heap_size_before) as u64);
+
     let seeds = market_seeds!(market, ctx.accounts.market.key());
 
     drop(market);
 
-    if event_heap.len() > event_heap_size_before {
-        system_program_transfer(
-            PENALTY_EVENT_HEAP,
-            &ctx.accounts.system_program,
-            &ctx.accounts.penalty_payer,
-            &ctx.accounts.market,
-        )?;
-    }
+    system_program_transfer(
+        penalty,
+        &ctx.accounts.system_program,
+        &ctx.accounts.penalty_payer,
+        &ctx.accounts.market,
+    )?;
 
     let (user_deposit_acc, user_withdraw_acc, market_deposit_acc, market_withdraw_acc) = match side
     {
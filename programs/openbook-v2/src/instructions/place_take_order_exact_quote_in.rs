@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::instructions::place_take_order;
+use crate::state::*;
+
+pub fn place_take_order_exact_quote_in<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, PlaceTakeOrder<'info>>,
+    params: OrderParams,
+    quote_amount_native: u64,
+    limit: u8,
+) -> Result<()> {
+    let (max_base_lots, max_quote_lots_including_fees) = {
+        let market = ctx.accounts.market.load()?;
+        let max_quote_lots = market.max_quote_lots_net_of_taker_fees(quote_amount_native);
+        require_gt!(max_quote_lots, 0, OpenBookError::InvalidInputLots);
+        (
+            market.max_base_lots(),
+            market.add_taker_fees_ceil(max_quote_lots),
+        )
+    };
+
+    // The budget is computed in native units, so the quote lots left after subtracting the taker
+    // fees while matching cost at most quote_amount_native including fees. Only the spent
+    // amount is transferred, the unspent remainder stays in the user's token account.
+    let order = Order {
+        side: Side::Bid,
+        max_base_lots,
+        max_quote_lots_including_fees,
+        client_order_id: 0,
+        time_in_force: 0,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        params,
+    };
+
+    place_take_order(ctx, order, limit)
+}
@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::{AccountInfoRef, DynamicAccountLoader};
+use crate::error::*;
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn place_take_order_two_hop<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, PlaceTakeOrderTwoHop<'info>>,
+    amount_in_native: u64,
+    min_amount_out_native: u64,
+    limit: u8,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let (hop_1, hop_2) = (&accounts.hop_1, &accounts.hop_2);
+
+    require_keys_neq!(
+        hop_1.market.key(),
+        hop_2.market.key(),
+        OpenBookError::InvalidTwoHopRoute
+    );
+
+    let (side_1, intermediate_mint) = hop_route(hop_1, &accounts.user_input_account.mint)?;
+    let (side_2, output_mint) = hop_route(hop_2, &intermediate_mint)?;
+    require_keys_eq!(
+        output_mint,
+        accounts.user_output_account.mint,
+        OpenBookError::InvalidTwoHopRoute
+    );
+    if let Some(intermediate_account) = &accounts.user_intermediate_account {
+        require_keys_eq!(
+            intermediate_mint,
+            intermediate_account.mint,
+            OpenBookError::InvalidTwoHopRoute
+        );
+    }
+
+    let clock = Clock::get()?;
+    let signer = accounts.signer.key();
+
+    let (deposit_1, withdraw_1, penalty_1) = take_hop(
+        hop_1,
+        side_1,
+        amount_in_native,
+        &clock,
+        &signer,
+        limit,
+        ctx.remaining_accounts,
+    )?;
+
+    // The amount received on the first market is the input of the second one
+    let (deposit_2, withdraw_2, penalty_2) = take_hop(
+        hop_2,
+        side_2,
+        withdraw_1,
+        &clock,
+        &signer,
+        limit,
+        ctx.remaining_accounts,
+    )?;
+
+    require_gte!(
+        withdraw_2,
+        min_amount_out_native,
+        OpenBookError::TwoHopAmountOutBelowMinimum
+    );
+
+    let unspent_intermediate = withdraw_1 - deposit_2;
+
+    for (hop, penalty) in [(hop_1, penalty_1), (hop_2, penalty_2)] {
+        system_program_transfer(
+            penalty,
+            &accounts.system_program,
+            &accounts.penalty_payer,
+            &hop.market,
+        )?;
+    }
+
+    let (hop_1_deposit_vault, hop_1_withdraw_vault) = hop_vaults(hop_1, side_1);
+    let (hop_2_deposit_vault, hop_2_withdraw_vault) = hop_vaults(hop_2, side_2);
+
+    token_transfer(
+        deposit_1,
+        &accounts.token_program,
+        accounts.user_input_account.as_ref(),
+        hop_1_deposit_vault,
+        &accounts.signer,
+    )?;
+
+    let market_1 = hop_1.market.load()?;
+    let seeds_1 = market_seeds!(market_1, hop_1.market.key());
+    drop(market_1);
+
+    token_transfer_signed(
+        deposit_2,
+        &accounts.token_program,
+        hop_1_withdraw_vault,
+        hop_2_deposit_vault,
+        &hop_1.market_authority,
+        seeds_1,
+    )?;
+
+    if unspent_intermediate > 0 {
+        let intermediate_account =
+            accounts.user_intermediate_account.as_ref().ok_or_else(|| {
+                error_msg_typed!(
+                    OpenBookError::InvalidTwoHopRoute,
+                    "missing intermediate account for {unspent_intermediate} unspent native"
+                )
+            })?;
+        token_transfer_signed(
+            unspent_intermediate,
+            &accounts.token_program,
+            hop_1_withdraw_vault,
+            intermediate_account.as_ref(),
+            &hop_1.market_authority,
+            seeds_1,
+        )?;
+    }
+
+    let market_2 = hop_2.market.load()?;
+    let seeds_2 = market_seeds!(market_2, hop_2.market.key());
+    drop(market_2);
+
+    token_transfer_signed(
+        withdraw_2,
+        &accounts.token_program,
+        hop_2_withdraw_vault,
+        accounts.user_output_account.as_ref(),
+        &hop_2.market_authority,
+        seeds_2,
+    )?;
+
+    Ok(())
+}
+
+/// Returns the side of a take order paying with `input_mint` on the hop's market
+/// and the mint received in exchange.
+fn hop_route(hop: &TakeOrderHop, input_mint: &Pubkey) -> Result<(Side, Pubkey)> {
+    let base_mint = hop.market_base_vault.mint;
+    let quote_mint = hop.market_quote_vault.mint;
+    if input_mint == &base_mint {
+        Ok((Side::Ask, quote_mint))
+    } else if input_mint == &quote_mint {
+        Ok((Side::Bid, base_mint))
+    } else {
+        Err(error_msg_typed!(
+            OpenBookError::InvalidTwoHopRoute,
+            "mint {input_mint} is not traded on market {}",
+            hop.market.key()
+        ))
+    }
+}
+
+/// Returns the vaults receiving and paying out tokens for a take order on `side`.
+fn hop_vaults<'a, 'info>(
+    hop: &'a TakeOrderHop<'info>,
+    side: Side,
+) -> (
+    &'a Account<'info, TokenAccount>,
+    &'a Account<'info, TokenAccount>,
+) {
+    match side {
+        Side::Bid => (
+            hop.market_quote_vault.as_ref(),
+            hop.market_base_vault.as_ref(),
+        ),
+        Side::Ask => (
+            hop.market_base_vault.as_ref(),
+            hop.market_quote_vault.as_ref(),
+        ),
+    }
+}
+
+/// Takes liquidity worth `amount_in_native` on the hop's market.
+///
+/// Returns the native amounts to deposit to and withdraw from the market vaults, and
+/// the heap penalty in lamports owed to the market if events were added to the event heap.
+fn take_hop<'c: 'info, 'info>(
+    hop: &TakeOrderHop<'info>,
+    side: Side,
+    amount_in_native: u64,
+    clock: &Clock,
+    signer: &Pubkey,
+    limit: u8,
+    remaining_accs: &'c [AccountInfo<'info>],
+) -> Result<(u64, u64, u64)> {
+    let mut market = hop.market.load_mut()?;
+    require!(
+        !market.is_expired(clock.unix_timestamp),
+        OpenBookError::MarketHasExpired
+    );
+
+    let mut book = Orderbook {
+        bids: hop.bids.load_dynamic_mut()?.into(),
+        asks: hop.asks.load_dynamic_mut()?.into(),
+    };
+
+    let mut event_heap: EventHeapRefMut = hop.event_heap.load_dynamic_mut()?.into();
+    let event_heap_size_before = event_heap.len();
+
+    let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
+
+    let oracle_price_lots = market.oracle_price_lots(
+        AccountInfoRef::borrow_some(hop.oracle_a.as_ref())?.as_ref(),
+        AccountInfoRef::borrow_some(hop.oracle_b.as_ref())?.as_ref(),
+        clock,
+    )?;
+
+    let (max_base_lots, max_quote_lots_including_fees) = match side {
+        Side::Bid => (
+            market.max_base_lots(),
+            market.max_quote_lots_from_lamports(amount_in_native),
+        ),
+        Side::Ask => (
+            market.max_base_lots_from_lamports(amount_in_native),
+            market.max_quote_lots(),
+        ),
+    };
+
+    let order = Order {
+        side,
+        max_base_lots,
+        max_quote_lots_including_fees,
+        client_order_id: 0,
+        time_in_force: 0,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        params: OrderParams::Market,
+    };
+
+    let OrderWithAmounts {
+        total_base_taken_native,
+        total_quote_taken_native,
+        referrer_amount,
+        taker_fees,
+        ..
+    } = book.new_order(
+        &order,
+        &mut market,
+        &hop.market.key(),
+        &mut event_heap,
+        oracle_price_lots,
+        None,
+        signer,
+        now_ts,
+        limit,
+        remaining_accs,
+    )?;
+
+    // take orders don't pay to referrers
+    let makers_rebates = taker_fees - referrer_amount;
+
+    let (deposit_amount, withdraw_amount) = match side {
+        Side::Bid => {
+            let total_quote_including_fees = total_quote_taken_native + makers_rebates;
+            market.base_deposit_total -= total_base_taken_native;
+            market.quote_deposit_total += total_quote_including_fees;
+            (total_quote_including_fees, total_base_taken_native)
+        }
+        Side::Ask => {
+            let total_quote_discounting_fees = total_quote_taken_native - makers_rebates;
+            market.base_deposit_total += total_base_taken_native;
+            market.quote_deposit_total -= total_quote_discounting_fees;
+            (total_base_taken_native, total_quote_discounting_fees)
+        }
+    };
+
+    let penalty = market.collect_heap_penalty((event_heap.len() - event_heap_size_before) as u64);
+
+    Ok((deposit_amount, withdraw_amount, penalty))
+}
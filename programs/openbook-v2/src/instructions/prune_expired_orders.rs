@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use bytemuck::cast;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::DynamicAccountLoader;
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn prune_expired_orders<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, PruneExpiredOrders<'info>>,
+    limit: u8,
+) -> Result<()> {
+    let mut market = ctx.accounts.market.load_mut()?;
+    let mut event_heap: EventHeapRefMut = ctx.accounts.event_heap.load_dynamic_mut()?.into();
+    let event_heap_size_before = event_heap.len();
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_dynamic_mut()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_mut()?.into(),
+    };
+    let remaining_accs = ctx.remaining_accounts;
+
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+
+    let mut pruned_orders: u64 = 0;
+    for side in [Side::Bid, Side::Ask] {
+        let bookside = book.bookside_mut(side);
+        while pruned_orders < limit as u64 {
+            let Some((component, expired_order)) = bookside.find_earliest_expired(now_ts) else {
+                break;
+            };
+            let maker = remaining_accs
+                .iter()
+                .find(|ai| ai.key == &expired_order.owner);
+
+            // makers missing from the remaining accounts are refunded through the event heap
+            if maker.is_none() && event_heap.is_full() {
+                break;
+            }
+            bookside.remove_by_key(component, expired_order.key);
+
+            if let Some(maker) = maker {
+                let ooa: AccountLoader<OpenOrdersAccount> = AccountLoader::try_from(maker)?;
+                let mut maker = ooa.load_full_mut()?;
+                maker.cancel_order(
+                    expired_order.owner_slot_index(),
+                    expired_order.quantity,
+                    *market,
+                );
+            } else {
+                let event = OutEvent::new(
+                    side,
+                    expired_order.owner_slot_index() as u16,
+                    now_ts,
+                    event_heap.header.seq_num,
+                    expired_order.owner,
+                    expired_order.quantity,
+                );
+                event_heap.push_back(cast(event));
+            }
+            pruned_orders += 1;
+        }
+    }
+
+    let penalty = market.collect_heap_penalty((event_heap.len() - event_heap_size_before) as u64);
+    if let Some(crank_reward_receiver) = &ctx.accounts.crank_reward_receiver {
+        pay_crank_reward(
+            &mut market,
+            pruned_orders,
+            &ctx.accounts.market,
+            crank_reward_receiver,
+        )?;
+    }
+
+    drop(market);
+
+    system_program_transfer(
+        penalty,
+        &ctx.accounts.system_program,
+        &ctx.accounts.penalty_payer,
+        &ctx.accounts.market,
+    )?;
+
+    Ok(())
+}
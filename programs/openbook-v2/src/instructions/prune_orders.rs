@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
 
 use crate::accounts_ix::*;
+use crate::accounts_zerocopy::DynamicAccountLoader;
 use crate::error::*;
 use crate::state::*;
 
 pub fn prune_orders(ctx: Context<PruneOrders>, limit: u8) -> Result<()> {
-    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    let mut account = ctx.accounts.open_orders_account.load_full_mut()?;
     let market = ctx.accounts.market.load()?;
 
     require!(
@@ -14,8 +15,8 @@ pub fn prune_orders(ctx: Context<PruneOrders>, limit: u8) -> Result<()> {
     );
 
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_dynamic_mut()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_mut()?.into(),
     };
 
     book.cancel_all_orders(&mut account, *market, limit, None, None)?;
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::{AccountInfoRef, DynamicAccountLoader};
+use crate::error::*;
+use crate::state::*;
+
+pub fn quote_take_order<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, QuoteTakeOrder<'info>>,
+    order: Order,
+    limit: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
+
+    let market = ctx.accounts.market.load()?;
+    require!(
+        !market.is_expired(clock.unix_timestamp),
+        OpenBookError::MarketHasExpired
+    );
+
+    // The accounts are read-only, quote_order only does a dry run that doesn't change them
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_dynamic_dry_run()?.into(),
+        asks: ctx.accounts.asks.load_dynamic_dry_run()?.into(),
+    };
+    let mut event_heap: EventHeapRefMut = ctx.accounts.event_heap.load_dynamic_dry_run()?.into();
+
+    let oracle_price_lots = market.oracle_price_lots(
+        AccountInfoRef::borrow_some(ctx.accounts.oracle_a.as_ref())?.as_ref(),
+        AccountInfoRef::borrow_some(ctx.accounts.oracle_b.as_ref())?.as_ref(),
+        &clock,
+    )?;
+
+    let quote = book.quote_order(
+        &order,
+        &market,
+        &mut event_heap,
+        oracle_price_lots,
+        now_ts,
+        limit,
+        ctx.remaining_accounts,
+    )?;
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, SetAutoSettleLog};
+
+pub fn set_auto_settle(ctx: Context<SetAutoSettle>, enabled: bool) -> Result<()> {
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    account.position.auto_settle = u8::from(enabled);
+
+    emit_stack(SetAutoSettleLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        enabled,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::OpenBookError;
+use crate::logs::{emit_stack, SetCrankRewardLog};
+
+pub fn set_crank_reward(ctx: Context<SetCrankReward>, crank_reward: u64) -> Result<()> {
+    let mut market = ctx.accounts.market.load_mut()?;
+
+    // Rewards can't outgrow the penalties that fund them
+    require_gte!(
+        market.heap_penalty_per_event(),
+        crank_reward,
+        OpenBookError::InvalidCrankReward
+    );
+    market.crank_reward = crank_reward;
+
+    emit_stack(SetCrankRewardLog {
+        market: ctx.accounts.market.key(),
+        crank_reward,
+    });
+
+    Ok(())
+}
@@ -3,9 +3,10 @@ use anchor_lang::prelude::*;
 use crate::accounts_ix::*;
 use crate::logs::{emit_stack, SetDelegateLog};
 use crate::pubkey_option::NonZeroPubkeyOption;
+use crate::state::*;
 
 pub fn set_delegate(ctx: Context<SetDelegate>) -> Result<()> {
-    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    let mut account = ctx.accounts.open_orders_account.load_full_mut()?;
 
     let delegate_account: NonZeroPubkeyOption = ctx
         .accounts
@@ -14,6 +15,15 @@ pub fn set_delegate(ctx: Context<SetDelegate>) -> Result<()> {
         .map(|account| account.key())
         .into();
 
+    // session delegates and restrictions belong to the previous delegate
+    if account.delegate != delegate_account {
+        account.session_delegates.fill(SessionDelegate::default());
+        account
+            .position
+            .set_delegate_permissions(DELEGATE_ALL_PERMISSIONS);
+        account.position.delegate_max_order_quote_native = 0;
+    }
+
     account.delegate = delegate_account;
 
     emit_stack(SetDelegateLog {
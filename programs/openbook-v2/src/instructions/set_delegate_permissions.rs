@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::OpenBookError;
+use crate::logs::{emit_stack, SetDelegatePermissionsLog};
+
+pub fn set_delegate_permissions(
+    ctx: Context<SetDelegatePermissions>,
+    permissions: u8,
+    max_order_quote_native: u64,
+) -> Result<()> {
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    require!(account.delegate.is_some(), OpenBookError::NoDelegate);
+
+    account.position.set_delegate_permissions(permissions);
+    account.position.delegate_max_order_quote_native = max_order_quote_native;
+
+    emit_stack(SetDelegatePermissionsLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        permissions,
+        max_order_quote_native,
+    });
+
+    Ok(())
+}
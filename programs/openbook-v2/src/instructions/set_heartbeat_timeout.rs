@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, SetHeartbeatTimeoutLog};
+
+pub fn set_heartbeat_timeout(
+    ctx: Context<SetHeartbeatTimeout>,
+    timeout_seconds: u16,
+) -> Result<()> {
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    account.position.heartbeat_timeout_seconds = timeout_seconds;
+    account.position.record_heartbeat(now_ts);
+
+    emit_stack(SetHeartbeatTimeoutLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        timeout_seconds,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, SetRiskLimitsLog};
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn set_risk_limits(
+    ctx: Context<SetRiskLimits>,
+    max_base_lots_per_side: i64,
+    max_order_quote_native: u64,
+    max_taker_quote_native_per_window: u64,
+    window_seconds: u32,
+) -> Result<()> {
+    let has_limits = max_base_lots_per_side > 0
+        || max_order_quote_native > 0
+        || max_taker_quote_native_per_window > 0;
+
+    // the limits are stored between the open order slots and the session delegates, the
+    // account is grown the first time they're set
+    let has_slot = ctx
+        .accounts
+        .open_orders_account
+        .load()?
+        .position
+        .risk_limits_slots
+        > 0;
+    if !has_slot && has_limits {
+        let session_delegates = ctx
+            .accounts
+            .open_orders_account
+            .load_full_mut()?
+            .session_delegates
+            .to_vec();
+
+        let account_info = ctx.accounts.open_orders_account.to_account_info();
+        let new_space = account_info.data_len() + size_of::<RiskLimits>();
+        let rent_needed = Rent::get()?
+            .minimum_balance(new_space)
+            .saturating_sub(account_info.lamports());
+        system_program_transfer(
+            rent_needed,
+            &ctx.accounts.system_program,
+            &ctx.accounts.payer,
+            &ctx.accounts.open_orders_account,
+        )?;
+        account_info.realloc(new_space, false)?;
+
+        ctx.accounts
+            .open_orders_account
+            .load_mut()?
+            .position
+            .risk_limits_slots = 1;
+        ctx.accounts
+            .open_orders_account
+            .load_full_mut()?
+            .session_delegates
+            .copy_from_slice(&session_delegates);
+    }
+
+    let mut account = ctx.accounts.open_orders_account.load_full_mut()?;
+    if let Some(risk_limits) = account.risk_limits.first_mut() {
+        // start a fresh window with the new limits
+        *risk_limits = RiskLimits {
+            max_base_lots_per_side,
+            max_order_quote_native,
+            max_taker_quote_native_per_window,
+            window_seconds,
+            ..RiskLimits::default()
+        };
+    }
+
+    emit_stack(SetRiskLimitsLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        max_base_lots_per_side,
+        max_order_quote_native,
+        max_taker_quote_native_per_window,
+        window_seconds,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::logs::{emit_stack, SetSessionDelegateLog};
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn set_session_delegate(
+    ctx: Context<SetSessionDelegate>,
+    session_key: Pubkey,
+    expiry_timestamp: u64,
+) -> Result<()> {
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    require!(
+        expiry_timestamp == 0
+            || (expiry_timestamp > now_ts
+                && expiry_timestamp <= now_ts + MAX_SESSION_LIFETIME_SECONDS),
+        OpenBookError::InvalidInputSessionExpiry
+    );
+
+    // reuse the key's slot or a stale one, otherwise append a slot at the end of the account
+    let has_slot = {
+        let account = ctx.accounts.open_orders_account.load_full_mut()?;
+        account
+            .session_delegates
+            .iter()
+            .any(|session| session.key == session_key || !session.is_active(now_ts))
+    };
+    if !has_slot && expiry_timestamp != 0 {
+        let slots = ctx
+            .accounts
+            .open_orders_account
+            .load()?
+            .position
+            .session_delegate_slots as usize;
+        require_gt!(
+            MAX_SESSION_DELEGATES,
+            slots,
+            OpenBookError::SessionDelegatesFull
+        );
+
+        let account_info = ctx.accounts.open_orders_account.to_account_info();
+        let new_space = account_info.data_len() + size_of::<SessionDelegate>();
+        let rent_needed = Rent::get()?
+            .minimum_balance(new_space)
+            .saturating_sub(account_info.lamports());
+        system_program_transfer(
+            rent_needed,
+            &ctx.accounts.system_program,
+            &ctx.accounts.payer,
+            &ctx.accounts.open_orders_account,
+        )?;
+        account_info.realloc(new_space, false)?;
+
+        let mut account = ctx.accounts.open_orders_account.load_mut()?;
+        account.position.session_delegate_slots += 1;
+    }
+
+    let mut account = ctx.accounts.open_orders_account.load_full_mut()?;
+    let sessions = &mut account.session_delegates;
+    let slot = sessions
+        .iter()
+        .position(|session| session.key == session_key)
+        .or_else(|| {
+            sessions
+                .iter()
+                .position(|session| !session.is_active(now_ts))
+        });
+    if let Some(slot) = slot {
+        sessions[slot] = if expiry_timestamp == 0 {
+            SessionDelegate::default()
+        } else {
+            SessionDelegate {
+                key: session_key,
+                expiry_timestamp,
+            }
+        };
+    }
+
+    emit_stack(SetSessionDelegateLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        session_key,
+        expiry_timestamp,
+    });
+
+    Ok(())
+}
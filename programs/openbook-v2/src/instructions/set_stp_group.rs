@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, SetStpGroupLog};
+
+pub fn set_stp_group(ctx: Context<SetStpGroup>, stp_group: u16) -> Result<()> {
+    let mut account = ctx.accounts.open_orders_account.load_mut()?;
+    account.stp_group = stp_group;
+
+    emit_stack(SetStpGroupLog {
+        open_orders_account: ctx.accounts.open_orders_account.key(),
+        stp_group,
+    });
+
+    Ok(())
+}
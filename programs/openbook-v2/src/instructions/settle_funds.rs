@@ -8,6 +8,8 @@ use crate::token_utils::*;
 
 pub fn settle_funds<'info>(ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>) -> Result<()> {
     let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    open_orders_account.position.record_heartbeat(now_ts);
     let mut market = ctx.accounts.market.load_mut()?;
 
     let mut roundoff_maker_fees = 0;
@@ -31,19 +33,19 @@ pub fn settle_funds<'info>(ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>)
     market.quote_deposit_total -= pa.quote_free_native;
     market.referrer_rebates_accrued -= pa.referrer_rebates_available;
 
+    let penalty = market.collect_heap_penalty(pa.penalty_heap_count);
+
     let seeds = market_seeds!(market, ctx.accounts.market.key());
 
     drop(market);
 
-    if pa.penalty_heap_count > 0 {
-        system_program_transfer(
-            pa.penalty_heap_count * PENALTY_EVENT_HEAP,
-            &ctx.accounts.system_program,
-            &ctx.accounts.penalty_payer,
-            &ctx.accounts.market,
-        )?;
-        pa.penalty_heap_count = 0;
-    }
+    system_program_transfer(
+        penalty,
+        &ctx.accounts.system_program,
+        &ctx.accounts.penalty_payer,
+        &ctx.accounts.market,
+    )?;
+    pa.penalty_heap_count = 0;
 
     if let Some(referrer_account) = &ctx.accounts.referrer_account {
         token_transfer_signed(
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::OpenBookError;
+
+pub fn settle_funds_batch<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SettleFundsBatch<'info>>,
+) -> Result<()> {
+    let remaining_accs = ctx.remaining_accounts;
+    require!(
+        !remaining_accs.is_empty() && remaining_accs.len() % SETTLE_FUNDS_BATCH_GROUP_LEN == 0,
+        OpenBookError::InvalidSettleBatchAccounts
+    );
+
+    for group in remaining_accs.chunks_exact(SETTLE_FUNDS_BATCH_GROUP_LEN) {
+        crate::instructions::settle_funds(Context::new(
+            ctx.program_id,
+            &mut ctx.accounts.to_settle_funds(group)?,
+            &[],
+            SettleFundsBumps {},
+        ))?;
+    }
+
+    Ok(())
+}
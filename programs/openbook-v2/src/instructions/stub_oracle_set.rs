@@ -8,6 +8,24 @@ pub fn stub_oracle_set(ctx: Context<StubOracleSet>, price: f64) -> Result<()> {
     oracle.price = price;
     oracle.last_update_ts = clock.unix_timestamp;
     oracle.last_update_slot = clock.slot;
+    oracle.deviation = 0.0;
+
+    Ok(())
+}
+
+pub fn stub_oracle_set_test(
+    ctx: Context<StubOracleSet>,
+    price: f64,
+    last_update_slot: u64,
+    last_update_ts: i64,
+    deviation: f64,
+) -> Result<()> {
+    let mut oracle = ctx.accounts.oracle.load_mut()?;
+
+    oracle.price = price;
+    oracle.last_update_ts = last_update_ts;
+    oracle.last_update_slot = last_update_slot;
+    oracle.deviation = deviation;
 
     Ok(())
 }
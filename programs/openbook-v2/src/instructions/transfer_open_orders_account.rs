@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::accounts_ix::*;
+use crate::error::OpenBookError;
+use crate::logs::{emit_stack, TransferOpenOrdersAccountLog};
+use crate::pubkey_option::NonZeroPubkeyOption;
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn transfer_open_orders_account(ctx: Context<TransferOpenOrdersAccount>) -> Result<()> {
+    let open_orders_account_pk = ctx.accounts.open_orders_account.key();
+    let mut account = ctx.accounts.open_orders_account.load_full_mut()?;
+    assert!(ctx
+        .accounts
+        .open_orders_indexer
+        .remove(&open_orders_account_pk));
+    add_to_new_open_orders_indexer(&ctx, account.market, open_orders_account_pk)?;
+
+    // resting orders reference the account itself and stay on the book, while all delegates
+    // of the previous owner lose their authority. Self-trade prevention groups are scoped to
+    // an owner, so the account leaves its group.
+    account.owner = ctx.accounts.new_owner.key();
+    account.stp_group = 0;
+    account.delegate = NonZeroPubkeyOption::default();
+    account
+        .position
+        .set_delegate_permissions(DELEGATE_ALL_PERMISSIONS);
+    account.position.delegate_max_order_quote_native = 0;
+    account.session_delegates.fill(SessionDelegate::default());
+
+    emit_stack(TransferOpenOrdersAccountLog {
+        open_orders_account: open_orders_account_pk,
+        owner: ctx.accounts.owner.key(),
+        new_owner: ctx.accounts.new_owner.key(),
+    });
+
+    Ok(())
+}
+
+/// Add the account to the new owner's indexer, creating the indexer if it doesn't exist yet
+fn add_to_new_open_orders_indexer(
+    ctx: &Context<TransferOpenOrdersAccount>,
+    market: Pubkey,
+    open_orders_account: Pubkey,
+) -> Result<()> {
+    let account_info = ctx.accounts.new_open_orders_indexer.to_account_info();
+    let mut indexer = if account_info.owner == &crate::ID {
+        OpenOrdersIndexer::try_deserialize(&mut &account_info.try_borrow_data()?[..])?
+    } else {
+        let new_owner = ctx.accounts.new_owner.key();
+        let seeds: &[&[u8]] = &[
+            b"OpenOrdersIndexer".as_ref(),
+            new_owner.as_ref(),
+            &[ctx.bumps.new_open_orders_indexer],
+        ];
+        system_program::assign(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Assign {
+                    account_to_assign: account_info.clone(),
+                },
+                &[seeds],
+            ),
+            &crate::ID,
+        )?;
+        OpenOrdersIndexer {
+            bump: ctx.bumps.new_open_orders_indexer,
+            ..OpenOrdersIndexer::default()
+        }
+    };
+
+    require_gt!(
+        MAX_INDEXED_OPEN_ORDERS_ACCOUNTS,
+        indexer.addresses.len(),
+        OpenBookError::OpenOrdersIndexerFull
+    );
+    indexer.add(market, open_orders_account);
+
+    let new_space = OpenOrdersIndexer::space(indexer.addresses.len());
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_space)
+        .saturating_sub(account_info.lamports());
+    system_program_transfer(
+        rent_needed,
+        &ctx.accounts.system_program,
+        &ctx.accounts.payer,
+        &ctx.accounts.new_open_orders_indexer,
+    )?;
+    account_info.realloc(new_space, false)?;
+
+    let mut data = account_info.try_borrow_mut_data()?;
+    indexer.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
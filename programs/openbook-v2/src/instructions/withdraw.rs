@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::logs::{emit_stack, WithdrawLog};
+use crate::state::*;
+use crate::token_utils::*;
+
+pub fn withdraw(ctx: Context<Withdraw>, base_amount: u64, quote_amount: u64) -> Result<()> {
+    let mut open_orders_account = ctx.accounts.open_orders_account.load_mut()?;
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    open_orders_account.position.record_heartbeat(now_ts);
+    let mut market = ctx.accounts.market.load_mut()?;
+
+    let pa = &mut open_orders_account.position;
+    require_gte!(
+        pa.base_free_native,
+        base_amount,
+        OpenBookError::InsufficientFreeFunds
+    );
+    require_gte!(
+        pa.quote_free_native,
+        quote_amount,
+        OpenBookError::InsufficientFreeFunds
+    );
+
+    pa.base_free_native -= base_amount;
+    pa.quote_free_native -= quote_amount;
+    market.base_deposit_total -= base_amount;
+    market.quote_deposit_total -= quote_amount;
+
+    let penalty = market.collect_heap_penalty(pa.penalty_heap_count);
+
+    let seeds = market_seeds!(market, ctx.accounts.market.key());
+
+    drop(market);
+
+    system_program_transfer(
+        penalty,
+        &ctx.accounts.system_program,
+        &ctx.accounts.penalty_payer,
+        &ctx.accounts.market,
+    )?;
+    pa.penalty_heap_count = 0;
+
+    token_transfer_signed(
+        base_amount,
+        &ctx.accounts.token_program,
+        &ctx.accounts.market_base_vault,
+        &ctx.accounts.user_base_account,
+        &ctx.accounts.market_authority,
+        seeds,
+    )?;
+
+    token_transfer_signed(
+        quote_amount,
+        &ctx.accounts.token_program,
+        &ctx.accounts.market_quote_vault,
+        &ctx.accounts.user_quote_account,
+        &ctx.accounts.market_authority,
+        seeds,
+    )?;
+
+    if base_amount > 0 || quote_amount > 0 {
+        emit_stack(WithdrawLog {
+            open_orders_account: ctx.accounts.open_orders_account.key(),
+            signer: ctx.accounts.owner.key(),
+            base_amount,
+            quote_amount,
+        });
+    }
+
+    Ok(())
+}
@@ -26,8 +26,14 @@ pub mod instructions;
 
 use accounts_ix::*;
 use accounts_ix::{StubOracleCreate, StubOracleSet};
+use accounts_zerocopy::DynamicAccount;
 use error::*;
-use state::{OracleConfigParams, Order, OrderParams, PlaceOrderType, SelfTradeBehavior, Side};
+use state::{
+    EventHeap, OracleConfigParams, Order, OrderParams, PlaceOrderType, SelfTradeBehavior, Side,
+    DELEGATE_ALL_PERMISSIONS, MAX_OPEN_ORDERS, MAX_OPEN_ORDERS_CAPACITY,
+    MAX_OPEN_ORDERS_CREATION_CAPACITY, MIN_ORDERTREE_NODES, NO_NODE,
+};
+use static_assertions::const_assert;
 use std::cmp;
 
 #[cfg(all(not(feature = "no-entrypoint"), not(feature = "enable-gpl")))]
@@ -38,6 +44,22 @@ pub mod openbook_v2 {
     use super::*;
 
     /// Create a [`Market`](crate::state::Market) for a given token pair.
+    ///
+    /// The `bids`, `asks` and `event_heap` accounts are created by the client beforehand, sized
+    /// for `book_side_capacity` order tree nodes and `event_heap_capacity` events. See
+    /// [`DynamicAccount::space`](crate::accounts_zerocopy::DynamicAccount::space); the default
+    /// capacities are [`MAX_ORDERTREE_NODES`](crate::state::MAX_ORDERTREE_NODES) and
+    /// [`MAX_NUM_EVENTS`](crate::state::MAX_NUM_EVENTS).
+    ///
+    /// Takers pay `penalty_event_heap` lamports for every event they add to the event heap,
+    /// which fund a reward of `crank_reward` lamports per event consumed. The reward can't
+    /// exceed the penalty. The defaults are [`PENALTY_EVENT_HEAP`](crate::state::PENALTY_EVENT_HEAP),
+    /// which is also used when `penalty_event_heap` is 0, and
+    /// [`CRANK_REWARD`](crate::state::CRANK_REWARD).
+    ///
+    /// AMM pool oracles must trade the market's tokens: a single pool, or each pool when
+    /// falling back to `oracle_b`, has the base as first and the quote as second token. When
+    /// dividing the prices, the base and quote pools share their second token.
     #[allow(clippy::too_many_arguments)]
     pub fn create_market(
         ctx: Context<CreateMarket>,
@@ -48,7 +70,21 @@ pub mod openbook_v2 {
         maker_fee: i64,
         taker_fee: i64,
         time_expiry: i64,
+        book_side_capacity: u32,
+        event_heap_capacity: u16,
+        penalty_event_heap: u64,
+        crank_reward: u64,
     ) -> Result<()> {
+        require_gte!(
+            book_side_capacity as usize,
+            MIN_ORDERTREE_NODES,
+            OpenBookError::InvalidBookSideCapacity
+        );
+        require!(
+            (1..NO_NODE).contains(&event_heap_capacity),
+            OpenBookError::InvalidEventHeapCapacity
+        );
+
         #[cfg(feature = "enable-gpl")]
         instructions::create_market(
             ctx,
@@ -59,6 +95,10 @@ pub mod openbook_v2 {
             maker_fee,
             taker_fee,
             time_expiry,
+            book_side_capacity,
+            event_heap_capacity,
+            penalty_event_heap,
+            crank_reward,
         )?;
         Ok(())
     }
@@ -85,16 +125,60 @@ pub mod openbook_v2 {
         Ok(())
     }
 
-    /// Create an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).
+    /// Backfill the markets of an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer)
+    /// created before it recorded them, growing the account paid by the `payer`.
+    ///
+    /// Pass any of the indexed open orders accounts as remaining accounts, large indexers can
+    /// be migrated over several transactions. Anyone can migrate an indexer.
+    pub fn migrate_open_orders_indexer<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MigrateOpenOrdersIndexer<'info>>,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::migrate_open_orders_indexer(ctx)?;
+        Ok(())
+    }
+
+    /// Create an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) with room for
+    /// `capacity` open orders.
+    ///
+    /// `capacity` must be at least [`MAX_OPEN_ORDERS`](crate::state::MAX_OPEN_ORDERS). As
+    /// accounts created by a program are limited to 10KiB, use
+    /// [`grow_open_orders_account`](crate::openbook_v2::grow_open_orders_account) to go
+    /// beyond [`MAX_OPEN_ORDERS_CREATION_CAPACITY`](crate::state::MAX_OPEN_ORDERS_CREATION_CAPACITY)
+    /// (248) open orders.
     pub fn create_open_orders_account(
         ctx: Context<CreateOpenOrdersAccount>,
         name: String,
+        capacity: u16,
     ) -> Result<()> {
+        require!(
+            (MAX_OPEN_ORDERS..=MAX_OPEN_ORDERS_CREATION_CAPACITY).contains(&(capacity as usize)),
+            OpenBookError::InvalidOpenOrdersCapacity
+        );
+
         #[cfg(feature = "enable-gpl")]
         instructions::create_open_orders_account(ctx, name)?;
         Ok(())
     }
 
+    /// Grow an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) to have room for
+    /// `capacity` open orders, at most [`MAX_OPEN_ORDERS_GROWTH`](crate::state::MAX_OPEN_ORDERS_GROWTH)
+    /// (256) more than it had before.
+    pub fn grow_open_orders_account(
+        ctx: Context<GrowOpenOrdersAccount>,
+        capacity: u16,
+    ) -> Result<()> {
+        require_gte!(
+            MAX_OPEN_ORDERS_CAPACITY,
+            capacity as usize,
+            OpenBookError::InvalidOpenOrdersCapacity
+        );
+
+        #[cfg(feature = "enable-gpl")]
+        instructions::grow_open_orders_account(ctx, capacity)?;
+        Ok(())
+    }
+
     /// Close an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).
     pub fn close_open_orders_account(ctx: Context<CloseOpenOrdersAccount>) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
@@ -441,6 +525,53 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Place a take order buying as much base as possible for `quote_amount_native`,
+    /// taker fees included.
+    ///
+    /// Only the spent quote is transferred from the user, the unspent remainder (e.g. lot
+    /// rounding dust) stays in the user's token account.
+    pub fn place_take_order_exact_quote_in<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, PlaceTakeOrder<'info>>,
+        args: PlaceTakeOrderExactQuoteInArgs,
+    ) -> Result<()> {
+        require_gte!(args.price_lots, 1, OpenBookError::InvalidInputPriceLots);
+
+        let params = match args.order_type {
+            PlaceOrderType::Market => OrderParams::Market,
+            PlaceOrderType::ImmediateOrCancel => OrderParams::ImmediateOrCancel {
+                price_lots: args.price_lots,
+            },
+            _ => return Err(OpenBookError::InvalidInputOrderType.into()),
+        };
+
+        #[cfg(feature = "enable-gpl")]
+        instructions::place_take_order_exact_quote_in(
+            ctx,
+            params,
+            args.quote_amount_native,
+            args.limit,
+        )?;
+        Ok(())
+    }
+
+    /// Swap through two markets sharing a token in a single instruction, e.g. A->USDC->B.
+    ///
+    /// The tokens received from a take order on the first market are used as input of a take
+    /// order on the second one. Fails if less than `min_amount_out_native` would be received.
+    pub fn place_take_order_two_hop<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, PlaceTakeOrderTwoHop<'info>>,
+        args: PlaceTakeOrderTwoHopArgs,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::place_take_order_two_hop(
+            ctx,
+            args.amount_in_native,
+            args.min_amount_out_native,
+            args.limit,
+        )?;
+        Ok(())
+    }
+
     /// Process up to `limit` [events](crate::state::AnyEvent).
     ///
     /// When a user places a 'take' order, they do not know beforehand which
@@ -459,11 +590,20 @@ pub mod openbook_v2 {
     /// it to the maker, and debiting whatever the taker is buying from the
     /// maker and crediting it to the taker. Note that *no tokens are moved*,
     /// these are just debits and credits to each party's [`Position`](crate::state::Position).
+    /// Makers that opted into [`auto_settle`](crate::state::Position::auto_settle) are paid the
+    /// funds credited by the fill directly if the market authority, the vaults, the token program
+    /// and the associated token accounts of the maker's owner are passed as remaining accounts. The same holds
+    /// for fills executed right away while a taker order is matched.
     ///
     /// An `OutEvent` is emitted when a limit order needs to be removed from
     /// the book during a `place_order` invocation, and it is handled by
     /// crediting whatever the maker would have sold (quote token in a bid,
     /// base token in an ask) back to the maker.
+    ///
+    /// The `crank_reward_receiver` is paid [`crank_reward`](crate::state::Market::crank_reward)
+    /// lamports for every processed event, as long as the market's
+    /// [`penalty_pool`](crate::state::Market::penalty_pool) covers it. The reward stays in the
+    /// pool if it would leave the receiver below rent exemption.
     pub fn consume_events<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, ConsumeEvents>,
         limit: usize,
@@ -474,16 +614,19 @@ pub mod openbook_v2 {
     }
 
     /// Process the [events](crate::state::AnyEvent) at the given positions.
+    ///
+    /// All positions must be below the capacity of the market's event heap.
     pub fn consume_given_events<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, ConsumeEvents>,
         slots: Vec<usize>,
     ) -> Result<()> {
+        let heap_capacity = EventHeap::capacity(ctx.accounts.event_heap.as_ref().data_len())
+            .ok_or(ErrorCode::AccountDidNotDeserialize)?;
         require!(
-            slots
-                .iter()
-                .all(|slot| *slot < crate::state::MAX_NUM_EVENTS as usize),
+            slots.iter().all(|slot| *slot < heap_capacity),
             OpenBookError::InvalidInputHeapSlots
         );
+
         #[cfg(feature = "enable-gpl")]
         instructions::consume_events(ctx, slots.len(), Some(slots))?;
         Ok(())
@@ -525,6 +668,18 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Cancel up to `limit` orders of an open orders account whose
+    /// [`heartbeat_timeout_seconds`](crate::state::Position::heartbeat_timeout_seconds) elapsed
+    /// without a heartbeat. Permissionless.
+    pub fn cancel_on_missed_heartbeat(
+        ctx: Context<CancelOnMissedHeartbeat>,
+        limit: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::cancel_on_missed_heartbeat(ctx, limit)?;
+        Ok(())
+    }
+
     /// Deposit a certain amount of `base` and `quote` lamports into one's
     /// [`Position`](crate::state::Position).
     ///
@@ -555,6 +710,18 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Withdraw a certain amount of `base` and `quote` lamports from one's
+    /// [`Position`](crate::state::Position), leaving the rest available for placing orders.
+    ///
+    /// The amounts can't exceed the free funds of the position. Use `settle_funds` to also
+    /// claim referrer rebates. Outstanding event heap penalties are collected from the
+    /// `penalty_payer` like in `settle_funds`.
+    pub fn withdraw(ctx: Context<Withdraw>, base_amount: u64, quote_amount: u64) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::withdraw(ctx, base_amount, quote_amount)?;
+        Ok(())
+    }
+
     /// Withdraw any available tokens.
     pub fn settle_funds<'info>(ctx: Context<'_, '_, '_, 'info, SettleFunds<'info>>) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
@@ -562,6 +729,21 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Withdraw any available tokens of several open orders accounts across markets.
+    ///
+    /// Every open orders account must be listed in the owner's
+    /// [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) and is passed as a group of
+    /// [`SETTLE_FUNDS_BATCH_GROUP_LEN`](crate::accounts_ix::SETTLE_FUNDS_BATCH_GROUP_LEN)
+    /// remaining accounts: the open orders account, its market, the market authority, the base
+    /// and quote vaults and the base and quote token accounts receiving the funds.
+    pub fn settle_funds_batch<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SettleFundsBatch<'info>>,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::settle_funds_batch(ctx)?;
+        Ok(())
+    }
+
     /// Withdraw any available tokens when the market is expired (only
     /// [`close_market_admin`](crate::state::Market::close_market_admin)).
     pub fn settle_funds_expired<'info>(
@@ -579,13 +761,152 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Set the [`crank_reward`](crate::state::Market::crank_reward) paid for every processed
+    /// event, as the market's collect fee admin. It can't exceed the event heap penalty.
+    pub fn set_crank_reward(ctx: Context<SetCrankReward>, crank_reward: u64) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_crank_reward(ctx, crank_reward)?;
+        Ok(())
+    }
+
+    /// Move an open orders account, including its resting orders, to a new owner.
+    ///
+    /// Both owners sign, the account moves between their
+    /// [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer)s. The new owner's indexer is
+    /// created, paid by the `payer`, if it doesn't exist yet. All delegates, including session
+    /// delegates, are removed and the [`stp_group`](crate::state::OpenOrdersAccount::stp_group)
+    /// is reset to none. The balances, auto settle, risk limits and heartbeat timeout carry over
+    /// and can be changed by the new owner.
+    pub fn transfer_open_orders_account(ctx: Context<TransferOpenOrdersAccount>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::transfer_open_orders_account(ctx)?;
+        Ok(())
+    }
+
     /// Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.
+    ///
+    /// A new delegate starts with all permissions and no order limit. Changing or removing the
+    /// delegate revokes all its session delegates.
     pub fn set_delegate(ctx: Context<SetDelegate>) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::set_delegate(ctx)?;
         Ok(())
     }
 
+    /// Restrict the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders
+    /// account.
+    ///
+    /// `permissions` is a bitmask of the `DELEGATE_*` actions the delegate may take, 0 for none
+    /// and `DELEGATE_ALL_PERMISSIONS` for all of them. A non-zero `max_order_quote_native`
+    /// limits the value of the delegate's orders. Both are reset when the delegate changes.
+    pub fn set_delegate_permissions(
+        ctx: Context<SetDelegatePermissions>,
+        permissions: u8,
+        max_order_quote_native: u64,
+    ) -> Result<()> {
+        require_gte!(
+            DELEGATE_ALL_PERMISSIONS,
+            permissions,
+            OpenBookError::InvalidDelegatePermissions
+        );
+
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_delegate_permissions(ctx, permissions, max_order_quote_native)?;
+        Ok(())
+    }
+
+    /// Add, renew or revoke a session delegate of an open orders account, as the owner or a
+    /// [`delegate`](crate::state::OpenOrdersAccount::delegate) with all permissions.
+    ///
+    /// Session delegates have the permissions of the delegate until `expiry_timestamp`, which
+    /// can be at most [`MAX_SESSION_LIFETIME_SECONDS`](crate::state::MAX_SESSION_LIFETIME_SECONDS)
+    /// ahead, 0 revokes the key. They lose their authority when the delegate changes or is
+    /// removed. The account is grown by a slot if needed, paid by the `payer`.
+    pub fn set_session_delegate(
+        ctx: Context<SetSessionDelegate>,
+        session_key: Pubkey,
+        expiry_timestamp: u64,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_session_delegate(ctx, session_key, expiry_timestamp)?;
+        Ok(())
+    }
+
+    /// Update the [`stp_group`](crate::state::OpenOrdersAccount::stp_group) of an open orders
+    /// account. Use 0 to leave the group.
+    ///
+    /// Orders of the other accounts in the group are only recognized when those accounts are
+    /// passed as remaining accounts of the taking instruction, otherwise they match normally.
+    pub fn set_stp_group(ctx: Context<SetStpGroup>, stp_group: u16) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_stp_group(ctx, stp_group)?;
+        Ok(())
+    }
+
+    /// Opt in or out of having maker fills of an open orders account transferred directly to the
+    /// owner's associated token accounts during [`consume_events`](crate::openbook_v2::consume_events).
+    pub fn set_auto_settle(ctx: Context<SetAutoSettle>, enabled: bool) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_auto_settle(ctx, enabled)?;
+        Ok(())
+    }
+
+    /// Let anyone cancel the orders of an open orders account once no heartbeat was recorded
+    /// for `timeout_seconds`, 0 disables it.
+    ///
+    /// Placing, cancelling, settling and withdrawing record heartbeats, as does
+    /// [`heartbeat`](crate::openbook_v2::heartbeat).
+    pub fn set_heartbeat_timeout(
+        ctx: Context<SetHeartbeatTimeout>,
+        timeout_seconds: u16,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_heartbeat_timeout(ctx, timeout_seconds)?;
+        Ok(())
+    }
+
+    /// Record a heartbeat of the owner or a delegate, keeping the orders of the account on the
+    /// book for another heartbeat timeout.
+    pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::heartbeat(ctx)?;
+        Ok(())
+    }
+
+    /// Set the owner's risk limits of an open orders account, enforced whenever an order is
+    /// placed. A limit of 0 disables it.
+    ///
+    /// `max_taker_quote_native_per_window` caps the taker volume within windows of
+    /// `window_seconds`. The account is grown to store the limits the first time they're set,
+    /// paid by the `payer`.
+    pub fn set_risk_limits(
+        ctx: Context<SetRiskLimits>,
+        max_base_lots_per_side: i64,
+        max_order_quote_native: u64,
+        max_taker_quote_native_per_window: u64,
+        window_seconds: u32,
+    ) -> Result<()> {
+        require_gte!(
+            max_base_lots_per_side,
+            0,
+            OpenBookError::InvalidInputRiskLimits
+        );
+        require!(
+            max_taker_quote_native_per_window == 0 || window_seconds > 0,
+            OpenBookError::InvalidInputRiskLimits
+        );
+
+        #[cfg(feature = "enable-gpl")]
+        instructions::set_risk_limits(
+            ctx,
+            max_base_lots_per_side,
+            max_order_quote_native,
+            max_taker_quote_native_per_window,
+            window_seconds,
+        )?;
+        Ok(())
+    }
+
     /// Set market to expired before pruning orders and closing the market (only
     /// [`close_market_admin`](crate::state::Market::close_market_admin)).
     pub fn set_market_expired(ctx: Context<SetMarketExpired>) -> Result<()> {
@@ -602,6 +923,77 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Remove up to `limit` expired orders from the book, earliest expiry first on each side.
+    ///
+    /// Makers whose [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) is passed as a
+    /// remaining account are refunded right away. The other makers are refunded through an
+    /// [`OutEvent`](crate::state::OutEvent), for which the `penalty_payer` pays the event heap
+    /// penalty like a taker would. Pruning stops at the first order that needs an event once the
+    /// heap is full.
+    ///
+    /// The `crank_reward_receiver` is paid the market's
+    /// [`crank_reward`](crate::state::Market::crank_reward) for every removed order.
+    pub fn prune_expired_orders<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, PruneExpiredOrders<'info>>,
+        limit: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::prune_expired_orders(ctx, limit)?;
+        Ok(())
+    }
+
+    /// Match a take order against the book without changing it and return the expected
+    /// [`TakeQuote`](crate::state::TakeQuote) through the return data.
+    ///
+    /// Fails like `place_take_order` would, e.g. for fill or kill orders that can't be filled
+    /// or when the event heap is full. Pass the makers' open orders accounts as remaining
+    /// accounts to quote with the same accounts as the take order.
+    pub fn quote_take_order<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, QuoteTakeOrder<'info>>,
+        args: PlaceTakeOrderArgs,
+    ) -> Result<()> {
+        require_gte!(args.price_lots, 1, OpenBookError::InvalidInputPriceLots);
+
+        let order = Order {
+            side: args.side,
+            max_base_lots: args.max_base_lots,
+            max_quote_lots_including_fees: args.max_quote_lots_including_fees,
+            client_order_id: 0,
+            time_in_force: 0,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            params: match args.order_type {
+                PlaceOrderType::Market => OrderParams::Market,
+                PlaceOrderType::ImmediateOrCancel => OrderParams::ImmediateOrCancel {
+                    price_lots: args.price_lots,
+                },
+                PlaceOrderType::FillOrKill => OrderParams::FillOrKill {
+                    price_lots: args.price_lots,
+                },
+                _ => return Err(OpenBookError::InvalidInputOrderType.into()),
+            },
+        };
+
+        #[cfg(feature = "enable-gpl")]
+        instructions::quote_take_order(ctx, order, args.limit)?;
+        Ok(())
+    }
+
+    /// Return the aggregated price levels of the book as [`L2Depth`] through the return data.
+    ///
+    /// Up to `max_levels` levels per side, best price first. Expired orders are skipped and
+    /// oracle pegged orders are resolved at the current oracle price, or skipped when the
+    /// oracle is not usable.
+    pub fn get_l2_depth(ctx: Context<GetL2Depth>, max_levels: u8) -> Result<()> {
+        require_gte!(
+            MAX_L2_DEPTH_LEVELS,
+            max_levels,
+            OpenBookError::InvalidInputDepthLevels
+        );
+        #[cfg(feature = "enable-gpl")]
+        instructions::get_l2_depth(ctx, max_levels)?;
+        Ok(())
+    }
+
     pub fn stub_oracle_create(ctx: Context<StubOracleCreate>, price: f64) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::stub_oracle_create(ctx, price)?;
@@ -614,11 +1006,33 @@ pub mod openbook_v2 {
         Ok(())
     }
 
+    /// Set the price of a stub oracle, last updated at the current slot and time with no
+    /// deviation.
     pub fn stub_oracle_set(ctx: Context<StubOracleSet>, price: f64) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::stub_oracle_set(ctx, price)?;
         Ok(())
     }
+
+    /// Set every field of a stub oracle read by the price checks, for tests of stale
+    /// (`last_update_slot`, `last_update_ts`) and low confidence (`deviation`) oracles.
+    pub fn stub_oracle_set_test(
+        ctx: Context<StubOracleSet>,
+        price: f64,
+        last_update_slot: u64,
+        last_update_ts: i64,
+        deviation: f64,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::stub_oracle_set_test(
+            ctx,
+            price,
+            last_update_slot,
+            last_update_ts,
+            deviation,
+        )?;
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone)]
@@ -696,6 +1110,55 @@ pub struct PlaceTakeOrderArgs {
     pub limit: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PlaceTakeOrderExactQuoteInArgs {
+    pub price_lots: i64,
+    // Quote amount to spend including taker fees, in native units.
+    pub quote_amount_native: u64,
+    // Only Market and ImmediateOrCancel are allowed.
+    pub order_type: PlaceOrderType,
+    // Maximum number of orders from the book to fill.
+    //
+    // Use this to limit compute used during order matching.
+    // When the limit is reached, processing stops and the instruction succeeds.
+    pub limit: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PlaceTakeOrderTwoHopArgs {
+    // Amount of the input token to sell on the first market, in native units.
+    pub amount_in_native: u64,
+    // Minimum amount of the output token to receive from the second market, in native units.
+    pub min_amount_out_native: u64,
+    // Maximum number of orders from each book to fill.
+    pub limit: u8,
+}
+
+/// Most levels per side returned by `get_l2_depth`, so that both sides fit into the return data
+pub const MAX_L2_DEPTH_LEVELS: u8 = 31;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct L2Level {
+    pub price_lots: i64,
+    // Sum of the quantity of all orders at this price.
+    pub base_lots: i64,
+}
+
+/// Return data of `get_l2_depth`
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct L2Depth {
+    // Best price first.
+    pub bids: Vec<L2Level>,
+    // Best price first.
+    pub asks: Vec<L2Level>,
+}
+const_assert!(
+    2 * 4 + 2 * MAX_L2_DEPTH_LEVELS as usize * std::mem::size_of::<L2Level>()
+        <= anchor_lang::solana_program::program::MAX_RETURN_DATA
+);
+
 // Add security details to explorer.solana.com
 #[cfg(not(feature = "no-entrypoint"))]
 use {default_env::default_env, solana_security_txt::security_txt};
@@ -25,11 +25,19 @@ pub struct DepositLog {
     pub quote_amount: u64,
 }
 
+#[event]
+pub struct WithdrawLog {
+    pub open_orders_account: Pubkey,
+    pub signer: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
 #[event]
 pub struct FillLog {
     pub market: Pubkey,
     pub taker_side: u8, // side from the taker's POV
-    pub maker_slot: u8,
+    pub maker_slot: u16,
     pub maker_out: bool, // true if maker order quantity == 0
     pub timestamp: u64,
     pub seq_num: u64, // note: usize same as u64
@@ -65,6 +73,10 @@ pub struct MarketMetaDataLog {
     pub quote_decimals: u8,
     pub base_lot_size: i64,
     pub quote_lot_size: i64,
+    /// Whether the market follows the oracle's EMA price instead of its spot price
+    pub use_ema_price: bool,
+    /// Whether oracle_b replaces an unusable oracle_a instead of dividing its price
+    pub fallback_to_oracle_b: bool,
 }
 
 #[event]
@@ -82,6 +94,66 @@ pub struct SetDelegateLog {
     pub delegate: Option<Pubkey>,
 }
 
+#[event]
+pub struct SetDelegatePermissionsLog {
+    pub open_orders_account: Pubkey,
+    pub permissions: u8,
+    pub max_order_quote_native: u64,
+}
+
+#[event]
+pub struct TransferOpenOrdersAccountLog {
+    pub open_orders_account: Pubkey,
+    pub owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct SetSessionDelegateLog {
+    pub open_orders_account: Pubkey,
+    pub session_key: Pubkey,
+    pub expiry_timestamp: u64,
+}
+
+#[event]
+pub struct SetAutoSettleLog {
+    pub open_orders_account: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct SetHeartbeatTimeoutLog {
+    pub open_orders_account: Pubkey,
+    pub timeout_seconds: u16,
+}
+
+#[event]
+pub struct CancelOnMissedHeartbeatLog {
+    pub open_orders_account: Pubkey,
+    pub heartbeat_timestamp: u64,
+}
+
+#[event]
+pub struct SetRiskLimitsLog {
+    pub open_orders_account: Pubkey,
+    pub max_base_lots_per_side: i64,
+    pub max_order_quote_native: u64,
+    pub max_taker_quote_native_per_window: u64,
+    pub window_seconds: u32,
+}
+
+#[event]
+pub struct SetStpGroupLog {
+    pub open_orders_account: Pubkey,
+    pub stp_group: u16,
+}
+
+#[event]
+pub struct SetCrankRewardLog {
+    pub market: Pubkey,
+    pub crank_reward: u64,
+}
+
 #[event]
 pub struct SettleFundsLog {
     pub open_orders_account: Pubkey,
@@ -13,8 +13,10 @@ use super::{orderbook, OracleConfig};
 
 // For a 1bps taker fee, set taker_fee to 100, so taker_fee/FEES_SCALE_FACTOR = 10e-4
 pub const FEES_SCALE_FACTOR: i128 = 1_000_000;
-// taker pays 500 lamports penalty for every transaction that adds to the event heap
+// default penalty in lamports a taker pays for every event it adds to the event heap
 pub const PENALTY_EVENT_HEAP: u64 = 500;
+// default reward in lamports paid out of the penalty pool for every consumed event
+pub const CRANK_REWARD: u64 = 100;
 
 #[account(zero_copy)]
 #[derive(Debug)]
@@ -57,6 +59,9 @@ pub struct Market {
 
     /// Oracles account address
     pub oracle_a: NonZeroPubkeyOption,
+    /// Divides the price of oracle_a, e.g. BTC/USD by USDC/USD for a BTC/USDC market. With
+    /// `oracle_config.fallback_to_oracle_b` it is instead a price of the base in quote on its own,
+    /// only used when oracle_a is stale or fails the confidence filter.
     pub oracle_b: NonZeroPubkeyOption,
     /// Oracle configuration
     pub oracle_config: OracleConfig,
@@ -116,7 +121,21 @@ pub struct Market {
     pub market_quote_vault: Pubkey,
     pub quote_deposit_total: u64,
 
-    pub reserved: [u8; 128],
+    /// Lamports a taker pays for every event it adds to the event heap. 0 stands for
+    /// `PENALTY_EVENT_HEAP`, as on markets created before this was configurable.
+    pub penalty_event_heap: u64,
+    /// Lamports paid out of the penalty pool for every event processed by consume_events,
+    /// at most the heap penalty per event
+    pub crank_reward: u64,
+    /// Penalty lamports held by the market account that weren't paid out as crank rewards yet
+    pub penalty_pool: u64,
+
+    /// Pyth pull feeds the price updates of oracle_a and oracle_b must be for, zeroed for
+    /// other oracle types
+    pub oracle_a_feed_id: [u8; 32],
+    pub oracle_b_feed_id: [u8; 32],
+
+    pub reserved: [u8; 40],
 }
 
 const_assert_eq!(
@@ -151,7 +170,11 @@ const_assert_eq!(
     8 +                         // quote_deposit_total
     8 +                         // base_fees_accrued
     8 +                         // referrer_rebates_accrued
-    128 // reserved
+    8 +                         // penalty_event_heap
+    8 +                         // crank_reward
+    8 +                         // penalty_pool
+    2 * 32 +                    // oracle_a_feed_id and oracle_b_feed_id
+    40 // reserved
 );
 const_assert_eq!(size_of::<Market>(), 840);
 const_assert_eq!(size_of::<Market>() % 8, 0);
@@ -167,6 +190,31 @@ impl Market {
         self.time_expiry != 0 && self.time_expiry < timestamp
     }
 
+    /// Lamports a taker pays for every event it adds to the event heap
+    pub fn heap_penalty_per_event(&self) -> u64 {
+        if self.penalty_event_heap == 0 {
+            PENALTY_EVENT_HEAP
+        } else {
+            self.penalty_event_heap
+        }
+    }
+
+    /// Add the penalty for `events` added to the event heap to the penalty pool. Returns the
+    /// lamports to transfer to the market account.
+    pub fn collect_heap_penalty(&mut self, events: u64) -> u64 {
+        let penalty = events * self.heap_penalty_per_event();
+        self.penalty_pool += penalty;
+        penalty
+    }
+
+    /// The crank reward for `events` processed events, as far as the penalty pool covers it
+    ///
+    /// Every event on the heap was paid for with at least the reward, so the pool only
+    /// runs short for events that were added without a penalty.
+    pub fn crank_reward_for(&self, events: u64) -> u64 {
+        (events * self.crank_reward).min(self.penalty_pool)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.base_deposit_total == 0
             && self.quote_deposit_total == 0
@@ -230,9 +278,9 @@ impl Market {
         &self,
         oracle_a_acc: Option<&impl KeyedAccountReader>,
         oracle_b_acc: Option<&impl KeyedAccountReader>,
-        slot: u64,
+        clock: &Clock,
     ) -> Result<Option<i64>> {
-        let oracle_price = self.oracle_price(oracle_a_acc, oracle_b_acc, slot)?;
+        let oracle_price = self.oracle_price(oracle_a_acc, oracle_b_acc, clock)?;
         match oracle_price {
             Some(p) => Ok(Some(self.native_price_to_lot(p)?)),
             None => Ok(None),
@@ -243,12 +291,16 @@ impl Market {
         &self,
         oracle_a_acc: Option<&impl KeyedAccountReader>,
         oracle_b_acc: Option<&impl KeyedAccountReader>,
-        slot: u64,
+        clock: &Clock,
     ) -> Result<Option<I80F48>> {
         if self.oracle_a.is_some() && self.oracle_b.is_some() {
-            self.oracle_price_from_a_and_b(oracle_a_acc.unwrap(), oracle_b_acc.unwrap(), slot)
+            if self.oracle_config.fallback_to_oracle_b() {
+                self.oracle_price_from_a_or_b(oracle_a_acc.unwrap(), oracle_b_acc.unwrap(), clock)
+            } else {
+                self.oracle_price_from_a_and_b(oracle_a_acc.unwrap(), oracle_b_acc.unwrap(), clock)
+            }
         } else if self.oracle_a.is_some() {
-            self.oracle_price_from_a(oracle_a_acc.unwrap(), slot)
+            self.oracle_price_from_a(oracle_a_acc.unwrap(), clock)
         } else {
             Ok(None)
         }
@@ -257,18 +309,43 @@ impl Market {
     fn oracle_price_from_a(
         &self,
         oracle_acc: &impl KeyedAccountReader,
-        now_slot: u64,
+        clock: &Clock,
     ) -> Result<Option<I80F48>> {
         assert_eq!(self.oracle_a, *oracle_acc.key());
-        let oracle = oracle::oracle_state_unchecked(oracle_acc)?;
+        self.oracle_price_from_single(oracle_acc, clock)
+    }
+
+    /// Uses oracle_b on its own if oracle_a is stale or fails the confidence filter
+    fn oracle_price_from_a_or_b(
+        &self,
+        oracle_a_acc: &impl KeyedAccountReader,
+        oracle_b_acc: &impl KeyedAccountReader,
+        clock: &Clock,
+    ) -> Result<Option<I80F48>> {
+        assert_eq!(self.oracle_a, *oracle_a_acc.key());
+        assert_eq!(self.oracle_b, *oracle_b_acc.key());
 
-        if oracle.is_stale(oracle_acc.key(), &self.oracle_config, now_slot)
+        match self.oracle_price_from_single(oracle_a_acc, clock)? {
+            Some(price) => Ok(Some(price)),
+            None => self.oracle_price_from_single(oracle_b_acc, clock),
+        }
+    }
+
+    fn oracle_price_from_single(
+        &self,
+        oracle_acc: &impl KeyedAccountReader,
+        clock: &Clock,
+    ) -> Result<Option<I80F48>> {
+        let oracle = self.oracle_state_unchecked(oracle_acc)?;
+        let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
+
+        if oracle.is_stale(oracle_acc.key(), &self.oracle_config, clock.slot, now_ts)
             || !oracle.has_valid_confidence(oracle_acc.key(), &self.oracle_config)
+            || !oracle.has_min_liquidity(oracle_acc.key(), &self.oracle_config)
         {
             Ok(None)
         } else {
-            let decimals = (self.quote_decimals as i8) - (self.base_decimals as i8);
-            let decimal_adj = oracle::power_of_ten_float(decimals);
+            let decimal_adj = self.oracle_decimal_adj(oracle.oracle_type);
             Ok(I80F48::checked_from_num(oracle.price * decimal_adj))
         }
     }
@@ -277,33 +354,95 @@ impl Market {
         &self,
         oracle_a_acc: &impl KeyedAccountReader,
         oracle_b_acc: &impl KeyedAccountReader,
-        now_slot: u64,
+        clock: &Clock,
     ) -> Result<Option<I80F48>> {
         assert_eq!(self.oracle_a, *oracle_a_acc.key());
         assert_eq!(self.oracle_b, *oracle_b_acc.key());
 
-        let oracle_a = oracle::oracle_state_unchecked(oracle_a_acc)?;
-        let oracle_b = oracle::oracle_state_unchecked(oracle_b_acc)?;
+        let oracle_a = self.oracle_state_unchecked(oracle_a_acc)?;
+        let oracle_b = self.oracle_state_unchecked(oracle_b_acc)?;
+        let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
 
-        if oracle_a.is_stale(oracle_a_acc.key(), &self.oracle_config, now_slot)
-            || oracle_b.is_stale(oracle_b_acc.key(), &self.oracle_config, now_slot)
+        if oracle_a.is_stale(oracle_a_acc.key(), &self.oracle_config, clock.slot, now_ts)
+            || oracle_b.is_stale(oracle_b_acc.key(), &self.oracle_config, clock.slot, now_ts)
             || !oracle_a.has_valid_combined_confidence(&oracle_b, &self.oracle_config)
+            || !oracle_a.has_min_liquidity(oracle_a_acc.key(), &self.oracle_config)
+            || !oracle_b.has_min_liquidity(oracle_b_acc.key(), &self.oracle_config)
         {
             Ok(None)
         } else {
+            // both oracles have the same type, see create_market
             let price = oracle_a.price / oracle_b.price;
-            let decimals = (self.quote_decimals as i8) - (self.base_decimals as i8);
-            let decimal_adj = oracle::power_of_ten_float(decimals);
+            let decimal_adj = self.oracle_decimal_adj(oracle_a.oracle_type);
             Ok(I80F48::checked_from_num(price * decimal_adj))
         }
     }
 
+    /// Oracle state with the spot or EMA price, depending on the oracle config. Pyth pull
+    /// updates must be for the feed recorded at market creation.
+    fn oracle_state_unchecked(
+        &self,
+        oracle_acc: &impl KeyedAccountReader,
+    ) -> Result<oracle::OracleState> {
+        let mut oracle = oracle::oracle_state_unchecked(oracle_acc)?;
+        if oracle.oracle_type == oracle::OracleType::PythPull {
+            let expected_feed_id = if self.oracle_a == *oracle_acc.key() {
+                self.oracle_a_feed_id
+            } else {
+                self.oracle_b_feed_id
+            };
+            require!(
+                oracle::pyth_pull_feed_id(oracle_acc)? == expected_feed_id,
+                OpenBookError::InvalidOracleFeed
+            );
+        }
+        if self.oracle_config.use_ema_price() {
+            oracle.use_ema_price()?;
+        }
+        Ok(oracle)
+    }
+
+    /// Factor converting oracle prices into native quote per native base
+    fn oracle_decimal_adj(&self, oracle_type: oracle::OracleType) -> f64 {
+        if oracle_type.is_native_price() {
+            1f64
+        } else {
+            let decimals = (self.quote_decimals as i8) - (self.base_decimals as i8);
+            oracle::power_of_ten_float(decimals)
+        }
+    }
+
     pub fn subtract_taker_fees(&self, quote: i64) -> i64 {
         ((quote as i128) * FEES_SCALE_FACTOR / (FEES_SCALE_FACTOR + (self.taker_fee as i128)))
             .try_into()
             .unwrap()
     }
 
+    /// Inverse of subtract_taker_fees, rounded up so that subtracting the fees again gives
+    /// back `quote`
+    pub fn add_taker_fees_ceil(&self, quote: i64) -> i64 {
+        let fee_scale = FEES_SCALE_FACTOR + (self.taker_fee as i128);
+        self.ceil_fee_division((quote as i128) * fee_scale)
+            .try_into()
+            .unwrap()
+    }
+
+    /// Most quote lots that can be taken with `lamports` when the rounded up taker fees of
+    /// the taken amount are paid out of `lamports` as well
+    pub fn max_quote_lots_net_of_taker_fees(&self, lamports: u64) -> i64 {
+        let fee_scale = FEES_SCALE_FACTOR + (self.taker_fee as i128);
+        // quote_lots * quote_lot_size * fee_scale <= lamports * FEES_SCALE_FACTOR also bounds
+        // the integer cost quote_native + taker_fees_ceil(quote_native) by lamports
+        let quote_lots =
+            (lamports as i128) * FEES_SCALE_FACTOR / (fee_scale * self.quote_lot_size as i128);
+        std::cmp::min(
+            self.subtract_taker_fees(self.max_quote_lots()) as i128,
+            quote_lots,
+        )
+        .try_into()
+        .unwrap()
+    }
+
     pub fn maker_fees_floor(self, amount: u64) -> u64 {
         if self.maker_fee.is_positive() {
             self.unsigned_maker_fees_floor(amount)
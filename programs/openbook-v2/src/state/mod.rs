@@ -10,4 +10,7 @@ mod open_orders_indexer;
 mod orderbook;
 
 pub mod oracle;
+mod pyth_pull_internal;
 mod raydium_internal;
+mod switchboard_on_demand_internal;
+mod whirlpool_internal;
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
 use derivative::Derivative;
 use static_assertions::const_assert_eq;
+use std::cell::{RefCell, RefMut};
 use std::mem::size_of;
+use std::ops::{Deref, DerefMut};
 
 use crate::logs::{emit_stack, FillLog};
 use crate::pubkey_option::NonZeroPubkeyOption;
@@ -11,6 +14,35 @@ use super::{BookSideOrderTree, FillEvent, LeafNode, Market, Side, SideAndOrderTr
 
 pub const MAX_OPEN_ORDERS: usize = 24;
 
+/// Upper bound for the number of open order slots an account can be created with or grown to.
+///
+/// Slots beyond MAX_OPEN_ORDERS are stored after the fixed part of the account data.
+pub const MAX_OPEN_ORDERS_CAPACITY: usize = 1024;
+
+/// Upper bound for the number of open order slots an account can be created with, as accounts
+/// created by a program are limited to MAX_PERMITTED_DATA_INCREASE bytes.
+pub const MAX_OPEN_ORDERS_CREATION_CAPACITY: usize = MAX_OPEN_ORDERS
+    + (MAX_PERMITTED_DATA_INCREASE - 8 - size_of::<OpenOrdersAccount>()) / size_of::<OpenOrder>();
+const_assert_eq!(MAX_OPEN_ORDERS_CREATION_CAPACITY, 248);
+
+/// Upper bound for the number of open order slots an account can grow by at once, limited by
+/// the realloc limit of the runtime.
+pub const MAX_OPEN_ORDERS_GROWTH: usize = MAX_PERMITTED_DATA_INCREASE / size_of::<OpenOrder>();
+const_assert_eq!(MAX_OPEN_ORDERS_GROWTH, 256);
+
+/// Bits of the permissions a delegate may hold, see [`Position::delegate_permissions`]
+pub const DELEGATE_PLACE_ORDERS: u8 = 1 << 0;
+pub const DELEGATE_CANCEL_ORDERS: u8 = 1 << 1;
+pub const DELEGATE_SETTLE_FUNDS: u8 = 1 << 2;
+pub const DELEGATE_ALL_PERMISSIONS: u8 =
+    DELEGATE_PLACE_ORDERS | DELEGATE_CANCEL_ORDERS | DELEGATE_SETTLE_FUNDS;
+
+/// Upper bound for the number of session delegates of an account
+pub const MAX_SESSION_DELEGATES: usize = 4;
+
+/// Longest time a session delegate can be valid for, one week
+pub const MAX_SESSION_LIFETIME_SECONDS: u64 = 7 * 24 * 60 * 60;
+
 #[account(zero_copy)]
 #[derive(Debug)]
 pub struct OpenOrdersAccount {
@@ -29,7 +61,9 @@ pub struct OpenOrdersAccount {
     // Introducing a version as we are adding a new field bids_quote_lots
     pub version: u8,
 
-    pub padding: [u8; 2],
+    /// Self-trade prevention group, 0 means none. Orders of accounts of the same owner
+    /// sharing a group are subject to the taker's SelfTradeBehavior.
+    pub stp_group: u16,
 
     pub position: Position,
 
@@ -43,7 +77,8 @@ const_assert_eq!(
         + 32
         + 4
         + 1
-        + 3
+        + 1
+        + 2
         + size_of::<Position>()
         + MAX_OPEN_ORDERS * size_of::<OpenOrder>()
 );
@@ -56,6 +91,25 @@ impl OpenOrdersAccount {
         8 + size_of::<OpenOrdersAccount>()
     }
 
+    /// Number of bytes needed for an OpenOrdersAccount with `capacity` open order slots,
+    /// including the discriminator
+    pub fn space_with_capacity(capacity: usize) -> usize {
+        Self::space() + capacity.saturating_sub(MAX_OPEN_ORDERS) * size_of::<OpenOrder>()
+    }
+
+    /// Number of open order slots that fit into an account of `space` bytes, without risk
+    /// limits and session delegate slots
+    pub fn capacity_for_space(space: usize) -> usize {
+        MAX_OPEN_ORDERS + space.saturating_sub(Self::space()) / size_of::<OpenOrder>()
+    }
+
+    /// Number of bytes stored after the open order slots, for the risk limits and the session
+    /// delegates
+    pub fn trailing_space(&self) -> usize {
+        self.position.risk_limits_slots as usize * size_of::<RiskLimits>()
+            + self.position.session_delegate_slots as usize * size_of::<SessionDelegate>()
+    }
+
     pub fn name(&self) -> &str {
         std::str::from_utf8(&self.name)
             .unwrap()
@@ -71,37 +125,224 @@ impl OpenOrdersAccount {
             account_num: 0,
             bump: 0,
             version: 1,
-            padding: [0; 2],
+            stp_group: 0,
             position: Position::default(),
             open_orders: [OpenOrder::default(); MAX_OPEN_ORDERS],
         })
     }
 
-    pub fn is_owner_or_delegate(&self, ix_signer: Pubkey) -> bool {
+    /// Is the signer the owner, or the delegate holding all of the `permissions` bits?
+    pub fn is_owner_or_delegate(&self, ix_signer: Pubkey, permissions: u8) -> bool {
+        self.owner == ix_signer
+            || (self.is_delegate(ix_signer) && self.position.has_delegate_permissions(permissions))
+    }
+
+    pub fn is_delegate(&self, ix_signer: Pubkey) -> bool {
         let delegate_option: Option<Pubkey> = Option::from(self.delegate);
-        if let Some(delegate) = delegate_option {
-            return self.owner == ix_signer || delegate == ix_signer;
+        delegate_option == Some(ix_signer)
+    }
+
+    /// Fails if the delegate signed for an order worth more than its limit
+    pub fn check_delegate_order_notional(
+        &self,
+        ix_signer: Pubkey,
+        order_quote_native: u64,
+    ) -> Result<()> {
+        let max_order_quote_native = self.position.delegate_max_order_quote_native;
+        if self.owner != ix_signer && max_order_quote_native > 0 {
+            require_gte!(
+                max_order_quote_native,
+                order_quote_native,
+                OpenBookError::DelegateOrderNotionalExceeded
+            );
         }
-        self.owner == ix_signer
+        Ok(())
     }
 
+    /// Expects the signer to be checked with `is_owner_or_any_delegate` before
     pub fn is_settle_destination_allowed(&self, ix_signer: Pubkey, account_owner: Pubkey) -> bool {
-        // delegate can withdraw to owner accounts
-        let delegate_option: Option<Pubkey> = Option::from(self.delegate);
-        if Some(ix_signer) == delegate_option {
-            return self.owner == account_owner;
+        // owner can withdraw to anywhere, delegates only to owner accounts
+        ix_signer == self.owner || account_owner == self.owner
+    }
+
+    /// Release funds and apply taker fees to the taker account. Account fees for referrer
+    pub fn execute_taker(
+        &mut self,
+        market: &mut Market,
+        taker_side: Side,
+        base_native: u64,
+        quote_native: u64,
+        taker_fees: u64,
+        referrer_amount: u64,
+    ) {
+        let pa = &mut self.position;
+        match taker_side {
+            Side::Bid => pa.base_free_native += base_native,
+            Side::Ask => pa.quote_free_native += quote_native - taker_fees,
+        };
+
+        pa.taker_volume += quote_native as u128;
+        pa.referrer_rebates_available += referrer_amount;
+        market.referrer_rebates_accrued += referrer_amount;
+
+        emit_stack(OpenOrdersPositionLog {
+            owner: self.owner,
+            open_orders_account_num: self.account_num,
+            market: self.market,
+            bids_base_lots: pa.bids_base_lots,
+            bids_quote_lots: pa.bids_quote_lots,
+            asks_base_lots: pa.asks_base_lots,
+            base_free_native: pa.base_free_native,
+            quote_free_native: pa.quote_free_native,
+            locked_maker_fees: pa.locked_maker_fees,
+            referrer_rebates_available: pa.referrer_rebates_available,
+            maker_volume: pa.maker_volume,
+            taker_volume: pa.taker_volume,
+        })
+    }
+}
+
+/// Mutable access to an OpenOrdersAccount including the open order slots that are stored
+/// after the fixed part of the account data.
+///
+/// Accounts created with a capacity above MAX_OPEN_ORDERS (or grown later) keep the additional
+/// OpenOrder slots in `extra_orders`. Slot indexes continue from the fixed `open_orders`.
+/// They are followed by the `position.risk_limits_slots` risk limits, while the
+/// `position.session_delegate_slots` session delegates are stored at the very end.
+pub struct OpenOrdersAccountRefMut<'a> {
+    pub fixed: RefMut<'a, OpenOrdersAccount>,
+    pub extra_orders: RefMut<'a, [OpenOrder]>,
+    pub risk_limits: RefMut<'a, [RiskLimits]>,
+    pub session_delegates: RefMut<'a, [SessionDelegate]>,
+}
+
+impl<'a> Deref for OpenOrdersAccountRefMut<'a> {
+    type Target = OpenOrdersAccount;
+
+    fn deref(&self) -> &Self::Target {
+        &self.fixed
+    }
+}
+
+impl<'a> DerefMut for OpenOrdersAccountRefMut<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.fixed
+    }
+}
+
+impl<'a> OpenOrdersAccountRefMut<'a> {
+    /// Splits the account data (without discriminator) into the fixed part, the extra slots,
+    /// the risk limits and the session delegates
+    pub fn from_bytes(data: RefMut<'a, [u8]>) -> Result<Self> {
+        let fixed_len = size_of::<OpenOrdersAccount>();
+        require_gte!(
+            data.len(),
+            fixed_len,
+            anchor_lang::error::ErrorCode::AccountDidNotDeserialize
+        );
+        let (risk_limits_len, sessions_len) = {
+            let fixed: &OpenOrdersAccount = bytemuck::from_bytes(&data[..fixed_len]);
+            (
+                fixed.position.risk_limits_slots as usize * size_of::<RiskLimits>(),
+                fixed.position.session_delegate_slots as usize * size_of::<SessionDelegate>(),
+            )
+        };
+        require_gte!(
+            data.len(),
+            fixed_len + risk_limits_len + sessions_len,
+            anchor_lang::error::ErrorCode::AccountDidNotDeserialize
+        );
+        let sessions_start = data.len() - sessions_len;
+        let risk_limits_start = sessions_start - risk_limits_len;
+        let extra_len =
+            (risk_limits_start - fixed_len) / size_of::<OpenOrder>() * size_of::<OpenOrder>();
+        let (rest, session_delegates) =
+            RefMut::map_split(data, |data| data.split_at_mut(sessions_start));
+        let (fixed_and_extra, risk_limits) =
+            RefMut::map_split(rest, |data| data.split_at_mut(risk_limits_start));
+        let (fixed, extra_orders) = RefMut::map_split(fixed_and_extra, |data| {
+            data[..fixed_len + extra_len].split_at_mut(fixed_len)
+        });
+        Ok(Self {
+            fixed: RefMut::map(fixed, bytemuck::from_bytes_mut),
+            extra_orders: RefMut::map(extra_orders, bytemuck::cast_slice_mut),
+            risk_limits: RefMut::map(risk_limits, bytemuck::cast_slice_mut),
+            session_delegates: RefMut::map(session_delegates, bytemuck::cast_slice_mut),
+        })
+    }
+
+    pub fn default_for_tests(capacity: usize) -> OpenOrdersAccountRefMut<'static> {
+        let words = (OpenOrdersAccount::space_with_capacity(capacity) - 8) / 8;
+        let data: &'static RefCell<Vec<u64>> = Box::leak(Box::new(RefCell::new(vec![0; words])));
+        let mut account =
+            OpenOrdersAccountRefMut::from_bytes(RefMut::map(data.borrow_mut(), |d| {
+                bytemuck::cast_slice_mut(d.as_mut_slice())
+            }))
+            .unwrap();
+        *account.fixed = *OpenOrdersAccount::default_for_tests();
+        account.extra_orders.fill(OpenOrder::default());
+        account
+    }
+
+    /// Number of open order slots, including the ones stored past the fixed part
+    pub fn capacity(&self) -> usize {
+        MAX_OPEN_ORDERS + self.extra_orders.len()
+    }
+
+    /// Fails if a just placed order breaks one of the owner's risk limits. Its taken volume is
+    /// added to the current risk window.
+    pub fn check_risk_limits(
+        &mut self,
+        side: Side,
+        order_quote_native: u64,
+        taker_quote_native: u64,
+        now_ts: u64,
+    ) -> Result<()> {
+        let open_base_lots = match side {
+            Side::Bid => self.fixed.position.bids_base_lots,
+            Side::Ask => self.fixed.position.asks_base_lots,
+        };
+        match self.risk_limits.first_mut() {
+            Some(risk_limits) => risk_limits.check(
+                open_base_lots,
+                order_quote_native,
+                taker_quote_native,
+                now_ts,
+            ),
+            None => Ok(()),
         }
+    }
+
+    /// Session delegates act for the delegate, so they're only honoured while there is one
+    pub fn is_session_delegate(&self, ix_signer: Pubkey, now_ts: u64) -> bool {
+        self.delegate.is_some()
+            && self
+                .session_delegates
+                .iter()
+                .any(|session| session.key == ix_signer && session.is_active(now_ts))
+    }
 
-        // owner can withdraw to anywhere
-        ix_signer == self.owner
+    /// Like `is_owner_or_delegate`, but session delegates are accepted too
+    pub fn is_owner_or_any_delegate(
+        &self,
+        ix_signer: Pubkey,
+        permissions: u8,
+        now_ts: u64,
+    ) -> bool {
+        self.is_owner_or_delegate(ix_signer, permissions)
+            || (self.is_session_delegate(ix_signer, now_ts)
+                && self.position.has_delegate_permissions(permissions))
     }
 
     pub fn all_orders(&self) -> impl Iterator<Item = &OpenOrder> {
-        self.open_orders.iter()
+        self.fixed
+            .open_orders
+            .iter()
+            .chain(self.extra_orders.iter())
     }
 
     pub fn has_no_orders(&self) -> bool {
-        self.open_orders.iter().count() == 0
+        self.all_orders().count() == 0
     }
 
     pub fn all_orders_in_use(&self) -> impl Iterator<Item = &OpenOrder> {
@@ -124,11 +365,19 @@ impl OpenOrdersAccount {
     }
 
     pub fn open_order_by_raw_index(&self, raw_index: usize) -> &OpenOrder {
-        &self.open_orders[raw_index]
+        if raw_index < MAX_OPEN_ORDERS {
+            &self.fixed.open_orders[raw_index]
+        } else {
+            &self.extra_orders[raw_index - MAX_OPEN_ORDERS]
+        }
     }
 
     pub fn open_order_mut_by_raw_index(&mut self, raw_index: usize) -> &mut OpenOrder {
-        &mut self.open_orders[raw_index]
+        if raw_index < MAX_OPEN_ORDERS {
+            &mut self.fixed.open_orders[raw_index]
+        } else {
+            &mut self.extra_orders[raw_index - MAX_OPEN_ORDERS]
+        }
     }
 
     pub fn execute_maker(&mut self, market: &mut Market, fill: &FillEvent) {
@@ -186,7 +435,7 @@ impl OpenOrdersAccount {
             market.fees_accrued += maker_fees as u128;
 
             if fill.maker_out() {
-                self.remove_order(fill.maker_slot as usize, fill.quantity, locked_price);
+                self.remove_order(fill.maker_slot_index(), fill.quantity, locked_price);
             } else {
                 match side {
                     Side::Bid => {
@@ -208,7 +457,7 @@ impl OpenOrdersAccount {
         emit_stack(FillLog {
             market: self.market,
             taker_side: fill.taker_side,
-            maker_slot: fill.maker_slot,
+            maker_slot: fill.maker_slot_index() as u16,
             maker_out: fill.maker_out(),
             timestamp: fill.timestamp,
             seq_num: fill.market_seq_num,
@@ -240,42 +489,6 @@ impl OpenOrdersAccount {
         })
     }
 
-    /// Release funds and apply taker fees to the taker account. Account fees for referrer
-    pub fn execute_taker(
-        &mut self,
-        market: &mut Market,
-        taker_side: Side,
-        base_native: u64,
-        quote_native: u64,
-        taker_fees: u64,
-        referrer_amount: u64,
-    ) {
-        let pa = &mut self.position;
-        match taker_side {
-            Side::Bid => pa.base_free_native += base_native,
-            Side::Ask => pa.quote_free_native += quote_native - taker_fees,
-        };
-
-        pa.taker_volume += quote_native as u128;
-        pa.referrer_rebates_available += referrer_amount;
-        market.referrer_rebates_accrued += referrer_amount;
-
-        emit_stack(OpenOrdersPositionLog {
-            owner: self.owner,
-            open_orders_account_num: self.account_num,
-            market: self.market,
-            bids_base_lots: pa.bids_base_lots,
-            bids_quote_lots: pa.bids_quote_lots,
-            asks_base_lots: pa.asks_base_lots,
-            base_free_native: pa.base_free_native,
-            quote_free_native: pa.quote_free_native,
-            locked_maker_fees: pa.locked_maker_fees,
-            referrer_rebates_available: pa.referrer_rebates_available,
-            maker_volume: pa.maker_volume,
-            taker_volume: pa.taker_volume,
-        })
-    }
-
     pub fn add_order(
         &mut self,
         side: Side,
@@ -292,7 +505,7 @@ impl OpenOrdersAccount {
             }
             Side::Ask => position.asks_base_lots += order.quantity,
         };
-        let slot = order.owner_slot as usize;
+        let slot = order.owner_slot_index();
 
         let oo = self.open_order_mut_by_raw_index(slot);
         oo.is_free = false.into();
@@ -344,6 +557,38 @@ impl OpenOrdersAccount {
     }
 }
 
+pub trait OpenOrdersLoader {
+    /// Like `load_mut()`, but also gives access to the extra open order slots
+    fn load_full_mut(&self) -> Result<OpenOrdersAccountRefMut<'_>>;
+
+    /// Like `load_init()`, but also gives access to the extra open order slots
+    fn load_full_init(&self) -> Result<OpenOrdersAccountRefMut<'_>>;
+
+    /// `is_owner_or_any_delegate` at the current time
+    fn is_owner_or_any_delegate(&self, ix_signer: Pubkey, permissions: u8) -> Result<bool> {
+        let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+        Ok(self
+            .load_full_mut()?
+            .is_owner_or_any_delegate(ix_signer, permissions, now_ts))
+    }
+}
+
+impl<'info> OpenOrdersLoader for AccountLoader<'info, OpenOrdersAccount> {
+    fn load_full_mut(&self) -> Result<OpenOrdersAccountRefMut<'_>> {
+        // checks owner, discriminator and that the account is writable
+        drop(self.load_mut()?);
+        let data = self.as_ref().try_borrow_mut_data()?;
+        OpenOrdersAccountRefMut::from_bytes(RefMut::map(data, |data| &mut data[8..]))
+    }
+
+    fn load_full_init(&self) -> Result<OpenOrdersAccountRefMut<'_>> {
+        // checks owner, that the discriminator is unset and that the account is writable
+        drop(self.load_init()?);
+        let data = self.as_ref().try_borrow_mut_data()?;
+        OpenOrdersAccountRefMut::from_bytes(RefMut::map(data, |data| &mut data[8..]))
+    }
+}
+
 #[zero_copy]
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -358,7 +603,7 @@ pub struct Position {
 
     pub locked_maker_fees: u64,
     pub referrer_rebates_available: u64,
-    /// Count of ixs when events are added to the heap
+    /// Count of events added to the heap, each paying the market's heap penalty on settlement
     /// To avoid this, send remaining accounts in order to process the events
     pub penalty_heap_count: u64,
 
@@ -370,13 +615,37 @@ pub struct Position {
     /// Quote lots in open bids
     pub bids_quote_lots: i64,
 
+    /// Opt-in to have the funds credited by a fill transferred to the owner's associated token
+    /// accounts right when the fill is executed, if the crank or taker provides them. 0 means
+    /// disabled.
+    pub auto_settle: u8,
+
+    /// Bitmask of the `DELEGATE_*` actions the delegate may not take. Stored inverted so
+    /// delegates set before permissions existed keep all of them.
+    pub delegate_revoked_permissions: u8,
+    /// Number of session delegate slots stored at the end of the account
+    pub session_delegate_slots: u8,
+    /// Number of [`RiskLimits`] stored before the session delegates, 0 or 1
+    pub risk_limits_slots: u8,
     #[derivative(Debug = "ignore")]
-    pub reserved: [u8; 64],
+    pub padding: [u8; 2],
+    /// Orders can be cancelled by anyone once no heartbeat was recorded for this long.
+    /// 0 means disabled.
+    pub heartbeat_timeout_seconds: u16,
+    /// Largest quote native value, taken plus posted, of an order placed by the delegate.
+    /// 0 means unlimited.
+    pub delegate_max_order_quote_native: u64,
+
+    /// Last time the owner or a delegate used the account
+    pub heartbeat_timestamp: u64,
+
+    #[derivative(Debug = "ignore")]
+    pub reserved: [u8; 40],
 }
 
 const_assert_eq!(
     size_of::<Position>(),
-    8 + 8 + 8 + 8 + 8 + 8 + 8 + 16 + 16 + 8 + 64
+    8 + 8 + 8 + 8 + 8 + 8 + 8 + 16 + 16 + 8 + 1 + 1 + 1 + 1 + 2 + 2 + 8 + 8 + 40
 );
 const_assert_eq!(size_of::<Position>(), 160);
 const_assert_eq!(size_of::<Position>() % 8, 0);
@@ -394,7 +663,15 @@ impl Default for Position {
             maker_volume: 0,
             taker_volume: 0,
             bids_quote_lots: 0,
-            reserved: [0; 64],
+            auto_settle: 0,
+            delegate_revoked_permissions: 0,
+            session_delegate_slots: 0,
+            risk_limits_slots: 0,
+            padding: [0; 2],
+            heartbeat_timeout_seconds: 0,
+            delegate_max_order_quote_native: 0,
+            heartbeat_timestamp: 0,
+            reserved: [0; 40],
         }
     }
 }
@@ -408,6 +685,34 @@ impl Position {
         self.asks_base_lots != 0 || self.bids_base_lots != 0
     }
 
+    pub fn is_auto_settle(&self) -> bool {
+        self.auto_settle != 0
+    }
+
+    /// Bitmask of the `DELEGATE_*` actions the delegate may take
+    pub fn delegate_permissions(&self) -> u8 {
+        DELEGATE_ALL_PERMISSIONS & !self.delegate_revoked_permissions
+    }
+
+    /// Set the `DELEGATE_*` actions the delegate may take, 0 for none
+    pub fn set_delegate_permissions(&mut self, permissions: u8) {
+        self.delegate_revoked_permissions = DELEGATE_ALL_PERMISSIONS & !permissions;
+    }
+
+    pub fn has_delegate_permissions(&self, permissions: u8) -> bool {
+        self.delegate_permissions() & permissions == permissions
+    }
+
+    /// Keep the orders alive for another `heartbeat_timeout_seconds`
+    pub fn record_heartbeat(&mut self, now_ts: u64) {
+        self.heartbeat_timestamp = now_ts;
+    }
+
+    pub fn is_heartbeat_missed(&self, now_ts: u64) -> bool {
+        self.heartbeat_timeout_seconds > 0
+            && now_ts >= self.heartbeat_timestamp + self.heartbeat_timeout_seconds as u64
+    }
+
     pub fn is_empty(&self, version: u8) -> bool {
         self.bids_base_lots == 0
             && self.asks_base_lots == 0
@@ -437,6 +742,88 @@ const_assert_eq!(size_of::<OpenOrder>(), 16 + 8 + 8 + 1 + 1 + 6);
 const_assert_eq!(size_of::<OpenOrder>(), 40);
 const_assert_eq!(size_of::<OpenOrder>() % 8, 0);
 
+/// Short-lived delegate, e.g. a hot key of a trading bot, with the permissions of the delegate
+#[zero_copy]
+#[derive(Debug, Default)]
+pub struct SessionDelegate {
+    pub key: Pubkey,
+    /// Unix timestamp from which on the key has no authority anymore
+    pub expiry_timestamp: u64,
+}
+const_assert_eq!(size_of::<SessionDelegate>(), 32 + 8);
+const_assert_eq!(size_of::<SessionDelegate>() % 8, 0);
+
+/// Owner-set limits on the orders of an account, stored after the open order slots once set
+#[zero_copy]
+#[derive(Debug, Default)]
+pub struct RiskLimits {
+    /// Cap on base lots in open orders on each side. 0 means unlimited.
+    pub max_base_lots_per_side: i64,
+    /// Cap on the quote native value, taken plus posted, of a single order. 0 means unlimited.
+    pub max_order_quote_native: u64,
+    /// Cap on quote native taker volume per window. 0 means unlimited.
+    pub max_taker_quote_native_per_window: u64,
+    /// Length of the window `max_taker_quote_native_per_window` applies to
+    pub window_seconds: u32,
+    pub padding: [u8; 4],
+    /// Start of the current window
+    pub window_start_timestamp: u64,
+    /// Taker volume in quote native units since the start of the current window
+    pub window_taker_quote_native: u64,
+    pub reserved: [u8; 8],
+}
+const_assert_eq!(size_of::<RiskLimits>(), 8 + 8 + 8 + 4 + 4 + 8 + 8 + 8);
+const_assert_eq!(size_of::<RiskLimits>() % 8, 0);
+
+impl RiskLimits {
+    /// Fails if a just placed order breaks one of the limits, given the open base lots on the
+    /// order's side. Its taken volume is added to the current window.
+    pub fn check(
+        &mut self,
+        open_base_lots: i64,
+        order_quote_native: u64,
+        taker_quote_native: u64,
+        now_ts: u64,
+    ) -> Result<()> {
+        if self.max_order_quote_native > 0 {
+            require_gte!(
+                self.max_order_quote_native,
+                order_quote_native,
+                OpenBookError::RiskLimitOrderNotionalExceeded
+            );
+        }
+
+        if self.max_base_lots_per_side > 0 {
+            require_gte!(
+                self.max_base_lots_per_side,
+                open_base_lots,
+                OpenBookError::RiskLimitOpenBaseLotsExceeded
+            );
+        }
+
+        if self.max_taker_quote_native_per_window > 0 {
+            if now_ts >= self.window_start_timestamp + self.window_seconds as u64 {
+                self.window_start_timestamp = now_ts;
+                self.window_taker_quote_native = 0;
+            }
+            self.window_taker_quote_native += taker_quote_native;
+            require_gte!(
+                self.max_taker_quote_native_per_window,
+                self.window_taker_quote_native,
+                OpenBookError::RiskLimitTakerVolumeExceeded
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl SessionDelegate {
+    pub fn is_active(&self, now_ts: u64) -> bool {
+        self.key != Pubkey::default() && now_ts < self.expiry_timestamp
+    }
+}
+
 impl Default for OpenOrder {
     fn default() -> Self {
         Self {
@@ -1,4 +1,9 @@
 use anchor_lang::prelude::*;
+use std::io::Read;
+use std::ops::{Deref, DerefMut};
+
+/// Upper bound for the number of open orders accounts in an indexer
+pub const MAX_INDEXED_OPEN_ORDERS_ACCOUNTS: usize = 256;
 
 #[account]
 #[derive(Default)]
@@ -6,14 +11,118 @@ pub struct OpenOrdersIndexer {
     pub bump: u8,
     pub created_counter: u32,
     pub addresses: Vec<Pubkey>,
+    /// Market of the open orders account at the same index of `addresses`
+    pub markets: IndexedMarkets,
+}
+
+/// Trailing section of [`OpenOrdersIndexer`], missing from indexers created before markets
+/// were recorded.
+///
+/// Such indexers hold no markets until the next open orders account is added, after which the
+/// markets of the older accounts are the default pubkey until `migrate_open_orders_indexer`
+/// backfills them.
+#[derive(AnchorSerialize, Clone, Default, Debug, PartialEq)]
+pub struct IndexedMarkets {
+    pub keys: Vec<Pubkey>,
+}
+
+impl AnchorDeserialize for IndexedMarkets {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut first = [0u8; 1];
+        if reader.read(&mut first)? == 0 {
+            return Ok(Self::default());
+        }
+        Ok(Self {
+            keys: Vec::deserialize_reader(&mut (&first[..]).chain(reader))?,
+        })
+    }
+}
+
+impl Deref for IndexedMarkets {
+    type Target = Vec<Pubkey>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.keys
+    }
+}
+
+impl DerefMut for IndexedMarkets {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.keys
+    }
 }
 
 impl OpenOrdersIndexer {
     pub fn space(len: usize) -> usize {
-        8 + 1 + 4 + (4 + (len * 32))
+        8 + 1 + 4 + (4 + (len * 32)) + (4 + (len * 32))
+    }
+
+    /// Space left after removing an open orders account. Indexers that hold no markets yet
+    /// keep not holding them.
+    pub fn space_after_remove(&self) -> usize {
+        let len = self.addresses.len() - 1;
+        if self.markets.len() == self.addresses.len() {
+            Self::space(len)
+        } else {
+            Self::space(len) - len * 32
+        }
     }
 
     pub fn has_active_open_orders_accounts(&self) -> bool {
         !self.addresses.is_empty()
     }
+
+    /// Start recording markets on an indexer created before they were, the markets of the
+    /// accounts indexed so far are unknown
+    pub fn record_markets(&mut self) {
+        self.markets.resize(self.addresses.len(), Pubkey::default());
+    }
+
+    pub fn add(&mut self, market: Pubkey, open_orders_account: Pubkey) {
+        self.record_markets();
+        self.addresses.push(open_orders_account);
+        self.markets.push(market);
+    }
+
+    /// Remove an open orders account, returns false if it isn't indexed
+    pub fn remove(&mut self, open_orders_account: &Pubkey) -> bool {
+        let Some(index) = self
+            .addresses
+            .iter()
+            .position(|address| address == open_orders_account)
+        else {
+            return false;
+        };
+        self.addresses.remove(index);
+        if index < self.markets.len() {
+            self.markets.remove(index);
+        }
+        true
+    }
+
+    /// Set the market of an indexed open orders account, returns false if it isn't indexed
+    pub fn backfill_market(&mut self, open_orders_account: &Pubkey, market: Pubkey) -> bool {
+        self.record_markets();
+        let Some(index) = self
+            .addresses
+            .iter()
+            .position(|address| address == open_orders_account)
+        else {
+            return false;
+        };
+        self.markets[index] = market;
+        true
+    }
+
+    /// Open orders accounts of the owner on `market`, without those whose market is unknown
+    pub fn open_orders_accounts_for_market<'a>(
+        &'a self,
+        market: &'a Pubkey,
+    ) -> impl Iterator<Item = &'a Pubkey> {
+        self.addresses
+            .iter()
+            .zip(self.markets.iter())
+            .filter(move |(_, m)| *m == market)
+            .map(|(address, _)| address)
+    }
 }
@@ -8,8 +8,14 @@ use switchboard_solana::AggregatorAccountData;
 
 use crate::accounts_zerocopy::*;
 use crate::error::*;
+use crate::state::pyth_pull_internal;
+use crate::state::pyth_pull_internal::{PriceUpdateV2, VerificationLevel};
 use crate::state::raydium_internal;
 use crate::state::raydium_internal::PoolState;
+use crate::state::switchboard_on_demand_internal;
+use crate::state::switchboard_on_demand_internal::PullFeedAccountData;
+use crate::state::whirlpool_internal;
+use crate::state::whirlpool_internal::Whirlpool;
 
 const DECIMAL_CONSTANT_ZERO_INDEX: i8 = 12;
 const DECIMAL_CONSTANTS_F64: [f64; 25] = [
@@ -35,9 +41,18 @@ pub mod switchboard_v2_mainnet_oracle {
 pub struct OracleConfig {
     pub conf_filter: f64,
     pub max_staleness_slots: i64,
-    pub reserved: [u8; 72],
+    /// Maximum age of the oracle's publish time, 0 disables the check. Markets created before
+    /// the field existed have it zeroed.
+    pub max_staleness_seconds: u64,
+    /// Minimum in range liquidity of AMM pool oracles, 0 disables the check
+    pub min_liquidity: u64,
+    /// Use the oracle's EMA price instead of its spot price when 1
+    pub use_ema_price: u8,
+    /// Use oracle_b on its own when oracle_a is invalid instead of dividing by it when 1
+    pub fallback_to_oracle_b: u8,
+    pub reserved: [u8; 54],
 }
-const_assert_eq!(size_of::<OracleConfig>(), 8 + 8 + 72);
+const_assert_eq!(size_of::<OracleConfig>(), 8 + 8 + 8 + 8 + 1 + 1 + 54);
 const_assert_eq!(size_of::<OracleConfig>(), 88);
 const_assert_eq!(size_of::<OracleConfig>() % 8, 0);
 
@@ -48,6 +63,14 @@ pub struct OracleConfigParams {
     pub conf_filter: f32,
     #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub max_staleness_slots: Option<u32>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub max_staleness_seconds: Option<u32>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub min_liquidity: Option<u64>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub use_ema_price: bool,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub fallback_to_oracle_b: bool,
 }
 
 impl OracleConfigParams {
@@ -55,11 +78,25 @@ impl OracleConfigParams {
         OracleConfig {
             conf_filter: self.conf_filter as f64,
             max_staleness_slots: self.max_staleness_slots.map(|v| v as i64).unwrap_or(-1),
-            reserved: [0; 72],
+            max_staleness_seconds: self.max_staleness_seconds.map(|v| v as u64).unwrap_or(0),
+            min_liquidity: self.min_liquidity.unwrap_or(0),
+            use_ema_price: self.use_ema_price.into(),
+            fallback_to_oracle_b: self.fallback_to_oracle_b.into(),
+            reserved: [0; 54],
         }
     }
 }
 
+impl OracleConfig {
+    pub fn use_ema_price(&self) -> bool {
+        self.use_ema_price == 1
+    }
+
+    pub fn fallback_to_oracle_b(&self) -> bool {
+        self.fallback_to_oracle_b == 1
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, AnchorSerialize, AnchorDeserialize)]
 pub enum OracleType {
     Pyth,
@@ -67,17 +104,46 @@ pub enum OracleType {
     SwitchboardV1,
     SwitchboardV2,
     RaydiumCLMM,
+    PythPull,
+    SwitchboardOnDemand,
+    OrcaWhirlpool,
+}
+
+impl OracleType {
+    /// Is the price in native units already? Whirlpools don't know the decimals of their
+    /// tokens, the prices of all other oracles are in ui units.
+    pub fn is_native_price(&self) -> bool {
+        *self == OracleType::OrcaWhirlpool
+    }
+
+    /// Does the oracle publish an EMA price next to its spot price?
+    pub fn has_ema_price(&self) -> bool {
+        matches!(self, OracleType::Pyth | OracleType::PythPull)
+    }
 }
 
 pub struct OracleState {
     pub price: f64,
     pub deviation: f64,
     pub last_update_slot: u64,
+    /// Publish time of the price, u64::MAX if the oracle doesn't provide one
+    pub last_update_ts: u64,
+    /// In range liquidity of AMM pools, u128::MAX for other oracles
+    pub liquidity: u128,
+    /// EMA price and its deviation, if the oracle provides them
+    pub ema_price: Option<f64>,
+    pub ema_deviation: f64,
     pub oracle_type: OracleType,
 }
 
 impl OracleState {
-    pub fn is_stale(&self, oracle_pk: &Pubkey, config: &OracleConfig, now_slot: u64) -> bool {
+    pub fn is_stale(
+        &self,
+        oracle_pk: &Pubkey,
+        config: &OracleConfig,
+        now_slot: u64,
+        now_ts: u64,
+    ) -> bool {
         if config.max_staleness_slots >= 0
             && self
                 .last_update_slot
@@ -92,6 +158,20 @@ impl OracleState {
                 now_slot,
             );
             true
+        } else if config.max_staleness_seconds > 0
+            && self
+                .last_update_ts
+                .saturating_add(config.max_staleness_seconds)
+                < now_ts
+        {
+            msg!(
+                "Oracle is stale; pubkey {}, price: {}, last_update_ts: {}, now_ts: {}",
+                oracle_pk,
+                self.price,
+                self.last_update_ts,
+                now_ts,
+            );
+            true
         } else {
             false
         }
@@ -112,6 +192,31 @@ impl OracleState {
         }
     }
 
+    /// Replace the spot price and deviation by the EMA ones
+    pub fn use_ema_price(&mut self) -> Result<()> {
+        self.price = self
+            .ema_price
+            .ok_or(OpenBookError::OracleEmaPriceUnavailable)?;
+        self.deviation = self.ema_deviation;
+        require_gte!(self.price, 0f64);
+        Ok(())
+    }
+
+    pub fn has_min_liquidity(&self, oracle_pk: &Pubkey, config: &OracleConfig) -> bool {
+        if self.liquidity < config.min_liquidity as u128 {
+            msg!(
+                "Oracle liquidity too low: pubkey {}, price: {}, liquidity: {}, min_liquidity: {}",
+                oracle_pk,
+                self.price,
+                self.liquidity,
+                config.min_liquidity,
+            );
+            false
+        } else {
+            true
+        }
+    }
+
     pub fn has_valid_combined_confidence(&self, other: &Self, config: &OracleConfig) -> bool {
         // target uncertainty reads
         //   $ \sigma \approx \frac{A}{B} * \sqrt{(\sigma_A/A)^2 + (\sigma_B/B)^2} $
@@ -169,6 +274,18 @@ pub fn determine_oracle_type(acc_info: &impl KeyedAccountReader) -> Result<Oracl
         return Ok(OracleType::SwitchboardV1);
     } else if acc_info.owner() == &raydium_internal::ID {
         return Ok(OracleType::RaydiumCLMM);
+    } else if acc_info.owner() == &pyth_pull_internal::ID
+        && data[0..8] == PriceUpdateV2::discriminator()
+    {
+        return Ok(OracleType::PythPull);
+    } else if acc_info.owner() == &switchboard_on_demand_internal::ID
+        && data[0..8] == PullFeedAccountData::discriminator()
+    {
+        return Ok(OracleType::SwitchboardOnDemand);
+    } else if acc_info.owner() == &whirlpool_internal::ID
+        && data[0..8] == Whirlpool::discriminator()
+    {
+        return Ok(OracleType::OrcaWhirlpool);
     }
 
     Err(OpenBookError::UnknownOracleType.into())
@@ -206,6 +323,34 @@ fn pyth_get_price(
     }
 }
 
+/// Token mints of AMM pool oracles as (mint of the priced token, mint of the pricing token),
+/// None for price feeds
+pub fn oracle_pool_mints(acc_info: &impl KeyedAccountReader) -> Result<Option<(Pubkey, Pubkey)>> {
+    let data = &acc_info.data();
+    Ok(match determine_oracle_type(acc_info)? {
+        OracleType::RaydiumCLMM => {
+            let pool = bytemuck::from_bytes::<PoolState>(&data[8..]);
+            Some((pool.token_mint_0, pool.token_mint_1))
+        }
+        OracleType::OrcaWhirlpool => {
+            let pool = bytemuck::from_bytes::<Whirlpool>(&data[8..]);
+            Some((pool.token_mint_a, pool.token_mint_b))
+        }
+        _ => None,
+    })
+}
+
+/// Feed a Pyth pull price update is for, zeroed for other oracle types
+pub fn pyth_pull_feed_id(acc_info: &impl KeyedAccountReader) -> Result<[u8; 32]> {
+    Ok(match determine_oracle_type(acc_info)? {
+        OracleType::PythPull => {
+            let price_update = PriceUpdateV2::try_deserialize(&mut &acc_info.data()[..])?;
+            price_update.price_message.feed_id
+        }
+        _ => [0; 32],
+    })
+}
+
 /// Returns the price of one native base token, in native quote tokens
 ///
 /// Example: The for SOL at 40 USDC/SOL it would return 0.04 (the unit is USDC-native/SOL-native)
@@ -225,10 +370,19 @@ pub fn oracle_state_unchecked(acc_info: &impl KeyedAccountReader) -> Result<Orac
             } else {
                 stub.last_update_slot
             };
+            let last_update_ts = if stub.last_update_ts == 0 {
+                u64::MAX
+            } else {
+                stub.last_update_ts as u64
+            };
             OracleState {
                 price: stub.price,
                 last_update_slot,
+                last_update_ts,
                 deviation: stub.deviation,
+                liquidity: u128::MAX,
+                ema_price: None,
+                ema_deviation: 0f64,
                 oracle_type: OracleType::Stub,
             }
         }
@@ -240,11 +394,17 @@ pub fn oracle_state_unchecked(acc_info: &impl KeyedAccountReader) -> Result<Orac
             let decimal_adj = power_of_ten_float(decimals);
             let price = price_data.price as f64 * decimal_adj;
             let deviation = price_data.conf as f64 * decimal_adj;
+            let ema_price = price_account.ema_price.val as f64 * decimal_adj;
+            let ema_deviation = price_account.ema_conf.val as f64 * decimal_adj;
             require_gte!(price, 0f64);
             OracleState {
                 price,
                 last_update_slot,
+                last_update_ts: price_data.publish_time as u64,
                 deviation,
+                liquidity: u128::MAX,
+                ema_price: Some(ema_price),
+                ema_deviation,
                 oracle_type: OracleType::Pyth,
             }
         }
@@ -265,12 +425,17 @@ pub fn oracle_state_unchecked(acc_info: &impl KeyedAccountReader) -> Result<Orac
             // The round_open_slot is an underestimate of the last update slot: Reporters will see
             // the round opening and only then start executing the price tasks.
             let last_update_slot = feed.latest_confirmed_round.round_open_slot;
+            let last_update_ts = feed.latest_confirmed_round.round_open_timestamp as u64;
 
             require_gte!(price, 0f64);
             OracleState {
                 price,
                 last_update_slot,
+                last_update_ts,
                 deviation,
+                liquidity: u128::MAX,
+                ema_price: None,
+                ema_deviation: 0f64,
                 oracle_type: OracleType::SwitchboardV2,
             }
         }
@@ -284,7 +449,11 @@ pub fn oracle_state_unchecked(acc_info: &impl KeyedAccountReader) -> Result<Orac
             OracleState {
                 price,
                 last_update_slot,
+                last_update_ts: u64::MAX,
                 deviation,
+                liquidity: u128::MAX,
+                ema_price: None,
+                ema_deviation: 0f64,
                 oracle_type: OracleType::SwitchboardV1,
             }
         }
@@ -301,16 +470,86 @@ pub fn oracle_state_unchecked(acc_info: &impl KeyedAccountReader) -> Result<Orac
             OracleState {
                 price,
                 last_update_slot: u64::MAX, // ensure staleness slot will never fail
+                last_update_ts: u64::MAX,
                 deviation: 0f64,
+                liquidity: pool.liquidity,
+                ema_price: None,
+                ema_deviation: 0f64,
                 oracle_type: OracleType::RaydiumCLMM,
             }
         }
+        OracleType::PythPull => {
+            let price_update = PriceUpdateV2::try_deserialize(&mut &data[..])?;
+            require!(
+                price_update.verification_level == VerificationLevel::Full,
+                OpenBookError::InvalidOracleVerificationLevel
+            );
+
+            let message = price_update.price_message;
+            let decimal_adj = power_of_ten_float(message.exponent as i8);
+            let price = message.price as f64 * decimal_adj;
+            let deviation = message.conf as f64 * decimal_adj;
+            let ema_price = message.ema_price as f64 * decimal_adj;
+            let ema_deviation = message.ema_conf as f64 * decimal_adj;
+            require_gte!(price, 0f64);
+            OracleState {
+                price,
+                last_update_slot: price_update.posted_slot,
+                last_update_ts: message.publish_time as u64,
+                deviation,
+                liquidity: u128::MAX,
+                ema_price: Some(ema_price),
+                ema_deviation,
+                oracle_type: OracleType::PythPull,
+            }
+        }
+        OracleType::SwitchboardOnDemand => {
+            let feed = bytemuck::from_bytes::<PullFeedAccountData>(&data[8..]);
+            let result = feed.result;
+
+            // the standard deviation of the submissions is the confidence of the median
+            let scale = switchboard_on_demand_internal::PRECISION_SCALE;
+            let price = result.value as f64 / scale;
+            let deviation = result.std_dev as f64 / scale;
+            require_gte!(price, 0f64);
+            OracleState {
+                price,
+                last_update_slot: result.slot,
+                last_update_ts: feed.last_update_timestamp as u64,
+                deviation,
+                liquidity: u128::MAX,
+                ema_price: None,
+                ema_deviation: 0f64,
+                oracle_type: OracleType::SwitchboardOnDemand,
+            }
+        }
+        OracleType::OrcaWhirlpool => {
+            let pool = bytemuck::from_bytes::<Whirlpool>(&data[8..]);
+
+            // token_b per token_a in native units, squared as f64 since the square of the
+            // Q64.64 sqrt price can exceed 64 integer bits
+            let sqrt_price = U64F64::from_bits(pool.sqrt_price).to_num::<f64>();
+            let price = sqrt_price * sqrt_price;
+
+            require_gte!(price, 0f64);
+            OracleState {
+                price,
+                last_update_slot: u64::MAX, // ensure staleness slot will never fail
+                last_update_ts: u64::MAX,
+                deviation: 0f64,
+                liquidity: pool.liquidity,
+                ema_price: None,
+                ema_deviation: 0f64,
+                oracle_type: OracleType::OrcaWhirlpool,
+            }
+        }
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::Market;
     use solana_program_test::{find_file, read_file};
     use std::{cell::RefCell, path::PathBuf, str::FromStr};
 
@@ -382,6 +621,202 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    pub fn test_pyth_pull_price() -> Result<()> {
+        // a hand built update, not a dump of a posted one
+        let filename = "resources/test/pyth_pull_synthetic_price_update.bin";
+
+        let mut file_data = read_file(find_file(filename).unwrap());
+        let data = RefCell::new(&mut file_data[..]);
+        let ai = &AccountInfoRef {
+            key: &Pubkey::default(),
+            owner: &pyth_pull_internal::ID,
+            data: data.borrow(),
+        };
+        assert!(determine_oracle_type(ai)? == OracleType::PythPull);
+
+        let oracle = oracle_state_unchecked(ai)?;
+        assert!((oracle.price - 145.32118).abs() < 1e-10);
+        assert!((oracle.deviation - 0.075_032_12).abs() < 1e-10);
+        assert_eq!(oracle.last_update_slot, 270_000_000);
+        assert_eq!(oracle.last_update_ts, 1_718_000_000);
+
+        let config = OracleConfigParams {
+            conf_filter: 0.1,
+            max_staleness_slots: Some(100),
+            max_staleness_seconds: Some(60),
+            min_liquidity: None,
+            use_ema_price: false,
+            fallback_to_oracle_b: false,
+        }
+        .to_oracle_config();
+        let pk = Pubkey::default();
+        assert!(!oracle.is_stale(&pk, &config, 270_000_100, 1_718_000_060));
+        assert!(oracle.is_stale(&pk, &config, 270_000_101, 1_718_000_060));
+        assert!(oracle.is_stale(&pk, &config, 270_000_100, 1_718_000_061));
+
+        // zeroed bytes of markets created before the check existed disable it
+        let mut legacy_config: OracleConfig = bytemuck::Zeroable::zeroed();
+        legacy_config.conf_filter = 0.1;
+        legacy_config.max_staleness_slots = -1;
+        assert!(!oracle.is_stale(&pk, &legacy_config, 270_000_100, 1_718_001_000));
+
+        let mut ema_oracle = oracle_state_unchecked(ai)?;
+        ema_oracle.use_ema_price()?;
+        assert!((ema_oracle.price - 145.1).abs() < 1e-10);
+        assert!((ema_oracle.deviation - 0.08).abs() < 1e-10);
+        assert!(ema_oracle.oracle_type.has_ema_price());
+
+        // the wrong owner or a partially verified update aren't accepted
+        let other_owner = Pubkey::new_unique();
+        let ai_other_owner = &AccountInfoRef {
+            key: &Pubkey::default(),
+            owner: &other_owner,
+            data: data.borrow(),
+        };
+        assert!(determine_oracle_type(ai_other_owner).is_err());
+
+        let mut price_update = PriceUpdateV2::try_deserialize(&mut ai.data())?;
+        price_update.verification_level = VerificationLevel::Partial { num_signatures: 5 };
+        let mut partial_data = Vec::new();
+        price_update.try_serialize(&mut partial_data)?;
+        let partial_data = RefCell::new(&mut partial_data[..]);
+        let ai_partial = &AccountInfoRef {
+            key: &Pubkey::default(),
+            owner: &pyth_pull_internal::ID,
+            data: partial_data.borrow(),
+        };
+        assert_eq!(
+            oracle_state_unchecked(ai_partial).err().unwrap(),
+            OpenBookError::InvalidOracleVerificationLevel.into()
+        );
+
+        // markets only accept updates for the feed recorded at creation
+        let feed_id = pyth_pull_feed_id(ai)?;
+        assert_eq!(feed_id, price_update.price_message.feed_id);
+
+        let mut market: Market = bytemuck::Zeroable::zeroed();
+        market.oracle_a = Some(Pubkey::default()).into();
+        market.oracle_config = config;
+        market.base_decimals = 6;
+        market.quote_decimals = 6;
+        market.base_lot_size = 1;
+        market.quote_lot_size = 1;
+        let clock = Clock {
+            slot: 270_000_000,
+            unix_timestamp: 1_718_000_000,
+            ..Clock::default()
+        };
+        assert_eq!(
+            market
+                .oracle_price(Some(ai), None::<&AccountInfoRef>, &clock)
+                .err()
+                .unwrap(),
+            OpenBookError::InvalidOracleFeed.into()
+        );
+        market.oracle_a_feed_id = feed_id;
+        assert!(market
+            .oracle_price(Some(ai), None::<&AccountInfoRef>, &clock)?
+            .is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_switchboard_on_demand_price() -> Result<()> {
+        let mut feed: PullFeedAccountData = bytemuck::Zeroable::zeroed();
+        feed.result.value = 145_320_000_000_000_000_000;
+        feed.result.std_dev = 290_640_000_000_000_000;
+        feed.result.slot = 270_000_000;
+        feed.last_update_timestamp = 1_718_000_000;
+
+        let mut file_data = PullFeedAccountData::discriminator().to_vec();
+        file_data.extend_from_slice(bytemuck::bytes_of(&feed));
+        let data = RefCell::new(&mut file_data[..]);
+        let ai = &AccountInfoRef {
+            key: &Pubkey::default(),
+            owner: &switchboard_on_demand_internal::ID,
+            data: data.borrow(),
+        };
+        assert!(determine_oracle_type(ai).unwrap() == OracleType::SwitchboardOnDemand);
+
+        let oracle = oracle_state_unchecked(ai)?;
+        assert!((oracle.price - 145.32).abs() < 1e-10);
+        assert!((oracle.deviation - 0.29064).abs() < 1e-10);
+        assert_eq!(oracle.last_update_slot, 270_000_000);
+        assert_eq!(oracle.last_update_ts, 1_718_000_000);
+
+        // the deviation is 0.2% of the price
+        let config = |conf_filter: f32| {
+            OracleConfigParams {
+                conf_filter,
+                max_staleness_slots: None,
+                max_staleness_seconds: None,
+                min_liquidity: None,
+                use_ema_price: false,
+                fallback_to_oracle_b: false,
+            }
+            .to_oracle_config()
+        };
+        assert!(oracle.has_valid_confidence(ai.key(), &config(0.0021)));
+        assert!(!oracle.has_valid_confidence(ai.key(), &config(0.0019)));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_whirlpool_price() -> Result<()> {
+        // 145 USDC/SOL is 0.145 native USDC per native SOL
+        let mut pool = Whirlpool::default();
+        pool.sqrt_price = U64F64::from_num(0.145f64.sqrt()).to_bits();
+        pool.liquidity = 1_000_000;
+
+        let mut file_data = Whirlpool::discriminator().to_vec();
+        file_data.extend_from_slice(bytemuck::bytes_of(&pool));
+        let data = RefCell::new(&mut file_data[..]);
+        let ai = &AccountInfoRef {
+            key: &Pubkey::default(),
+            owner: &whirlpool_internal::ID,
+            data: data.borrow(),
+        };
+        assert!(determine_oracle_type(ai).unwrap() == OracleType::OrcaWhirlpool);
+
+        let oracle = oracle_state_unchecked(ai)?;
+        assert!((oracle.price - 0.145).abs() < 1e-10);
+        assert!(oracle.oracle_type.is_native_price());
+        assert!(!oracle.oracle_type.has_ema_price());
+        assert!(oracle_state_unchecked(ai)?.use_ema_price().is_err());
+
+        let config = |min_liquidity: u64| {
+            OracleConfigParams {
+                conf_filter: 0.1,
+                max_staleness_slots: None,
+                max_staleness_seconds: None,
+                min_liquidity: Some(min_liquidity),
+                use_ema_price: false,
+                fallback_to_oracle_b: false,
+            }
+            .to_oracle_config()
+        };
+        assert!(oracle.has_min_liquidity(ai.key(), &config(1_000_000)));
+        assert!(!oracle.has_min_liquidity(ai.key(), &config(1_000_001)));
+
+        // the square of the largest sqrt price doesn't fit in U64F64
+        pool.sqrt_price = 79_226_673_515_401_279_992_447_579_055;
+        let mut file_data = Whirlpool::discriminator().to_vec();
+        file_data.extend_from_slice(bytemuck::bytes_of(&pool));
+        let data = RefCell::new(&mut file_data[..]);
+        let ai = &AccountInfoRef {
+            key: &Pubkey::default(),
+            owner: &whirlpool_internal::ID,
+            data: data.borrow(),
+        };
+        let oracle = oracle_state_unchecked(ai)?;
+        assert!((oracle.price / 2f64.powi(64) - 1.0).abs() < 1e-4);
+
+        Ok(())
+    }
+
     #[test]
     pub fn lookup_test() {
         for idx in -12..0_i8 {
@@ -1,12 +1,16 @@
 use crate::logs::*;
-use crate::state::MAX_OPEN_ORDERS;
+use crate::token_utils::token_transfer_signed;
 use crate::{
     error::*,
-    state::{orderbook::bookside::*, EventHeap, Market, OpenOrdersAccount},
+    state::{
+        market_seeds, orderbook::bookside::*, EventHeapRefMut, Market, OpenOrdersAccount,
+        OpenOrdersAccountRefMut, OpenOrdersLoader,
+    },
 };
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::Token;
 use bytemuck::cast;
-use std::cell::RefMut;
 
 use super::*;
 
@@ -14,12 +18,12 @@ use super::*;
 /// This exists as a guard against excessive compute use.
 pub const DROP_EXPIRED_ORDER_LIMIT: usize = 5;
 
-/// Process up to this remaining accounts in the fill event
+/// Process up to this remaining accounts in the fill event, unless the event heap is full
 pub const FILL_EVENT_REMAINING_LIMIT: usize = 15;
 
 pub struct Orderbook<'a> {
-    pub bids: RefMut<'a, BookSide>,
-    pub asks: RefMut<'a, BookSide>,
+    pub bids: BookSideRefMut<'a>,
+    pub asks: BookSideRefMut<'a>,
 }
 
 pub struct OrderWithAmounts {
@@ -31,26 +35,39 @@ pub struct OrderWithAmounts {
     pub taker_fees: u64,
     pub maker_fees: u64,
     pub referrer_amount: u64,
+    // The book ran out of orders within the price limit before the order was filled.
+    pub not_enough_liquidity: bool,
+}
+
+/// Expected outcome of a take order, see `Orderbook::quote_order`
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct TakeQuote {
+    pub total_base_taken_native: u64,
+    pub total_quote_taken_native: u64,
+    // Paid on top of the quote for bids, deducted from the quote for asks.
+    pub taker_fees_native: u64,
+    // The book ran out of orders within the price limit before the order was filled.
+    pub not_enough_liquidity: bool,
 }
 
 impl<'a> Orderbook<'a> {
     pub fn init(&mut self) {
-        self.bids.nodes.order_tree_type = OrderTreeType::Bids.into();
-        self.asks.nodes.order_tree_type = OrderTreeType::Asks.into();
+        self.bids.header.order_tree.order_tree_type = OrderTreeType::Bids.into();
+        self.asks.header.order_tree.order_tree_type = OrderTreeType::Asks.into();
     }
 
     pub fn is_empty(&self) -> bool {
         self.bids.is_empty() && self.asks.is_empty()
     }
 
-    pub fn bookside_mut(&mut self, side: Side) -> &mut BookSide {
+    pub fn bookside_mut(&mut self, side: Side) -> &mut BookSideRefMut<'a> {
         match side {
             Side::Bid => &mut self.bids,
             Side::Ask => &mut self.asks,
         }
     }
 
-    pub fn bookside(&self, side: Side) -> &BookSide {
+    pub fn bookside(&self, side: Side) -> &BookSideRefMut<'a> {
         match side {
             Side::Bid => &self.bids,
             Side::Ask => &self.asks,
@@ -63,13 +80,94 @@ impl<'a> Orderbook<'a> {
         order: &Order,
         open_book_market: &mut Market,
         market_pk: &Pubkey,
-        event_heap: &mut EventHeap,
+        event_heap: &mut EventHeapRefMut,
+        oracle_price_lots: Option<i64>,
+        open_orders_account: Option<&mut OpenOrdersAccountRefMut>,
+        owner: &Pubkey,
+        now_ts: u64,
+        limit: u8,
+        remaining_accs: &'c [AccountInfo<'info>],
+    ) -> std::result::Result<OrderWithAmounts, Error> {
+        self.new_order_impl(
+            order,
+            open_book_market,
+            market_pk,
+            event_heap,
+            oracle_price_lots,
+            open_orders_account,
+            owner,
+            now_ts,
+            limit,
+            remaining_accs,
+            false,
+        )
+    }
+
+    /// Match a take order against the book like `new_order` would, without changing the
+    /// book, the event heap, the market or any open orders account
+    ///
+    /// The order is assumed not to self trade.
+    #[allow(clippy::too_many_arguments)]
+    pub fn quote_order<'c: 'info, 'info>(
+        &mut self,
+        order: &Order,
+        market: &Market,
+        event_heap: &mut EventHeapRefMut,
+        oracle_price_lots: Option<i64>,
+        now_ts: u64,
+        limit: u8,
+        remaining_accs: &'c [AccountInfo<'info>],
+    ) -> Result<TakeQuote> {
+        require!(
+            matches!(
+                order.params,
+                OrderParams::Market
+                    | OrderParams::ImmediateOrCancel { .. }
+                    | OrderParams::FillOrKill { .. }
+            ),
+            OpenBookError::InvalidInputOrderType
+        );
+
+        // new_order bumps the order id sequence number even if nothing gets posted
+        let mut market = *market;
+        let taken = self.new_order_impl(
+            order,
+            &mut market,
+            &Pubkey::default(),
+            event_heap,
+            oracle_price_lots,
+            None,
+            &Pubkey::default(),
+            now_ts,
+            limit,
+            remaining_accs,
+            true,
+        )?;
+
+        Ok(TakeQuote {
+            total_base_taken_native: taken.total_base_taken_native,
+            total_quote_taken_native: taken.total_quote_taken_native,
+            taker_fees_native: taken.taker_fees,
+            not_enough_liquidity: taken.not_enough_liquidity,
+        })
+    }
+
+    /// Matches and posts the order, or only computes the outcome in a `dry_run`, which
+    /// skips all writes to the books, the event heap, the open orders accounts and the logs.
+    #[allow(clippy::too_many_arguments)]
+    fn new_order_impl<'c: 'info, 'info>(
+        &mut self,
+        order: &Order,
+        open_book_market: &mut Market,
+        market_pk: &Pubkey,
+        event_heap: &mut EventHeapRefMut,
         oracle_price_lots: Option<i64>,
-        mut open_orders_account: Option<&mut OpenOrdersAccount>,
+        mut open_orders_account: Option<&mut OpenOrdersAccountRefMut>,
         owner: &Pubkey,
         now_ts: u64,
         mut limit: u8,
         remaining_accs: &'c [AccountInfo<'info>],
+        dry_run: bool,
     ) -> std::result::Result<OrderWithAmounts, Error> {
         let market = open_book_market;
 
@@ -119,21 +217,39 @@ impl<'a> Orderbook<'a> {
         let mut matched_order_deletes: Vec<(BookSideOrderTree, u128)> = vec![];
         let mut number_of_dropped_expired_orders = 0;
         let mut number_of_processed_fill_events = 0;
+        // events a dry run would have pushed to the heap
+        let mut dry_run_events = 0;
+        let mut not_enough_liquidity = true;
 
         let opposing_bookside = self.bookside_mut(other_side);
         for best_opposing in opposing_bookside.iter_all_including_invalid(now_ts, oracle_price_lots)
         {
             if remaining_base_lots == 0 || remaining_quote_lots == 0 {
+                not_enough_liquidity = false;
                 break;
             }
 
             if !best_opposing.is_valid() {
-                // Remove the order from the book unless we've done that enough
-                if number_of_dropped_expired_orders < DROP_EXPIRED_ORDER_LIMIT {
+                // Remove the order from the book unless we've done that enough or it would need
+                // an event on the full heap
+                let is_maker_at_hand = (open_orders_account.is_some()
+                    && owner == &best_opposing.node.owner)
+                    || remaining_accs
+                        .iter()
+                        .any(|ai| ai.key == &best_opposing.node.owner);
+                if number_of_dropped_expired_orders < DROP_EXPIRED_ORDER_LIMIT
+                    && (!is_event_heap_full(event_heap, dry_run_events) || is_maker_at_hand)
+                {
                     number_of_dropped_expired_orders += 1;
+                    if dry_run {
+                        if !is_maker_at_hand {
+                            dry_run_push_event(event_heap, &mut dry_run_events)?;
+                        }
+                        continue;
+                    }
                     let event = OutEvent::new(
                         other_side,
-                        best_opposing.node.owner_slot,
+                        best_opposing.node.owner_slot_index() as u16,
                         now_ts,
                         event_heap.header.seq_num,
                         best_opposing.node.owner,
@@ -161,11 +277,13 @@ impl<'a> Orderbook<'a> {
             }
             if post_only {
                 msg!("Order could not be placed due to PostOnly");
+                not_enough_liquidity = false;
                 post_target = None;
                 break; // return silently to not fail other instructions in tx
             }
             if limit == 0 {
                 msg!("Order matching limit reached");
+                not_enough_liquidity = false;
                 post_target = None;
                 break;
             }
@@ -173,6 +291,7 @@ impl<'a> Orderbook<'a> {
             let max_match_by_quote = remaining_quote_lots / best_opposing_price;
             // Do not post orders in the book due to bad pricing and negative spread
             if max_match_by_quote == 0 {
+                not_enough_liquidity = false;
                 post_target = None;
                 break;
             }
@@ -183,30 +302,63 @@ impl<'a> Orderbook<'a> {
             let match_quote_lots = match_base_lots * best_opposing_price;
 
             // Self-trade behaviour
-            if open_orders_account.is_some() && owner == &best_opposing.node.owner {
+            let is_own_order = open_orders_account.is_some() && owner == &best_opposing.node.owner;
+            let is_stp_group_order = !is_own_order
+                && match open_orders_account.as_deref() {
+                    Some(oo) => is_same_stp_group(oo, best_opposing.node, remaining_accs)?,
+                    None => false,
+                };
+            if is_own_order || is_stp_group_order {
                 match order.self_trade_behavior {
-                    SelfTradeBehavior::DecrementTake => {
-                        // remember all decremented quote lots to only charge fees on not-self-trades
-                        decremented_quote_lots += match_quote_lots;
-                    }
-                    SelfTradeBehavior::CancelProvide => {
-                        // The open orders acc is always present in this case, no need event_heap
-                        open_orders_account.as_mut().unwrap().cancel_order(
-                            best_opposing.node.owner_slot as usize,
-                            best_opposing.node.quantity,
-                            *market,
-                        );
-                        matched_order_deletes
-                            .push((best_opposing.handle.order_tree, best_opposing.node.key));
+                    SelfTradeBehavior::DecrementTake => {}
+                    SelfTradeBehavior::CancelProvide | SelfTradeBehavior::CancelBoth => {
+                        // The maker account is either the taker's or in remaining_accs,
+                        // no need for event_heap
+                        if !dry_run {
+                            let event = OutEvent::new(
+                                other_side,
+                                best_opposing.node.owner_slot_index() as u16,
+                                now_ts,
+                                event_heap.header.seq_num,
+                                best_opposing.node.owner,
+                                best_opposing.node.quantity,
+                            );
+                            process_out_event(
+                                event,
+                                market,
+                                event_heap,
+                                open_orders_account.as_deref_mut(),
+                                owner,
+                                remaining_accs,
+                            )?;
+                            matched_order_deletes
+                                .push((best_opposing.handle.order_tree, best_opposing.node.key));
+                        }
+
+                        if order.self_trade_behavior == SelfTradeBehavior::CancelBoth {
+                            not_enough_liquidity = false;
+                            post_target = None;
+                            break;
+                        }
 
                         // skip actual matching
                         continue;
                     }
+                    SelfTradeBehavior::CancelTake => {
+                        not_enough_liquidity = false;
+                        post_target = None;
+                        break;
+                    }
                     SelfTradeBehavior::AbortTransaction => {
                         return err!(OpenBookError::WouldSelfTrade)
                     }
                 }
                 assert!(order.self_trade_behavior == SelfTradeBehavior::DecrementTake);
+            }
+
+            if is_own_order {
+                // remember all decremented quote lots to only charge fees on not-self-trades
+                decremented_quote_lots += match_quote_lots;
             } else {
                 maker_rebates_acc +=
                     market.maker_rebate_floor((match_quote_lots * market.quote_lot_size) as u64);
@@ -218,6 +370,22 @@ impl<'a> Orderbook<'a> {
 
             let new_best_opposing_quantity = best_opposing.node.quantity - match_base_lots;
             let maker_out = new_best_opposing_quantity == 0;
+            limit -= 1;
+
+            if dry_run {
+                let is_processed = (number_of_processed_fill_events < FILL_EVENT_REMAINING_LIMIT
+                    || is_event_heap_full(event_heap, dry_run_events))
+                    && remaining_accs
+                        .iter()
+                        .any(|ai| ai.key == &best_opposing.node.owner);
+                if is_processed {
+                    number_of_processed_fill_events += 1;
+                } else {
+                    dry_run_push_event(event_heap, &mut dry_run_events)?;
+                }
+                continue;
+            }
+
             if maker_out {
                 matched_order_deletes
                     .push((best_opposing.handle.order_tree, best_opposing.node.key));
@@ -228,7 +396,7 @@ impl<'a> Orderbook<'a> {
             let fill = FillEvent::new(
                 side,
                 maker_out,
-                best_opposing.node.owner_slot,
+                best_opposing.node.owner_slot_index() as u16,
                 now_ts,
                 market.seq_num,
                 best_opposing.node.owner,
@@ -249,12 +417,14 @@ impl<'a> Orderbook<'a> {
             process_fill_event(
                 fill,
                 market,
+                market_pk,
                 event_heap,
                 remaining_accs,
                 &mut number_of_processed_fill_events,
             )?;
-
-            limit -= 1;
+        }
+        if remaining_base_lots == 0 || remaining_quote_lots == 0 {
+            not_enough_liquidity = false;
         }
 
         let total_quote_lots_taken = order_max_quote_lots - remaining_quote_lots;
@@ -277,8 +447,11 @@ impl<'a> Orderbook<'a> {
 
                 // Only account taker fees now. Maker fees accounted once processing the event
                 referrer_amount = taker_fees_native - maker_rebates_acc;
-                market.fees_accrued += referrer_amount as u128;
             };
+        }
+
+        if !dry_run && (total_quote_lots_taken > 0 || total_base_lots_taken > 0) {
+            market.fees_accrued += referrer_amount as u128;
 
             if let Some(open_orders_account) = &mut open_orders_account {
                 open_orders_account.execute_taker(
@@ -379,7 +552,7 @@ impl<'a> Orderbook<'a> {
         let mut posted_base_native = 0;
         let mut posted_quote_native = 0;
 
-        if let Some(order_tree_target) = post_target {
+        if post_target.is_some() {
             require_gte!(
                 market.max_quote_lots(),
                 book_base_quantity_lots * price,
@@ -389,25 +562,31 @@ impl<'a> Orderbook<'a> {
             posted_base_native = book_base_quantity_lots * market.base_lot_size;
             posted_quote_native = book_base_quantity_lots * price * market.quote_lot_size;
 
-            // Open orders always exists in this case
-            let open_orders = open_orders_account.as_mut().unwrap();
-
             // Subtract maker fees in bid.
             if side == Side::Bid {
                 maker_fees_native = market
                     .maker_fees_ceil(posted_quote_native)
                     .try_into()
                     .unwrap();
-
-                open_orders.position.locked_maker_fees += maker_fees_native;
             }
+        }
+
+        if let Some(order_tree_target) = post_target.filter(|_| !dry_run) {
+            // Open orders always exists in this case
+            let open_orders = open_orders_account.as_mut().unwrap();
+            open_orders.position.locked_maker_fees += maker_fees_native;
 
             let bookside = self.bookside_mut(side);
             // Drop an expired order if possible
-            if let Some(expired_order) = bookside.remove_one_expired(order_tree_target, now_ts) {
+            let expired_order = if event_heap.is_full() {
+                None
+            } else {
+                bookside.remove_one_expired(order_tree_target, now_ts)
+            };
+            if let Some(expired_order) = expired_order {
                 let event = OutEvent::new(
                     side,
-                    expired_order.owner_slot,
+                    expired_order.owner_slot_index() as u16,
                     now_ts,
                     event_heap.header.seq_num,
                     expired_order.owner,
@@ -434,7 +613,7 @@ impl<'a> Orderbook<'a> {
                 );
                 let event = OutEvent::new(
                     side,
-                    worst_order.owner_slot,
+                    worst_order.owner_slot_index() as u16,
                     now_ts,
                     event_heap.header.seq_num,
                     worst_order.owner,
@@ -452,7 +631,7 @@ impl<'a> Orderbook<'a> {
 
             let owner_slot = open_orders.next_order_slot()?;
             let new_order = LeafNode::new(
-                owner_slot as u8,
+                owner_slot as u16,
                 order_id,
                 *owner,
                 book_base_quantity_lots,
@@ -487,6 +666,7 @@ impl<'a> Orderbook<'a> {
             referrer_amount,
             taker_fees: taker_fees_native,
             maker_fees: maker_fees_native,
+            not_enough_liquidity,
         })
     }
 
@@ -495,15 +675,15 @@ impl<'a> Orderbook<'a> {
     /// The orders are removed from the book and from the openorders account open order list.
     pub fn cancel_all_orders(
         &mut self,
-        open_orders_account: &mut OpenOrdersAccount,
+        open_orders_account: &mut OpenOrdersAccountRefMut,
         market: Market,
         mut limit: u8,
         side_to_cancel_option: Option<Side>,
         client_id_option: Option<u64>,
     ) -> Result<i64> {
         let mut total_quantity = 0_i64;
-        for i in 0..MAX_OPEN_ORDERS {
-            let oo = open_orders_account.open_orders[i];
+        for i in 0..open_orders_account.capacity() {
+            let oo = *open_orders_account.open_order_by_raw_index(i);
             if oo.is_free() {
                 continue;
             }
@@ -555,7 +735,7 @@ impl<'a> Orderbook<'a> {
     /// Cancels an order on a side, removing it from the book and the openorders account orders list
     pub fn cancel_order(
         &mut self,
-        open_orders_account: &mut OpenOrdersAccount,
+        open_orders_account: &mut OpenOrdersAccountRefMut,
         order_id: u128,
         side_and_tree: SideAndOrderTree,
         market: Market,
@@ -571,32 +751,75 @@ impl<'a> Orderbook<'a> {
         if let Some(owner) = expected_owner {
             require_keys_eq!(leaf_node.owner, owner);
         }
-        open_orders_account.cancel_order(leaf_node.owner_slot as usize, leaf_node.quantity, market);
+        open_orders_account.cancel_order(leaf_node.owner_slot_index(), leaf_node.quantity, market);
 
         Ok(leaf_node)
     }
 }
 
+/// Returns whether a resting order of another account is in the same self-trade prevention
+/// group as the taker.
+///
+/// The group can only be verified on the maker's OpenOrdersAccount, so orders of accounts
+/// that aren't passed in `remaining_accs` are matched normally. Callers wanting self-trade
+/// prevention across accounts must pass their other accounts of the market.
+fn is_same_stp_group<'c: 'info, 'info>(
+    taker: &OpenOrdersAccount,
+    maker_order: &LeafNode,
+    remaining_accs: &'c [AccountInfo<'info>],
+) -> Result<bool> {
+    if taker.stp_group == 0 {
+        return Ok(false);
+    }
+
+    if let Some(acc) = remaining_accs
+        .iter()
+        .find(|ai| ai.key == &maker_order.owner)
+    {
+        let ooa: AccountLoader<OpenOrdersAccount> = AccountLoader::try_from(acc)?;
+        let maker = ooa.load()?;
+        return Ok(maker.owner == taker.owner && maker.stp_group == taker.stp_group);
+    }
+
+    Ok(false)
+}
+
+fn is_event_heap_full(event_heap: &EventHeapRefMut, dry_run_events: usize) -> bool {
+    event_heap.len() + dry_run_events >= event_heap.capacity()
+}
+
+/// Takes up a heap slot for an event that a dry run doesn't push, failing on a full heap
+/// like pushing it would
+fn dry_run_push_event(event_heap: &EventHeapRefMut, dry_run_events: &mut usize) -> Result<()> {
+    require!(
+        !is_event_heap_full(event_heap, *dry_run_events),
+        OpenBookError::EventHeapFull
+    );
+    *dry_run_events += 1;
+    Ok(())
+}
+
 pub fn process_out_event<'c: 'info, 'info>(
     event: OutEvent,
     market: &Market,
-    event_heap: &mut EventHeap,
-    open_orders_account: Option<&mut OpenOrdersAccount>,
+    event_heap: &mut EventHeapRefMut,
+    open_orders_account: Option<&mut OpenOrdersAccountRefMut>,
     owner: &Pubkey,
     remaining_accs: &'c [AccountInfo<'info>],
 ) -> Result<()> {
     if let Some(acc) = open_orders_account {
         if owner == &event.owner {
-            acc.cancel_order(event.owner_slot as usize, event.quantity, *market);
+            acc.cancel_order(event.owner_slot_index(), event.quantity, *market);
             return Ok(());
         }
     }
 
     if let Some(acc) = remaining_accs.iter().find(|ai| ai.key == &event.owner) {
         let ooa: AccountLoader<OpenOrdersAccount> = AccountLoader::try_from(acc)?;
-        let mut acc = ooa.load_mut()?;
-        acc.cancel_order(event.owner_slot as usize, event.quantity, *market);
+        let mut acc = ooa.load_full_mut()?;
+        acc.cancel_order(event.owner_slot_index(), event.quantity, *market);
     } else {
+        require!(!event_heap.is_full(), OpenBookError::EventHeapFull);
         event_heap.push_back(cast(event));
     }
 
@@ -606,24 +829,134 @@ pub fn process_out_event<'c: 'info, 'info>(
 pub fn process_fill_event<'c: 'info, 'info>(
     event: FillEvent,
     market: &mut Market,
-    event_heap: &mut EventHeap,
+    market_pk: &Pubkey,
+    event_heap: &mut EventHeapRefMut,
     remaining_accs: &'c [AccountInfo<'info>],
     number_of_processed_fill_events: &mut usize,
 ) -> Result<()> {
     let mut is_processed = false;
-    if *number_of_processed_fill_events < FILL_EVENT_REMAINING_LIMIT {
+    // once the heap is full makers are only filled if they are passed, whatever the limit
+    if *number_of_processed_fill_events < FILL_EVENT_REMAINING_LIMIT || event_heap.is_full() {
         if let Some(acc) = remaining_accs.iter().find(|ai| ai.key == &event.maker) {
             let ooa: AccountLoader<OpenOrdersAccount> = AccountLoader::try_from(acc)?;
-            let mut maker = ooa.load_mut()?;
-            maker.execute_maker(market, &event);
+            let mut maker = ooa.load_full_mut()?;
+            execute_maker_fill(market, market_pk, &mut maker, &event, remaining_accs)?;
             is_processed = true;
             *number_of_processed_fill_events += 1;
         }
     }
 
     if !is_processed {
+        require!(!event_heap.is_full(), OpenBookError::EventHeapFull);
         event_heap.push_back(cast(event));
     }
 
     Ok(())
 }
+
+/// Executes the fill on the maker's account. Auto settling makers are paid the funds the
+/// fill credited right away, see `settle_maker`.
+pub fn execute_maker_fill<'info>(
+    market: &mut Market,
+    market_pk: &Pubkey,
+    maker: &mut OpenOrdersAccountRefMut,
+    fill: &FillEvent,
+    remaining_accs: &[AccountInfo<'info>],
+) -> Result<()> {
+    let base_free_before = maker.position.base_free_native;
+    let quote_free_before = maker.position.quote_free_native;
+    maker.execute_maker(market, fill);
+
+    if maker.position.is_auto_settle() {
+        let credited_base_native = maker.position.base_free_native - base_free_before;
+        let credited_quote_native = maker.position.quote_free_native - quote_free_before;
+        settle_maker(
+            market,
+            market_pk,
+            maker,
+            fill.maker,
+            credited_base_native,
+            credited_quote_native,
+            remaining_accs,
+        )?;
+    }
+    Ok(())
+}
+
+/// Transfer funds credited to an auto settling maker to the owner's token accounts.
+///
+/// Needs the market authority, the vaults, the token program and the associated token accounts
+/// of the maker's owner among the remaining accounts. Funds of a side that can't be transferred stay free
+/// and can be claimed with settle_funds, just like any funds that were free before the fill.
+fn settle_maker<'info>(
+    market: &mut Market,
+    market_key: &Pubkey,
+    maker: &mut OpenOrdersAccount,
+    maker_key: Pubkey,
+    credited_base_native: u64,
+    credited_quote_native: u64,
+    remaining_accs: &[AccountInfo<'info>],
+) -> Result<()> {
+    let find = |key: Pubkey| remaining_accs.iter().find(|ai| ai.key == &key);
+    let (Some(market_authority), Some(token_program)) =
+        (find(market.market_authority), find(Token::id()))
+    else {
+        return Ok(());
+    };
+
+    // only the owner's associated token accounts are destinations, so they can be matched by key
+    let owner = maker.owner;
+    let find_destination = |mint: Pubkey| find(get_associated_token_address(&owner, &mint));
+
+    let pa = &mut maker.position;
+    let mut base_native = 0;
+    let mut quote_native = 0;
+    let seeds = market_seeds!(market, market_key);
+
+    if let (Some(vault), Some(destination)) = (
+        find(market.market_base_vault),
+        find_destination(market.base_mint),
+    ) {
+        base_native = credited_base_native;
+        token_transfer_signed(
+            base_native,
+            token_program,
+            vault,
+            destination,
+            market_authority,
+            seeds,
+        )?;
+        pa.base_free_native -= base_native;
+    }
+
+    if let (Some(vault), Some(destination)) = (
+        find(market.market_quote_vault),
+        find_destination(market.quote_mint),
+    ) {
+        quote_native = credited_quote_native;
+        token_transfer_signed(
+            quote_native,
+            token_program,
+            vault,
+            destination,
+            market_authority,
+            seeds,
+        )?;
+        pa.quote_free_native -= quote_native;
+    }
+
+    if base_native > 0 || quote_native > 0 {
+        market.base_deposit_total -= base_native;
+        market.quote_deposit_total -= quote_native;
+
+        emit_stack(SettleFundsLog {
+            open_orders_account: maker_key,
+            base_native,
+            quote_native,
+            referrer_rebate: 0,
+            referrer: None,
+        });
+    }
+
+    Ok(())
+}
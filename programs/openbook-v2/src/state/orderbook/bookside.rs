@@ -3,6 +3,9 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 use static_assertions::const_assert_eq;
 
 use super::*;
+use crate::accounts_zerocopy::DynamicAccount;
+use std::cell::RefMut;
+use std::ops::{Deref, DerefMut};
 
 #[derive(
     Eq,
@@ -41,7 +44,53 @@ const_assert_eq!(
 const_assert_eq!(std::mem::size_of::<BookSide>(), 90944);
 const_assert_eq!(std::mem::size_of::<BookSide>() % 8, 0);
 
-impl BookSide {
+impl DynamicAccount for BookSide {
+    type Header = BookSideHeader;
+    type Item = AnyNode;
+}
+
+/// The fields of a BookSide account in front of its nodes
+#[zero_copy]
+pub struct BookSideHeader {
+    pub roots: [OrderTreeRoot; 2],
+    pub reserved_roots: [OrderTreeRoot; 4],
+    pub reserved: [u8; 256],
+    pub order_tree: OrderTreeHeader,
+}
+const_assert_eq!(
+    std::mem::size_of::<BookSideHeader>() + 88 * MAX_ORDERTREE_NODES,
+    std::mem::size_of::<BookSide>()
+);
+const_assert_eq!(std::mem::size_of::<BookSideHeader>() % 8, 0);
+
+/// A BookSide account with any number of nodes
+///
+/// Usually a `BookSideRefMut` borrowing the account data, see `DynamicAccount`.
+pub struct BookSideView<H, N> {
+    pub header: H,
+    pub nodes: N,
+}
+
+pub type BookSideRefMut<'a> = BookSideView<RefMut<'a, BookSideHeader>, RefMut<'a, [AnyNode]>>;
+
+impl<H, N> From<(H, N)> for BookSideView<H, N> {
+    fn from((header, nodes): (H, N)) -> Self {
+        Self { header, nodes }
+    }
+}
+
+impl<H, N> BookSideView<H, N>
+where
+    H: Deref<Target = BookSideHeader>,
+    N: Deref<Target = [AnyNode]>,
+{
+    pub fn order_tree(&self) -> OrderTreeRef<'_> {
+        OrderTreeRef {
+            header: &self.header.order_tree,
+            nodes: &self.nodes,
+        }
+    }
+
     /// Iterate over all entries in the book filtering out invalid orders
     ///
     /// smallest to highest for asks
@@ -64,29 +113,133 @@ impl BookSide {
     }
 
     pub fn node(&self, handle: NodeHandle) -> Option<&AnyNode> {
-        self.nodes.node(handle)
-    }
-
-    pub fn node_mut(&mut self, handle: NodeHandle) -> Option<&mut AnyNode> {
-        self.nodes.node_mut(handle)
+        self.order_tree().node(handle)
     }
 
     pub fn root(&self, component: BookSideOrderTree) -> &OrderTreeRoot {
-        &self.roots[component as usize]
+        &self.header.roots[component as usize]
     }
 
-    pub fn root_mut(&mut self, component: BookSideOrderTree) -> &mut OrderTreeRoot {
-        &mut self.roots[component as usize]
+    pub fn capacity(&self) -> usize {
+        self.nodes.len()
     }
 
     pub fn is_full(&self) -> bool {
-        self.nodes.is_full()
+        self.order_tree().is_full()
     }
 
     pub fn is_empty(&self) -> bool {
         [BookSideOrderTree::Fixed, BookSideOrderTree::OraclePegged]
             .into_iter()
-            .all(|component| self.nodes.iter(self.root(component)).count() == 0)
+            .all(|component| self.order_tree().iter(self.root(component)).count() == 0)
+    }
+
+    pub fn side(&self) -> Side {
+        self.order_tree().order_tree_type().side()
+    }
+
+    /// Return the quantity of orders that can be matched by an order at `limit_price_lots`
+    pub fn quantity_at_price(
+        &self,
+        limit_price_lots: i64,
+        now_ts: u64,
+        oracle_price_lots: i64,
+    ) -> i64 {
+        let side = self.side();
+        let mut sum = 0;
+        for item in self.iter_valid(now_ts, Some(oracle_price_lots)) {
+            if side.is_price_better(limit_price_lots, item.price_lots) {
+                break;
+            }
+            sum += item.node.quantity;
+        }
+        sum
+    }
+
+    /// Return the price of the order closest to the spread
+    pub fn best_price(&self, now_ts: u64, oracle_price_lots: Option<i64>) -> Option<i64> {
+        Some(
+            self.iter_valid(now_ts, oracle_price_lots)
+                .next()?
+                .price_lots,
+        )
+    }
+
+    /// Walk up the book `quantity` units and return the price at that level. If `quantity` units
+    /// not on book, return None
+    pub fn impact_price(&self, quantity: i64, now_ts: u64, oracle_price_lots: i64) -> Option<i64> {
+        let mut sum: i64 = 0;
+        for order in self.iter_valid(now_ts, Some(oracle_price_lots)) {
+            sum += order.node.quantity;
+            if sum >= quantity {
+                return Some(order.price_lots);
+            }
+        }
+        None
+    }
+
+    /// Return up to `max_levels` (price, quantity) pairs, summing the quantity of all valid
+    /// orders at the same price, best price first
+    pub fn price_levels(
+        &self,
+        now_ts: u64,
+        oracle_price_lots: Option<i64>,
+        max_levels: usize,
+    ) -> Vec<(i64, i64)> {
+        let mut levels: Vec<(i64, i64)> = Vec::with_capacity(max_levels);
+        for order in self.iter_valid(now_ts, oracle_price_lots) {
+            match levels.last_mut() {
+                Some((price, quantity)) if *price == order.price_lots => {
+                    *quantity += order.node.quantity;
+                }
+                _ if levels.len() == max_levels => break,
+                _ => levels.push((order.price_lots, order.node.quantity)),
+            }
+        }
+        levels
+    }
+
+    /// Return the order with the lowest expiry timestamp across both components, if that's
+    /// < now_ts, along with the component it's in
+    pub fn find_earliest_expired(&self, now_ts: u64) -> Option<(BookSideOrderTree, LeafNode)> {
+        let order_tree = self.order_tree();
+        [BookSideOrderTree::Fixed, BookSideOrderTree::OraclePegged]
+            .into_iter()
+            .filter_map(|component| {
+                let (handle, expires_at) = order_tree.find_earliest_expiry(self.root(component))?;
+                Some((component, handle, expires_at))
+            })
+            .filter(|(_, _, expires_at)| *expires_at < now_ts)
+            .min_by_key(|(_, _, expires_at)| *expires_at)
+            .map(|(component, handle, _)| {
+                (component, *self.node(handle).unwrap().as_leaf().unwrap())
+            })
+    }
+}
+
+impl<H, N> BookSideView<H, N>
+where
+    H: DerefMut<Target = BookSideHeader>,
+    N: DerefMut<Target = [AnyNode]>,
+{
+    /// Split into the roots and the order tree they point into
+    fn split_roots_mut(&mut self) -> (&mut [OrderTreeRoot; 2], OrderTreeRefMut<'_>) {
+        let header = &mut *self.header;
+        (
+            &mut header.roots,
+            OrderTreeRefMut {
+                header: &mut header.order_tree,
+                nodes: &mut self.nodes,
+            },
+        )
+    }
+
+    pub fn node_mut(&mut self, handle: NodeHandle) -> Option<&mut AnyNode> {
+        self.split_roots_mut().1.node_mut(handle)
+    }
+
+    pub fn root_mut(&mut self, component: BookSideOrderTree) -> &mut OrderTreeRoot {
+        &mut self.header.roots[component as usize]
     }
 
     pub fn insert_leaf(
@@ -94,8 +247,8 @@ impl BookSide {
         component: BookSideOrderTree,
         new_leaf: &LeafNode,
     ) -> Result<(NodeHandle, Option<LeafNode>)> {
-        let root = &mut self.roots[component as usize];
-        self.nodes.insert_leaf(root, new_leaf)
+        let (roots, mut order_tree) = self.split_roots_mut();
+        order_tree.insert_leaf(&mut roots[component as usize], new_leaf)
     }
 
     /// Remove the overall worst-price order.
@@ -104,9 +257,10 @@ impl BookSide {
         now_ts: u64,
         oracle_price_lots: Option<i64>,
     ) -> Option<(LeafNode, i64)> {
-        let worst_fixed = self.nodes.find_worst(&self.roots[0]);
-        let worst_pegged = self.nodes.find_worst(&self.roots[1]);
-        let side = self.nodes.order_tree_type().side();
+        let order_tree = self.order_tree();
+        let worst_fixed = order_tree.find_worst(&self.header.roots[0]);
+        let worst_pegged = order_tree.find_worst(&self.header.roots[1]);
+        let side = order_tree.order_tree_type().side();
         let worse = rank_orders(
             side,
             worst_fixed,
@@ -129,8 +283,8 @@ impl BookSide {
         component: BookSideOrderTree,
         now_ts: u64,
     ) -> Option<LeafNode> {
-        let root = &mut self.roots[component as usize];
-        if let Some(n) = self.nodes.remove_one_expired(root, now_ts) {
+        let (roots, mut order_tree) = self.split_roots_mut();
+        if let Some(n) = order_tree.remove_one_expired(&mut roots[component as usize], now_ts) {
             return Some(n);
         }
 
@@ -138,8 +292,7 @@ impl BookSide {
             BookSideOrderTree::Fixed => BookSideOrderTree::OraclePegged,
             BookSideOrderTree::OraclePegged => BookSideOrderTree::Fixed,
         };
-        let other_root = &mut self.roots[other_component as usize];
-        self.nodes.remove_one_expired(other_root, now_ts)
+        order_tree.remove_one_expired(&mut roots[other_component as usize], now_ts)
     }
 
     pub fn remove_by_key(
@@ -147,52 +300,20 @@ impl BookSide {
         component: BookSideOrderTree,
         search_key: u128,
     ) -> Option<LeafNode> {
-        let root = &mut self.roots[component as usize];
-        self.nodes.remove_by_key(root, search_key)
-    }
-
-    pub fn side(&self) -> Side {
-        self.nodes.order_tree_type().side()
-    }
-
-    /// Return the quantity of orders that can be matched by an order at `limit_price_lots`
-    pub fn quantity_at_price(
-        &self,
-        limit_price_lots: i64,
-        now_ts: u64,
-        oracle_price_lots: i64,
-    ) -> i64 {
-        let side = self.side();
-        let mut sum = 0;
-        for item in self.iter_valid(now_ts, Some(oracle_price_lots)) {
-            if side.is_price_better(limit_price_lots, item.price_lots) {
-                break;
-            }
-            sum += item.node.quantity;
-        }
-        sum
+        let (roots, mut order_tree) = self.split_roots_mut();
+        order_tree.remove_by_key(&mut roots[component as usize], search_key)
     }
+}
 
-    /// Return the price of the order closest to the spread
-    pub fn best_price(&self, now_ts: u64, oracle_price_lots: Option<i64>) -> Option<i64> {
-        Some(
-            self.iter_valid(now_ts, oracle_price_lots)
-                .next()?
-                .price_lots,
-        )
+impl BookSide {
+    /// View of a BookSide at the default capacity
+    pub fn view(&self) -> BookSideView<&BookSideHeader, &[AnyNode]> {
+        self.split().into()
     }
 
-    /// Walk up the book `quantity` units and return the price at that level. If `quantity` units
-    /// not on book, return None
-    pub fn impact_price(&self, quantity: i64, now_ts: u64, oracle_price_lots: i64) -> Option<i64> {
-        let mut sum: i64 = 0;
-        for order in self.iter_valid(now_ts, Some(oracle_price_lots)) {
-            sum += order.node.quantity;
-            if sum >= quantity {
-                return Some(order.price_lots);
-            }
-        }
-        None
+    /// Same as view(), but mut
+    pub fn view_mut(&mut self) -> BookSideView<&mut BookSideHeader, &mut [AnyNode]> {
+        self.split_mut().into()
     }
 }
 
@@ -201,10 +322,15 @@ mod tests {
     use super::*;
     use bytemuck::Zeroable;
 
-    fn new_order_tree(order_tree_type: OrderTreeType) -> OrderTreeNodes {
-        let mut ot = OrderTreeNodes::zeroed();
-        ot.order_tree_type = order_tree_type.into();
-        ot
+    type TestBookSide = BookSideView<Box<BookSideHeader>, Vec<AnyNode>>;
+
+    fn new_bookside(order_tree_type: OrderTreeType) -> TestBookSide {
+        let mut header = BookSideHeader::zeroed();
+        header.order_tree.order_tree_type = order_tree_type.into();
+        BookSideView {
+            header: Box::new(header),
+            nodes: vec![AnyNode::zeroed(); MAX_ORDERTREE_NODES],
+        }
     }
 
     fn bookside_iteration_random_helper(side: Side) {
@@ -216,9 +342,7 @@ mod tests {
             Side::Ask => OrderTreeType::Asks,
         };
 
-        let mut order_tree = new_order_tree(order_tree_type);
-        let mut root_fixed = OrderTreeRoot::zeroed();
-        let mut root_pegged = OrderTreeRoot::zeroed();
+        let mut bookside = new_bookside(order_tree_type);
         let new_leaf = |key: u128| LeafNode::new(0, key, Pubkey::default(), 0, 1, 0, -1, 0);
 
         // add 100 leaves to each BookSide, mostly random
@@ -227,11 +351,11 @@ mod tests {
         // ensure at least one oracle pegged order visible even at oracle price 1
         let key = new_node_key(side, oracle_pegged_price_data(20), 0);
         keys.push(key);
-        order_tree
-            .insert_leaf(&mut root_pegged, &new_leaf(key))
+        bookside
+            .insert_leaf(BookSideOrderTree::OraclePegged, &new_leaf(key))
             .unwrap();
 
-        while root_pegged.leaf_count < 100 {
+        while bookside.root(BookSideOrderTree::OraclePegged).leaf_count < 100 {
             let price_data: u64 = oracle_pegged_price_data(rng.gen_range(-20..20));
             let seq_num: u64 = rng.gen_range(0..1000);
             let key = new_node_key(side, price_data, seq_num);
@@ -239,12 +363,12 @@ mod tests {
                 continue;
             }
             keys.push(key);
-            order_tree
-                .insert_leaf(&mut root_pegged, &new_leaf(key))
+            bookside
+                .insert_leaf(BookSideOrderTree::OraclePegged, &new_leaf(key))
                 .unwrap();
         }
 
-        while root_fixed.leaf_count < 100 {
+        while bookside.root(BookSideOrderTree::Fixed).leaf_count < 100 {
             let price_data: u64 = rng.gen_range(1..50);
             let seq_num: u64 = rng.gen_range(0..1000);
             let key = new_node_key(side, price_data, seq_num);
@@ -252,18 +376,11 @@ mod tests {
                 continue;
             }
             keys.push(key);
-            order_tree
-                .insert_leaf(&mut root_fixed, &new_leaf(key))
+            bookside
+                .insert_leaf(BookSideOrderTree::Fixed, &new_leaf(key))
                 .unwrap();
         }
 
-        let bookside = BookSide {
-            roots: [root_fixed, root_pegged],
-            reserved_roots: [OrderTreeRoot::zeroed(); 4],
-            reserved: [0; 256],
-            nodes: order_tree,
-        };
-
         // verify iteration order for different oracle prices
         for oracle_price_lots in 1..40 {
             let mut total = 0;
@@ -293,30 +410,31 @@ mod tests {
         bookside_iteration_random_helper(Side::Ask);
     }
 
-    fn bookside_setup() -> BookSide {
+    fn bookside_setup() -> TestBookSide {
         use std::cell::RefCell;
 
         let side = Side::Bid;
         let order_tree_type = OrderTreeType::Bids;
 
-        let order_tree = RefCell::new(new_order_tree(order_tree_type));
-        let mut root_fixed = OrderTreeRoot::zeroed();
-        let mut root_pegged = OrderTreeRoot::zeroed();
+        let bookside = RefCell::new(new_bookside(order_tree_type));
         let new_node = |key: u128, tif: u16, peg_limit: i64| {
             LeafNode::new(0, key, Pubkey::default(), 0, 1000, tif, peg_limit, 0)
         };
-        let mut add_fixed = |price: i64, tif: u16| {
+        let add_fixed = |price: i64, tif: u16| {
             let key = new_node_key(side, fixed_price_data(price).unwrap(), 0);
-            order_tree
+            bookside
                 .borrow_mut()
-                .insert_leaf(&mut root_fixed, &new_node(key, tif, -1))
+                .insert_leaf(BookSideOrderTree::Fixed, &new_node(key, tif, -1))
                 .unwrap();
         };
-        let mut add_pegged = |price_offset: i64, tif: u16, peg_limit: i64| {
+        let add_pegged = |price_offset: i64, tif: u16, peg_limit: i64| {
             let key = new_node_key(side, oracle_pegged_price_data(price_offset), 0);
-            order_tree
+            bookside
                 .borrow_mut()
-                .insert_leaf(&mut root_pegged, &new_node(key, tif, peg_limit))
+                .insert_leaf(
+                    BookSideOrderTree::OraclePegged,
+                    &new_node(key, tif, peg_limit),
+                )
                 .unwrap();
         };
 
@@ -326,12 +444,7 @@ mod tests {
         add_pegged(-15, 0, -1);
         add_pegged(-20, 7, 95);
 
-        BookSide {
-            roots: [root_fixed, root_pegged],
-            reserved_roots: [OrderTreeRoot::zeroed(); 4],
-            reserved: [0; 256],
-            nodes: order_tree.into_inner(),
-        }
+        bookside.into_inner()
     }
 
     #[test]
@@ -398,5 +511,75 @@ mod tests {
         assert_eq!(order_prices(0, 100), Vec::<i64>::new());
     }
 
+    #[test]
+    fn bookside_find_earliest_expired() {
+        let side = Side::Bid;
+        let mut bookside = new_bookside(OrderTreeType::Bids);
+        let mut add = |component, price_data, tif| {
+            let key = new_node_key(side, price_data, 0);
+            let leaf = LeafNode::new(0, key, Pubkey::default(), 1, 1000, tif, -1, 0);
+            bookside.insert_leaf(component, &leaf).unwrap();
+            key
+        };
+
+        add(BookSideOrderTree::Fixed, fixed_price_data(100).unwrap(), 0);
+        let fixed = add(BookSideOrderTree::Fixed, fixed_price_data(90).unwrap(), 7);
+        let pegged = add(
+            BookSideOrderTree::OraclePegged,
+            oracle_pegged_price_data(-10),
+            5,
+        );
+
+        // the pegged order expires first, even though the fixed tree has expired orders too
+        assert!(bookside.find_earliest_expired(1005).is_none());
+        let (component, leaf) = bookside.find_earliest_expired(1010).unwrap();
+        assert_eq!(component, BookSideOrderTree::OraclePegged);
+        assert_eq!(leaf.key, pegged);
+
+        bookside.remove_by_key(component, leaf.key).unwrap();
+        assert!(bookside.find_earliest_expired(1007).is_none());
+        let (component, leaf) = bookside.find_earliest_expired(1010).unwrap();
+        assert_eq!(component, BookSideOrderTree::Fixed);
+        assert_eq!(leaf.key, fixed);
+
+        bookside.remove_by_key(component, leaf.key).unwrap();
+        assert!(bookside.find_earliest_expired(u64::MAX).is_none());
+    }
+
+    #[test]
+    fn bookside_price_levels() {
+        let side = Side::Bid;
+        let mut bookside = new_bookside(OrderTreeType::Bids);
+        let mut add = |component, price_data, seq_num, quantity, tif| {
+            let key = new_node_key(side, price_data, seq_num);
+            let leaf = LeafNode::new(0, key, Pubkey::default(), quantity, 1000, tif, -1, 0);
+            bookside.insert_leaf(component, &leaf).unwrap();
+        };
+        let fixed = BookSideOrderTree::Fixed;
+        let pegged = BookSideOrderTree::OraclePegged;
+        add(fixed, fixed_price_data(100).unwrap(), 0, 1, 0);
+        add(fixed, fixed_price_data(100).unwrap(), 1, 2, 0);
+        add(fixed, fixed_price_data(90).unwrap(), 2, 3, 0);
+        add(fixed, fixed_price_data(120).unwrap(), 3, 4, 5);
+        add(pegged, oracle_pegged_price_data(-10), 4, 5, 0);
+
+        assert_eq!(
+            bookside.price_levels(1000, Some(100), 10),
+            vec![(120, 4), (100, 3), (90, 8)]
+        );
+        // expired orders are skipped
+        assert_eq!(
+            bookside.price_levels(1005, Some(100), 10),
+            vec![(100, 3), (90, 8)]
+        );
+        assert_eq!(bookside.price_levels(1005, Some(100), 1), vec![(100, 3)]);
+        // pegged orders are skipped without oracle price
+        assert_eq!(
+            bookside.price_levels(1000, None, 10),
+            vec![(120, 4), (100, 3), (90, 3)]
+        );
+        assert_eq!(bookside.price_levels(1000, Some(100), 0), vec![]);
+    }
+
     // add test for oracle expired
 }
@@ -1,4 +1,5 @@
 use super::*;
+use std::ops::Deref;
 
 pub struct BookSideIterItem<'a> {
     pub handle: BookSideOrderHandle,
@@ -29,14 +30,19 @@ pub struct BookSideIter<'a> {
 }
 
 impl<'a> BookSideIter<'a> {
-    pub fn new(book_side: &'a BookSide, now_ts: u64, oracle_price_lots: Option<i64>) -> Self {
+    pub fn new<H, N>(
+        book_side: &'a BookSideView<H, N>,
+        now_ts: u64,
+        oracle_price_lots: Option<i64>,
+    ) -> Self
+    where
+        H: Deref<Target = BookSideHeader>,
+        N: Deref<Target = [AnyNode]>,
+    {
+        let order_tree = book_side.order_tree();
         Self {
-            fixed_iter: book_side
-                .nodes
-                .iter(book_side.root(BookSideOrderTree::Fixed)),
-            oracle_pegged_iter: book_side
-                .nodes
-                .iter(book_side.root(BookSideOrderTree::OraclePegged)),
+            fixed_iter: order_tree.iter(book_side.root(BookSideOrderTree::Fixed)),
+            oracle_pegged_iter: order_tree.iter(book_side.root(BookSideOrderTree::OraclePegged)),
             now_ts,
             oracle_price_lots,
         }
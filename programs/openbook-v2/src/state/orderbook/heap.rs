@@ -5,15 +5,21 @@ use static_assertions::const_assert_eq;
 use std::mem::size_of;
 
 use super::Side;
+use crate::accounts_zerocopy::DynamicAccount;
+use std::cell::RefMut;
+use std::ops::{Deref, DerefMut};
 
 pub const MAX_NUM_EVENTS: u16 = 600;
 pub const NO_NODE: u16 = u16::MAX;
 
 /// Container for the different EventTypes.
 ///
-/// Events are stored in a fixed-array of nodes. Free nodes are connected by a single-linked list
+/// Events are stored in an array of nodes. Free nodes are connected by a single-linked list
 /// starting at free_head while used nodes form a circular doubly-linked list starting at
 /// used_head.
+///
+/// This is the layout at the default capacity of `MAX_NUM_EVENTS` nodes. Accounts can be
+/// created with other capacities and are accessed through `EventHeapView`.
 #[account(zero_copy)]
 pub struct EventHeap {
     pub header: EventHeapHeader,
@@ -28,27 +34,54 @@ const_assert_eq!(
 const_assert_eq!(std::mem::size_of::<EventHeap>(), 91280);
 const_assert_eq!(std::mem::size_of::<EventHeap>() % 8, 0);
 
+impl DynamicAccount for EventHeap {
+    type Header = EventHeapHeader;
+    type Item = EventNode;
+
+    const TRAILER_SIZE: usize = 64;
+}
+
 impl EventHeap {
-    pub fn init(&mut self) {
-        self.header = EventHeapHeader {
-            free_head: 0,
-            used_head: NO_NODE,
-            count: 0,
-            seq_num: 0,
-            _padd: Default::default(),
-        };
+    /// View of an EventHeap at the default capacity
+    pub fn view(&self) -> EventHeapView<&EventHeapHeader, &[EventNode]> {
+        self.split().into()
+    }
 
-        for i in 0..MAX_NUM_EVENTS {
-            self.nodes[i as usize].next = i + 1;
-            self.nodes[i as usize].prev = NO_NODE;
-        }
-        self.nodes[MAX_NUM_EVENTS as usize - 1].next = NO_NODE;
+    /// Same as view(), but mut
+    pub fn view_mut(&mut self) -> EventHeapView<&mut EventHeapHeader, &mut [EventNode]> {
+        self.split_mut().into()
     }
+}
 
+/// An EventHeap account with any number of nodes
+///
+/// Usually an `EventHeapRefMut` borrowing the account data, see `DynamicAccount`.
+pub struct EventHeapView<H, N> {
+    pub header: H,
+    pub nodes: N,
+}
+
+pub type EventHeapRefMut<'a> = EventHeapView<RefMut<'a, EventHeapHeader>, RefMut<'a, [EventNode]>>;
+
+impl<H, N> From<(H, N)> for EventHeapView<H, N> {
+    fn from((header, nodes): (H, N)) -> Self {
+        Self { header, nodes }
+    }
+}
+
+impl<H, N> EventHeapView<H, N>
+where
+    H: Deref<Target = EventHeapHeader>,
+    N: Deref<Target = [EventNode]>,
+{
     pub fn len(&self) -> usize {
         self.header.count()
     }
 
+    pub fn capacity(&self) -> usize {
+        self.nodes.len()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -73,6 +106,38 @@ impl EventHeap {
         }
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&AnyEvent, usize)> {
+        EventHeapIterator {
+            nodes: &self.nodes,
+            len: self.len(),
+            index: 0,
+            slot: self.header.used_head(),
+        }
+    }
+}
+
+impl<H, N> EventHeapView<H, N>
+where
+    H: DerefMut<Target = EventHeapHeader>,
+    N: DerefMut<Target = [EventNode]>,
+{
+    pub fn init(&mut self) {
+        *self.header = EventHeapHeader {
+            free_head: 0,
+            used_head: NO_NODE,
+            count: 0,
+            seq_num: 0,
+            _padd: Default::default(),
+        };
+
+        let capacity = self.nodes.len();
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            node.next = (i + 1) as u16;
+            node.prev = NO_NODE;
+        }
+        self.nodes[capacity - 1].next = NO_NODE;
+    }
+
     pub fn push_back(&mut self, value: AnyEvent) {
         assert!(!self.is_full());
 
@@ -131,18 +196,11 @@ impl EventHeap {
 
         Ok(self.nodes[slot].event)
     }
-
-    pub fn iter(&self) -> impl Iterator<Item = (&AnyEvent, usize)> {
-        EventHeapIterator {
-            heap: self,
-            index: 0,
-            slot: self.header.used_head(),
-        }
-    }
 }
 
 struct EventHeapIterator<'a> {
-    heap: &'a EventHeap,
+    nodes: &'a [EventNode],
+    len: usize,
     index: usize,
     slot: usize,
 }
@@ -150,13 +208,13 @@ struct EventHeapIterator<'a> {
 impl<'a> Iterator for EventHeapIterator<'a> {
     type Item = (&'a AnyEvent, usize);
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index == self.heap.len() {
+        if self.index == self.len {
             None
         } else {
             let current_slot = self.slot;
-            self.slot = self.heap.nodes[current_slot].next as usize;
+            self.slot = self.nodes[current_slot].next as usize;
             self.index += 1;
-            Some((&self.heap.nodes[current_slot].event, current_slot))
+            Some((&self.nodes[current_slot].event, current_slot))
         }
     }
 }
@@ -240,8 +298,9 @@ pub struct FillEvent {
     pub event_type: u8,
     pub taker_side: u8, // Side, from the taker's POV
     pub maker_out: u8,  // 1 if maker order quantity == 0
-    pub maker_slot: u8,
-    pub padding: [u8; 4],
+    pub maker_slot: u8, // low byte, see maker_slot_index()
+    pub maker_slot_hi: u8,
+    pub padding: [u8; 3],
     pub timestamp: u64,
     pub market_seq_num: u64,
 
@@ -267,7 +326,7 @@ impl FillEvent {
     pub fn new(
         taker_side: Side,
         maker_out: bool,
-        maker_slot: u8,
+        maker_slot: u16,
         timestamp: u64,
         market_seq_num: u64,
         maker: Pubkey,
@@ -283,7 +342,8 @@ impl FillEvent {
             event_type: EventType::Fill as u8,
             taker_side: taker_side.into(),
             maker_out: maker_out.into(),
-            maker_slot,
+            maker_slot: maker_slot as u8,
+            maker_slot_hi: (maker_slot >> 8) as u8,
             timestamp,
             market_seq_num,
             maker,
@@ -305,6 +365,9 @@ impl FillEvent {
     pub fn maker_out(&self) -> bool {
         self.maker_out == 1
     }
+    pub fn maker_slot_index(&self) -> usize {
+        ((self.maker_slot_hi as usize) << 8) | self.maker_slot as usize
+    }
 }
 
 #[derive(
@@ -313,9 +376,10 @@ impl FillEvent {
 #[repr(C)]
 pub struct OutEvent {
     pub event_type: u8,
-    pub side: u8, // Side
-    pub owner_slot: u8,
-    padding0: [u8; 5],
+    pub side: u8,       // Side
+    pub owner_slot: u8, // low byte, see owner_slot_index()
+    pub owner_slot_hi: u8,
+    padding0: [u8; 4],
     pub timestamp: u64,
     pub seq_num: u64,
     pub owner: Pubkey,
@@ -328,7 +392,7 @@ const_assert_eq!(size_of::<OutEvent>(), EVENT_SIZE);
 impl OutEvent {
     pub fn new(
         side: Side,
-        owner_slot: u8,
+        owner_slot: u16,
         timestamp: u64,
         seq_num: u64,
         owner: Pubkey,
@@ -337,8 +401,9 @@ impl OutEvent {
         Self {
             event_type: EventType::Out.into(),
             side: side.into(),
-            owner_slot,
-            padding0: [0; 5],
+            owner_slot: owner_slot as u8,
+            owner_slot_hi: (owner_slot >> 8) as u8,
+            padding0: [0; 4],
             timestamp,
             seq_num,
             owner,
@@ -350,6 +415,9 @@ impl OutEvent {
     pub fn side(&self) -> Side {
         self.side.try_into().unwrap()
     }
+    pub fn owner_slot_index(&self) -> usize {
+        ((self.owner_slot_hi as usize) << 8) | self.owner_slot as usize
+    }
 }
 
 #[cfg(test)]
@@ -359,8 +427,8 @@ mod tests {
 
     const LAST_SLOT: u16 = MAX_NUM_EVENTS - 1;
 
-    fn count_free_nodes(event_heap: &EventHeap) -> usize {
-        event_heap.nodes.iter().filter(|n| n.is_free()).count()
+    fn count_free_nodes(nodes: &[EventNode]) -> usize {
+        nodes.iter().filter(|n| n.is_free()).count()
     }
 
     fn dummy_event_with_number(number: u8) -> AnyEvent {
@@ -371,19 +439,21 @@ mod tests {
 
     #[test]
     fn init() {
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
 
         assert_eq!(eq.header.count(), 0);
         assert_eq!(eq.header.free_head(), 0);
         assert_eq!(eq.header.used_head(), NO_NODE as usize);
-        assert_eq!(count_free_nodes(&eq), MAX_NUM_EVENTS as usize);
+        assert_eq!(count_free_nodes(&eq.nodes), MAX_NUM_EVENTS as usize);
     }
 
     #[test]
     #[should_panic]
     fn cannot_insert_if_full() {
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
         for _ in 0..MAX_NUM_EVENTS + 1 {
             eq.push_back(AnyEvent::zeroed());
@@ -393,14 +463,16 @@ mod tests {
     #[test]
     #[should_panic]
     fn cannot_delete_if_empty() {
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
         eq.pop_front().unwrap();
     }
 
     #[test]
     fn insert_until_full() {
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
 
         // insert one event in the first slot; the single used node should point to himself
@@ -438,7 +510,8 @@ mod tests {
 
     #[test]
     fn delete_full() {
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
         for _ in 0..MAX_NUM_EVENTS {
             eq.push_back(AnyEvent::zeroed());
@@ -473,12 +546,13 @@ mod tests {
         assert_eq!(eq.nodes[LAST_SLOT as usize].next, LAST_SLOT - 1);
 
         assert_eq!(eq.header.count(), 0);
-        assert_eq!(count_free_nodes(&eq), MAX_NUM_EVENTS as usize);
+        assert_eq!(count_free_nodes(&eq.nodes), MAX_NUM_EVENTS as usize);
     }
 
     #[test]
     fn delete_at_given_position() {
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
         for _ in 0..5 {
             eq.push_back(AnyEvent::zeroed());
@@ -491,7 +565,8 @@ mod tests {
     #[test]
     #[should_panic]
     fn cannot_delete_twice_same() {
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
         for _ in 0..5 {
             eq.push_back(AnyEvent::zeroed());
@@ -502,7 +577,8 @@ mod tests {
 
     #[test]
     fn read_front() {
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
         eq.push_back(dummy_event_with_number(1));
         eq.push_back(AnyEvent::zeroed());
@@ -511,7 +587,8 @@ mod tests {
 
     #[test]
     fn read_at_slot() {
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
         eq.push_back(AnyEvent::zeroed());
         eq.push_back(AnyEvent::zeroed());
@@ -528,7 +605,8 @@ mod tests {
         // [3|2| | | ] push_back
         // [3| | | | ] pop_front
 
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
         assert!(eq.nodes[0].is_free());
         assert!(eq.nodes[1].is_free());
@@ -570,7 +648,8 @@ mod tests {
         // [0| |1|2|3] push_back
         // [ | |0|1|2] push_back
 
-        let mut eq = EventHeap::zeroed();
+        let mut heap = EventHeap::zeroed();
+        let mut eq = heap.view_mut();
         eq.init();
         assert_eq!(eq.header.free_head(), 0);
         assert_eq!(eq.nodes[0].next, 1);
@@ -599,4 +678,29 @@ mod tests {
         assert_eq!(eq.header.free_head(), 2);
         assert_eq!(eq.nodes[2].next, 3);
     }
+
+    #[test]
+    fn small_capacity() {
+        let mut header = EventHeapHeader::zeroed();
+        let mut nodes = vec![EventNode::zeroed(); 3];
+        let mut eq = EventHeapView {
+            header: &mut header,
+            nodes: &mut nodes[..],
+        };
+        eq.init();
+        assert_eq!(eq.capacity(), 3);
+        assert_eq!(count_free_nodes(&eq.nodes), 3);
+
+        for i in 0..3 {
+            assert!(!eq.is_full());
+            eq.push_back(dummy_event_with_number(i));
+        }
+        assert!(eq.is_full());
+        assert_eq!(eq.header.free_head, NO_NODE);
+
+        assert_eq!(eq.pop_front().unwrap().event_type, 0);
+        eq.push_back(dummy_event_with_number(3));
+        let events: Vec<u8> = eq.iter().map(|(e, _)| e.event_type).collect();
+        assert_eq!(events, vec![1, 2, 3]);
+    }
 }
@@ -21,15 +21,16 @@ mod ordertree_iterator;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::state::{Market, OpenOrdersAccount, FEES_SCALE_FACTOR};
+    use crate::accounts_zerocopy::DynamicAccount;
+    use crate::state::{Market, OpenOrdersAccountRefMut, FEES_SCALE_FACTOR, MAX_OPEN_ORDERS};
     use bytemuck::Zeroable;
     use fixed::types::I80F48;
     use solana_program::pubkey::Pubkey;
-    use std::cell::RefCell;
+    use std::cell::{RefCell, RefMut};
 
-    fn order_tree_leaf_by_key(bookside: &BookSide, key: u128) -> Option<&LeafNode> {
+    fn order_tree_leaf_by_key(bookside: &BookSideRefMut, key: u128) -> Option<&LeafNode> {
         for component in [BookSideOrderTree::Fixed, BookSideOrderTree::OraclePegged] {
-            for (_, leaf) in bookside.nodes.iter(bookside.root(component)) {
+            for (_, leaf) in bookside.order_tree().iter(bookside.root(component)) {
                 if leaf.key == key {
                     return Some(leaf);
                 }
@@ -38,13 +39,13 @@ mod tests {
         None
     }
 
-    fn order_tree_contains_key(bookside: &BookSide, key: u128) -> bool {
+    fn order_tree_contains_key(bookside: &BookSideRefMut, key: u128) -> bool {
         order_tree_leaf_by_key(bookside, key).is_some()
     }
 
-    fn order_tree_contains_price(bookside: &BookSide, price_data: u64) -> bool {
+    fn order_tree_contains_price(bookside: &BookSideRefMut, price_data: u64) -> bool {
         for component in [BookSideOrderTree::Fixed, BookSideOrderTree::OraclePegged] {
-            for (_, leaf) in bookside.nodes.iter(bookside.root(component)) {
+            for (_, leaf) in bookside.order_tree().iter(bookside.root(component)) {
                 if leaf.price_data() == price_data {
                     return true;
                 }
@@ -56,6 +57,7 @@ mod tests {
     struct OrderbookAccounts {
         bids: Box<RefCell<BookSide>>,
         asks: Box<RefCell<BookSide>>,
+        event_heap: Box<RefCell<EventHeap>>,
     }
 
     impl OrderbookAccounts {
@@ -63,6 +65,7 @@ mod tests {
             let s = Self {
                 bids: Box::new(RefCell::new(BookSide::zeroed())),
                 asks: Box::new(RefCell::new(BookSide::zeroed())),
+                event_heap: Box::new(RefCell::new(EventHeap::zeroed())),
             };
             s.bids.borrow_mut().nodes.order_tree_type = OrderTreeType::Bids.into();
             s.asks.borrow_mut().nodes.order_tree_type = OrderTreeType::Asks.into();
@@ -71,17 +74,19 @@ mod tests {
 
         fn orderbook(&self) -> Orderbook {
             Orderbook {
-                bids: self.bids.borrow_mut(),
-                asks: self.asks.borrow_mut(),
+                bids: RefMut::map_split(self.bids.borrow_mut(), |b| b.split_mut()).into(),
+                asks: RefMut::map_split(self.asks.borrow_mut(), |b| b.split_mut()).into(),
             }
         }
+
+        fn event_heap(&self) -> EventHeapRefMut {
+            RefMut::map_split(self.event_heap.borrow_mut(), |h| h.split_mut()).into()
+        }
     }
 
-    fn test_setup(price: f64) -> (Market, Option<i64>, EventHeap, OrderbookAccounts) {
+    fn test_setup(price: f64) -> (Market, Option<i64>, OrderbookAccounts) {
         let book = OrderbookAccounts::new();
 
-        let event_heap = EventHeap::zeroed();
-
         let mut openbook_market = Market::zeroed();
         openbook_market.quote_lot_size = 1;
         openbook_market.base_lot_size = 1;
@@ -90,50 +95,54 @@ mod tests {
             .native_price_to_lot(I80F48::from_num(price))
             .ok();
 
-        (openbook_market, oracle_price_lots, event_heap, book)
+        (openbook_market, oracle_price_lots, book)
     }
 
     // Check what happens when one side of the book fills up
     #[test]
     fn book_bids_full() {
-        let (mut openbook_market, oracle_price_lots, mut event_heap, book_accs) =
-            test_setup(5000.0);
+        let (mut openbook_market, oracle_price_lots, book_accs) = test_setup(5000.0);
         let mut book = book_accs.orderbook();
+        let mut event_heap = book_accs.event_heap();
         let market_pk = Pubkey::new_unique();
 
-        let mut new_order =
-            |book: &mut Orderbook, event_heap: &mut EventHeap, side, price_lots, now_ts| -> u128 {
-                let mut account = OpenOrdersAccount::default_for_tests();
-
-                let max_base_lots = 1;
-                let time_in_force = 100;
-
-                book.new_order(
-                    &Order {
-                        side,
-                        max_base_lots,
-                        max_quote_lots_including_fees: i64::MAX / openbook_market.quote_lot_size,
-                        client_order_id: 0,
-                        time_in_force,
-                        params: OrderParams::Fixed {
-                            price_lots,
-                            order_type: PostOrderType::Limit,
-                        },
-                        self_trade_behavior: SelfTradeBehavior::DecrementTake,
+        let mut new_order = |book: &mut Orderbook,
+                             event_heap: &mut EventHeapRefMut,
+                             side,
+                             price_lots,
+                             now_ts|
+         -> u128 {
+            let mut account = OpenOrdersAccountRefMut::default_for_tests(MAX_OPEN_ORDERS);
+
+            let max_base_lots = 1;
+            let time_in_force = 100;
+
+            book.new_order(
+                &Order {
+                    side,
+                    max_base_lots,
+                    max_quote_lots_including_fees: i64::MAX / openbook_market.quote_lot_size,
+                    client_order_id: 0,
+                    time_in_force,
+                    params: OrderParams::Fixed {
+                        price_lots,
+                        order_type: PostOrderType::Limit,
                     },
-                    &mut openbook_market,
-                    &market_pk,
-                    event_heap,
-                    oracle_price_lots,
-                    Some(&mut account),
-                    &Pubkey::new_unique(),
-                    now_ts,
-                    u8::MAX,
-                    &[],
-                )
-                .unwrap();
-                account.open_order_by_raw_index(0).id
-            };
+                    self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                },
+                &mut openbook_market,
+                &market_pk,
+                event_heap,
+                oracle_price_lots,
+                Some(&mut account),
+                &Pubkey::new_unique(),
+                now_ts,
+                u8::MAX,
+                &[],
+            )
+            .unwrap();
+            account.open_order_by_raw_index(0).id
+        };
 
         // insert bids until book side is full
         for i in 1..10 {
@@ -160,8 +169,8 @@ mod tests {
         assert!(book.bids.is_full());
         assert_eq!(
             book.bids
-                .nodes
-                .min_leaf(&book.bids.roots[0])
+                .order_tree()
+                .min_leaf(&book.bids.header.roots[0])
                 .unwrap()
                 .1
                 .price_data(),
@@ -170,21 +179,21 @@ mod tests {
         assert_eq!(
             fixed_price_lots(
                 book.bids
-                    .nodes
-                    .max_leaf(&book.bids.roots[0])
+                    .order_tree()
+                    .max_leaf(&book.bids.header.roots[0])
                     .unwrap()
                     .1
                     .price_data()
             ),
-            (1000 + book.bids.roots[0].leaf_count) as i64
+            (1000 + book.bids.header.roots[0].leaf_count) as i64
         );
 
         // add another bid at a higher price before expiry, replacing the lowest-price one (1001)
         new_order(&mut book, &mut event_heap, Side::Bid, 1005, 1000000 - 1);
         assert_eq!(
             book.bids
-                .nodes
-                .min_leaf(&book.bids.roots[0])
+                .order_tree()
+                .min_leaf(&book.bids.header.roots[0])
                 .unwrap()
                 .1
                 .price_data(),
@@ -196,8 +205,8 @@ mod tests {
         new_order(&mut book, &mut event_heap, Side::Bid, 999, 2000000);
         assert_eq!(
             book.bids
-                .nodes
-                .min_leaf(&book.bids.roots[0])
+                .order_tree()
+                .min_leaf(&book.bids.header.roots[0])
                 .unwrap()
                 .1
                 .price_data(),
@@ -209,15 +218,15 @@ mod tests {
         // adding an ask will wipe up to three expired bids at the top of the book
         let bids_max = book
             .bids
-            .nodes
-            .max_leaf(&book.bids.roots[0])
+            .order_tree()
+            .max_leaf(&book.bids.header.roots[0])
             .unwrap()
             .1
             .price_data();
-        let bids_count = book.bids.roots[0].leaf_count;
+        let bids_count = book.bids.header.roots[0].leaf_count;
         new_order(&mut book, &mut event_heap, Side::Ask, 6000, 1500000);
-        assert_eq!(book.bids.roots[0].leaf_count, bids_count - 5);
-        assert_eq!(book.asks.roots[0].leaf_count, 1);
+        assert_eq!(book.bids.header.roots[0].leaf_count, bids_count - 5);
+        assert_eq!(book.asks.header.roots[0].leaf_count, 1);
         assert_eq!(event_heap.len(), 2 + 5);
         assert!(!order_tree_contains_price(&book.bids, bids_max));
         assert!(!order_tree_contains_price(&book.bids, bids_max - 1));
@@ -229,8 +238,9 @@ mod tests {
 
     #[test]
     fn book_new_order() {
-        let (mut market, oracle_price_lots, mut event_heap, book_accs) = test_setup(1000.0);
+        let (mut market, oracle_price_lots, book_accs) = test_setup(1000.0);
         let mut book = book_accs.orderbook();
+        let mut event_heap = book_accs.event_heap();
         let market_pk = Pubkey::new_unique();
 
         // Add lots and fees to make sure to exercise unit conversion
@@ -241,8 +251,8 @@ mod tests {
         market.maker_fee = maker_fee;
         market.taker_fee = taker_fee;
 
-        let mut maker = OpenOrdersAccount::default_for_tests();
-        let mut taker = OpenOrdersAccount::default_for_tests();
+        let mut maker = OpenOrdersAccountRefMut::default_for_tests(MAX_OPEN_ORDERS);
+        let mut taker = OpenOrdersAccountRefMut::default_for_tests(MAX_OPEN_ORDERS);
 
         let maker_pk = Pubkey::new_unique();
         let taker_pk = Pubkey::new_unique();
@@ -373,19 +383,20 @@ mod tests {
     // enough for a single lot
     #[test]
     fn book_max_quote_lots() {
-        let (mut market, oracle_price_lots, mut event_heap, book_accs) = test_setup(5000.0);
+        let (mut market, oracle_price_lots, book_accs) = test_setup(5000.0);
         let quote_lot_size = market.quote_lot_size;
         let mut book = book_accs.orderbook();
+        let mut event_heap = book_accs.event_heap();
         let market_pk = Pubkey::new_unique();
 
         let mut new_order = |book: &mut Orderbook,
-                             event_heap: &mut EventHeap,
+                             event_heap: &mut EventHeapRefMut,
                              side,
                              price_lots,
                              max_base_lots: i64,
                              max_quote_lots_including_fees: i64|
          -> u128 {
-            let mut account = OpenOrdersAccount::default_for_tests();
+            let mut account = OpenOrdersAccountRefMut::default_for_tests(MAX_OPEN_ORDERS);
 
             book.new_order(
                 &Order {
@@ -450,4 +461,246 @@ mod tests {
         new_order(&mut book, &mut event_heap, Side::Bid, 5005, 30, 1);
         assert_eq!(event_heap.len(), 1);
     }
+
+    #[test]
+    fn book_quote_order() {
+        let (mut market, oracle_price_lots, book_accs) = test_setup(5000.0);
+        market.taker_fee = 400;
+        let mut book = book_accs.orderbook();
+        let mut event_heap = book_accs.event_heap();
+        let market_pk = Pubkey::new_unique();
+
+        let mut maker = OpenOrdersAccountRefMut::default_for_tests(MAX_OPEN_ORDERS);
+        for price_lots in [5000, 5001, 5002] {
+            book.new_order(
+                &Order {
+                    side: Side::Ask,
+                    max_base_lots: 5,
+                    max_quote_lots_including_fees: i64::MAX / market.quote_lot_size,
+                    client_order_id: 0,
+                    time_in_force: 0,
+                    params: OrderParams::Fixed {
+                        price_lots,
+                        order_type: PostOrderType::Limit,
+                    },
+                    self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                },
+                &mut market,
+                &market_pk,
+                &mut event_heap,
+                oracle_price_lots,
+                Some(&mut maker),
+                &Pubkey::new_unique(),
+                0, // now_ts
+                u8::MAX,
+                &[],
+            )
+            .unwrap();
+        }
+
+        // fill or kill orders fail if the book can't fill them completely
+        let fill_or_kill = |max_base_lots| Order {
+            side: Side::Bid,
+            max_base_lots,
+            max_quote_lots_including_fees: i64::MAX / 2,
+            client_order_id: 0,
+            time_in_force: 0,
+            params: OrderParams::FillOrKill { price_lots: 5002 },
+            self_trade_behavior: SelfTradeBehavior::DecrementTake,
+        };
+        let seq_num = market.seq_num;
+        let mut quote = |order: &Order| {
+            book.quote_order(
+                order,
+                &market,
+                &mut event_heap,
+                oracle_price_lots,
+                0,
+                u8::MAX,
+                &[],
+            )
+        };
+        assert_eq!(
+            quote(&fill_or_kill(15)).unwrap().total_base_taken_native,
+            15
+        );
+        assert!(quote(&fill_or_kill(16)).is_err());
+
+        // quoting doesn't change the book, the event heap or the market
+        assert_eq!(book.asks.header.roots[0].leaf_count, 3);
+        assert_eq!(event_heap.len(), 0);
+        assert_eq!(market.seq_num, seq_num);
+
+        // quote, then check the quote against the actual execution
+        let mut quote_and_take = |side: Side, params: OrderParams, max_base_lots, limit| {
+            let order = Order {
+                side,
+                max_base_lots,
+                max_quote_lots_including_fees: i64::MAX / 2,
+                client_order_id: 0,
+                time_in_force: 0,
+                params,
+                self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            };
+            let quote = book.quote_order(
+                &order,
+                &market,
+                &mut event_heap,
+                oracle_price_lots,
+                0,
+                limit,
+                &[],
+            );
+            let taken = book.new_order(
+                &order,
+                &mut market,
+                &market_pk,
+                &mut event_heap,
+                oracle_price_lots,
+                None,
+                &Pubkey::new_unique(),
+                0, // now_ts
+                limit,
+                &[],
+            );
+            match (&quote, &taken) {
+                (Ok(quote), Ok(taken)) => {
+                    assert_eq!(quote.total_base_taken_native, taken.total_base_taken_native);
+                    assert_eq!(
+                        quote.total_quote_taken_native,
+                        taken.total_quote_taken_native
+                    );
+                    assert_eq!(quote.taker_fees_native, taken.taker_fees);
+                }
+                _ => assert!(quote.is_err() && taken.is_err()),
+            }
+            quote
+        };
+
+        let quote = quote_and_take(
+            Side::Bid,
+            OrderParams::ImmediateOrCancel { price_lots: 5001 },
+            7,
+            u8::MAX,
+        )
+        .unwrap();
+        assert_eq!(quote.total_base_taken_native, 7);
+        assert_eq!(quote.total_quote_taken_native, 5 * 5000 + 2 * 5001);
+        assert_eq!(quote.taker_fees_native, 15);
+        assert!(!quote.not_enough_liquidity);
+
+        // stops at the matching limit
+        let quote = quote_and_take(Side::Bid, OrderParams::Market, 100, 1).unwrap();
+        assert_eq!(quote.total_base_taken_native, 3);
+        assert!(!quote.not_enough_liquidity);
+
+        let quote = quote_and_take(
+            Side::Bid,
+            OrderParams::ImmediateOrCancel { price_lots: 5002 },
+            100,
+            u8::MAX,
+        )
+        .unwrap();
+        assert_eq!(quote.total_base_taken_native, 5);
+        assert!(quote.not_enough_liquidity);
+
+        let quote = quote_and_take(Side::Ask, OrderParams::Market, 1, u8::MAX).unwrap();
+        assert_eq!(
+            quote,
+            TakeQuote {
+                not_enough_liquidity: true,
+                ..TakeQuote::default()
+            }
+        );
+    }
+
+    // Check that slots past the fixed open orders and past u8::MAX work for placing,
+    // filling and cancelling orders
+    #[test]
+    fn book_large_open_orders_capacity() {
+        let (mut market, oracle_price_lots, book_accs) = test_setup(5000.0);
+        let mut book = book_accs.orderbook();
+        let mut event_heap = book_accs.event_heap();
+        let market_pk = Pubkey::new_unique();
+        let maker_pk = Pubkey::new_unique();
+
+        let capacity = 300;
+        let mut maker = OpenOrdersAccountRefMut::default_for_tests(capacity);
+        assert_eq!(maker.capacity(), capacity);
+
+        let mut new_order = |book: &mut Orderbook,
+                             event_heap: &mut EventHeapRefMut,
+                             account: &mut OpenOrdersAccountRefMut,
+                             account_pk: &Pubkey,
+                             side,
+                             price_lots,
+                             client_order_id| {
+            book.new_order(
+                &Order {
+                    side,
+                    max_base_lots: 1,
+                    max_quote_lots_including_fees: i64::MAX / market.quote_lot_size,
+                    client_order_id,
+                    time_in_force: 0,
+                    params: OrderParams::Fixed {
+                        price_lots,
+                        order_type: PostOrderType::Limit,
+                    },
+                    self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                },
+                &mut market,
+                &market_pk,
+                event_heap,
+                oracle_price_lots,
+                Some(account),
+                account_pk,
+                0, // now_ts
+                u8::MAX,
+                &[],
+            )
+            .unwrap();
+        };
+
+        for i in 0..capacity {
+            new_order(
+                &mut book,
+                &mut event_heap,
+                &mut maker,
+                &maker_pk,
+                Side::Bid,
+                1000 + i as i64,
+                i as u64,
+            );
+        }
+        assert!(maker.next_order_slot().is_err());
+
+        let last_order = *maker.open_order_by_raw_index(capacity - 1);
+        assert_eq!(last_order.client_id, capacity as u64 - 1);
+        let leaf = order_tree_leaf_by_key(&book.bids, last_order.id).unwrap();
+        assert_eq!(leaf.owner_slot_index(), capacity - 1);
+
+        // the best bid is the one in the last slot
+        let mut taker = OpenOrdersAccountRefMut::default_for_tests(MAX_OPEN_ORDERS);
+        new_order(
+            &mut book,
+            &mut event_heap,
+            &mut taker,
+            &Pubkey::new_unique(),
+            Side::Ask,
+            1000,
+            0,
+        );
+        assert_eq!(event_heap.len(), 1);
+        let fill: &FillEvent = bytemuck::cast_ref(event_heap.front().unwrap());
+        assert_eq!(fill.maker_slot_index(), capacity - 1);
+        maker.execute_maker(&mut market, fill);
+        assert!(maker.open_order_by_raw_index(capacity - 1).is_free());
+
+        book.cancel_all_orders(&mut maker, market, u8::MAX, None, None)
+            .unwrap();
+        book.cancel_all_orders(&mut maker, market, u8::MAX, None, None)
+            .unwrap();
+        assert_eq!(maker.all_orders_in_use().count(), 0);
+        assert_eq!(maker.position.bids_base_lots, 0);
+    }
 }
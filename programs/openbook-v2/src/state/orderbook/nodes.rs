@@ -147,14 +147,18 @@ pub struct LeafNode {
     /// NodeTag
     pub tag: u8,
 
-    /// Index into the owning OpenOrdersAccount's OpenOrders
+    /// Index into the owning OpenOrdersAccount's OpenOrders, low byte
     pub owner_slot: u8,
 
     /// Time in seconds after `timestamp` at which the order expires.
     /// A value of 0 means no expiry.
     pub time_in_force: u16,
 
-    pub padding: [u8; 4],
+    /// High byte of the index into the owning OpenOrdersAccount's OpenOrders,
+    /// see owner_slot_index()
+    pub owner_slot_hi: u8,
+
+    pub padding: [u8; 3],
 
     /// The binary tree key, see new_node_key()
     pub key: u128,
@@ -179,7 +183,7 @@ pub struct LeafNode {
 }
 const_assert_eq!(
     size_of::<LeafNode>(),
-    4 + 1 + 1 + 1 + 1 + 16 + 32 + 8 + 8 + 8 + 8
+    1 + 1 + 2 + 1 + 3 + 16 + 32 + 8 + 8 + 8 + 8
 );
 const_assert_eq!(size_of::<LeafNode>(), NODE_SIZE);
 const_assert_eq!(size_of::<LeafNode>() % 8, 0);
@@ -187,7 +191,7 @@ const_assert_eq!(size_of::<LeafNode>() % 8, 0);
 impl LeafNode {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
-        owner_slot: u8,
+        owner_slot: u16,
         key: u128,
         owner: Pubkey,
         quantity: i64,
@@ -198,8 +202,9 @@ impl LeafNode {
     ) -> Self {
         Self {
             tag: NodeTag::LeafNode.into(),
-            owner_slot,
+            owner_slot: owner_slot as u8,
             time_in_force,
+            owner_slot_hi: (owner_slot >> 8) as u8,
             padding: Default::default(),
             key,
             owner,
@@ -210,6 +215,12 @@ impl LeafNode {
         }
     }
 
+    /// Index into the owning OpenOrdersAccount's OpenOrders
+    #[inline(always)]
+    pub fn owner_slot_index(&self) -> usize {
+        ((self.owner_slot_hi as usize) << 8) | self.owner_slot as usize
+    }
+
     /// The order's price_data as stored in the key
     ///
     /// Needs to be unpacked differently for fixed and oracle pegged orders.
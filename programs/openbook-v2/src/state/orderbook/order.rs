@@ -174,7 +174,7 @@ impl Order {
 }
 
 /// The implicit limit price to use for market orders
-fn market_order_limit_for_side(side: Side) -> i64 {
+pub fn market_order_limit_for_side(side: Side) -> i64 {
     match side {
         Side::Bid => i64::MAX,
         Side::Ask => 1,
@@ -98,20 +98,30 @@ pub enum PostOrderType {
 )]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(u8)]
-/// Self trade behavior controls how taker orders interact with resting limit orders of the same account.
+/// Self trade behavior controls how taker orders interact with resting limit orders of the same account,
+/// or of another account of the same owner sharing a non-zero `stp_group`.
 /// This setting has no influence on placing a resting or oracle pegged limit order that does not match
 /// immediately, instead it's the responsibility of the user to correctly configure his taker orders.
 pub enum SelfTradeBehavior {
     /// Both the maker and taker sides of the matched orders are decremented.
     /// This is equivalent to a normal order match, except for the fact that no fees are applied.
+    /// Orders of other accounts in the same STP group are matched normally, including fees.
     #[default]
     DecrementTake = 0,
 
-    /// Cancels the maker side of the trade, the taker side gets matched with other maker's orders.
+    /// Cancels the maker side of the trade (the oldest order), the taker side gets matched
+    /// with other maker's orders.
     CancelProvide = 1,
 
     /// Cancels the whole transaction as soon as a self-matching scenario is encountered.
     AbortTransaction = 2,
+
+    /// Cancels the remainder of the taker order (the newest order), nothing is posted on the book.
+    /// The resting maker order is left untouched.
+    CancelTake = 3,
+
+    /// Cancels both the maker order and the remainder of the taker order.
+    CancelBoth = 4,
 }
 
 #[derive(
@@ -8,6 +8,8 @@ use super::*;
 use crate::error::OpenBookError;
 
 pub const MAX_ORDERTREE_NODES: usize = 1024;
+/// An empty order tree with fewer nodes would already be full
+pub const MIN_ORDERTREE_NODES: usize = 3;
 
 #[derive(
     Eq,
@@ -53,9 +55,9 @@ impl OrderTreeRoot {
     }
 }
 
-/// A binary tree on AnyNode::key()
+/// Layout of the nodes of a BookSide at the default capacity
 ///
-/// The key encodes the price in the top 64 bits.
+/// BookSide accounts can hold any number of nodes, see OrderTreeHeader and OrderTreeRef.
 #[zero_copy]
 pub struct OrderTreeNodes {
     pub order_tree_type: u8, // OrderTreeType, but that's not POD
@@ -73,28 +75,52 @@ const_assert_eq!(
 const_assert_eq!(std::mem::size_of::<OrderTreeNodes>(), 90640);
 const_assert_eq!(std::mem::size_of::<OrderTreeNodes>() % 8, 0);
 
-impl OrderTreeNodes {
-    pub fn order_tree_type(&self) -> OrderTreeType {
-        OrderTreeType::try_from(self.order_tree_type).unwrap()
+/// The fields of OrderTreeNodes in front of the nodes
+#[zero_copy]
+pub struct OrderTreeHeader {
+    pub order_tree_type: u8, // OrderTreeType, but that's not POD
+    pub padding: [u8; 3],
+    pub bump_index: u32,
+    pub free_list_len: u32,
+    pub free_list_head: NodeHandle,
+    pub reserved: [u8; 512],
+}
+const_assert_eq!(
+    std::mem::size_of::<OrderTreeHeader>() + 88 * MAX_ORDERTREE_NODES,
+    std::mem::size_of::<OrderTreeNodes>()
+);
+const_assert_eq!(std::mem::size_of::<OrderTreeHeader>() % 8, 0);
+
+/// A binary tree on AnyNode::key()
+///
+/// The key encodes the price in the top 64 bits. The tree can use all of `nodes`, whatever
+/// their number.
+#[derive(Clone, Copy)]
+pub struct OrderTreeRef<'a> {
+    pub header: &'a OrderTreeHeader,
+    pub nodes: &'a [AnyNode],
+}
+
+/// Same as OrderTreeRef, but mut
+pub struct OrderTreeRefMut<'a> {
+    pub header: &'a mut OrderTreeHeader,
+    pub nodes: &'a mut [AnyNode],
+}
+
+impl<'a> OrderTreeRef<'a> {
+    pub fn order_tree_type(self) -> OrderTreeType {
+        OrderTreeType::try_from(self.header.order_tree_type).unwrap()
     }
 
     /// Iterate over all entries, including invalid orders
     ///
     /// smallest to highest for asks
     /// highest to smallest for bids
-    pub fn iter(&self, root: &OrderTreeRoot) -> OrderTreeIter {
+    pub fn iter(self, root: &OrderTreeRoot) -> OrderTreeIter<'a> {
         OrderTreeIter::new(self, root)
     }
 
-    pub fn node_mut(&mut self, handle: NodeHandle) -> Option<&mut AnyNode> {
-        let node = &mut self.nodes[handle as usize];
-        let tag = NodeTag::try_from(node.tag);
-        match tag {
-            Ok(NodeTag::InnerNode) | Ok(NodeTag::LeafNode) => Some(node),
-            _ => None,
-        }
-    }
-    pub fn node(&self, handle: NodeHandle) -> Option<&AnyNode> {
+    pub fn node(self, handle: NodeHandle) -> Option<&'a AnyNode> {
         let node = &self.nodes[handle as usize];
         let tag = NodeTag::try_from(node.tag);
         match tag {
@@ -103,35 +129,17 @@ impl OrderTreeNodes {
         }
     }
 
-    pub fn remove_worst(&mut self, root: &mut OrderTreeRoot) -> Option<LeafNode> {
-        self.remove_by_key(root, self.find_worst(root)?.1.key)
-    }
-
-    pub fn find_worst(&self, root: &OrderTreeRoot) -> Option<(NodeHandle, &LeafNode)> {
+    pub fn find_worst(self, root: &OrderTreeRoot) -> Option<(NodeHandle, &'a LeafNode)> {
         match self.order_tree_type() {
             OrderTreeType::Bids => self.min_leaf(root),
             OrderTreeType::Asks => self.max_leaf(root),
         }
     }
 
-    /// Remove the order with the lowest expiry timestamp, if that's < now_ts.
-    pub fn remove_one_expired(
-        &mut self,
-        root: &mut OrderTreeRoot,
-        now_ts: u64,
-    ) -> Option<LeafNode> {
-        let (handle, expires_at) = self.find_earliest_expiry(root)?;
-        if expires_at < now_ts {
-            self.remove_by_key(root, self.node(handle)?.key()?)
-        } else {
-            None
-        }
-    }
-
     // only for fixed-price ordertrees
     #[cfg(test)]
     #[allow(dead_code)]
-    fn as_price_quantity_vec(&self, root: &OrderTreeRoot, reverse: bool) -> Vec<(i64, i64)> {
+    fn as_price_quantity_vec(self, root: &OrderTreeRoot, reverse: bool) -> Vec<(i64, i64)> {
         let mut pqs = vec![];
         let mut current: NodeHandle = match root.node() {
             None => return pqs,
@@ -163,18 +171,18 @@ impl OrderTreeNodes {
         }
     }
 
-    pub fn min_leaf(&self, root: &OrderTreeRoot) -> Option<(NodeHandle, &LeafNode)> {
+    pub fn min_leaf(self, root: &OrderTreeRoot) -> Option<(NodeHandle, &'a LeafNode)> {
         self.leaf_min_max(false, root)
     }
 
-    pub fn max_leaf(&self, root: &OrderTreeRoot) -> Option<(NodeHandle, &LeafNode)> {
+    pub fn max_leaf(self, root: &OrderTreeRoot) -> Option<(NodeHandle, &'a LeafNode)> {
         self.leaf_min_max(true, root)
     }
     fn leaf_min_max(
-        &self,
+        self,
         find_max: bool,
         root: &OrderTreeRoot,
-    ) -> Option<(NodeHandle, &LeafNode)> {
+    ) -> Option<(NodeHandle, &'a LeafNode)> {
         let mut node_handle: NodeHandle = root.node()?;
 
         let i = usize::from(find_max);
@@ -191,6 +199,73 @@ impl OrderTreeNodes {
         }
     }
 
+    pub fn is_full(self) -> bool {
+        self.header.free_list_len <= 1 && (self.header.bump_index as usize) >= self.nodes.len() - 1
+    }
+
+    /// Returns the handle of the node with the lowest expiry timestamp, and this timestamp
+    pub fn find_earliest_expiry(self, root: &OrderTreeRoot) -> Option<(NodeHandle, u64)> {
+        let mut current: NodeHandle = match root.node() {
+            Some(h) => h,
+            None => return None,
+        };
+
+        loop {
+            let contents = *self.node(current).unwrap();
+            match contents.case() {
+                None => unreachable!(),
+                Some(NodeRef::Inner(inner)) => {
+                    current = inner.children[(inner.child_earliest_expiry[0]
+                        > inner.child_earliest_expiry[1])
+                        as usize];
+                }
+                _ => {
+                    return Some((current, contents.earliest_expiry()));
+                }
+            };
+        }
+    }
+}
+
+impl<'a> OrderTreeRefMut<'a> {
+    pub fn to_ref(&self) -> OrderTreeRef<'_> {
+        OrderTreeRef {
+            header: self.header,
+            nodes: self.nodes,
+        }
+    }
+
+    pub fn node(&self, handle: NodeHandle) -> Option<&AnyNode> {
+        self.to_ref().node(handle)
+    }
+
+    pub fn node_mut(&mut self, handle: NodeHandle) -> Option<&mut AnyNode> {
+        let node = &mut self.nodes[handle as usize];
+        let tag = NodeTag::try_from(node.tag);
+        match tag {
+            Ok(NodeTag::InnerNode) | Ok(NodeTag::LeafNode) => Some(node),
+            _ => None,
+        }
+    }
+
+    pub fn remove_worst(&mut self, root: &mut OrderTreeRoot) -> Option<LeafNode> {
+        self.remove_by_key(root, self.to_ref().find_worst(root)?.1.key)
+    }
+
+    /// Remove the order with the lowest expiry timestamp, if that's < now_ts.
+    pub fn remove_one_expired(
+        &mut self,
+        root: &mut OrderTreeRoot,
+        now_ts: u64,
+    ) -> Option<LeafNode> {
+        let (handle, expires_at) = self.to_ref().find_earliest_expiry(root)?;
+        if expires_at < now_ts {
+            self.remove_by_key(root, self.node(handle)?.key()?)
+        } else {
+            None
+        }
+    }
+
     pub fn remove_by_key(
         &mut self,
         root: &mut OrderTreeRoot,
@@ -255,19 +330,19 @@ impl OrderTreeNodes {
         let val = *self.node(key)?;
 
         self.nodes[key as usize] = cast(FreeNode {
-            tag: if self.free_list_len == 0 {
+            tag: if self.header.free_list_len == 0 {
                 NodeTag::LastFreeNode.into()
             } else {
                 NodeTag::FreeNode.into()
             },
             padding: Default::default(),
-            next: self.free_list_head,
+            next: self.header.free_list_head,
             reserved: [0; 72],
             force_align: 0,
         });
 
-        self.free_list_len += 1;
-        self.free_list_head = key;
+        self.header.free_list_len += 1;
+        self.header.free_list_head = key;
         Some(val)
     }
 
@@ -278,29 +353,30 @@ impl OrderTreeNodes {
             _ => unreachable!(),
         };
 
-        if self.free_list_len == 0 {
+        if self.header.free_list_len == 0 {
             require!(
-                (self.bump_index as usize) < self.nodes.len() && self.bump_index < u32::MAX,
+                (self.header.bump_index as usize) < self.nodes.len()
+                    && self.header.bump_index < u32::MAX,
                 OpenBookError::SomeError
             );
 
-            self.nodes[self.bump_index as usize] = *val;
-            let key = self.bump_index;
-            self.bump_index += 1;
+            self.nodes[self.header.bump_index as usize] = *val;
+            let key = self.header.bump_index;
+            self.header.bump_index += 1;
             return Ok(key);
         }
 
-        let key = self.free_list_head;
+        let key = self.header.free_list_head;
         let node = &mut self.nodes[key as usize];
 
         match NodeTag::try_from(node.tag) {
-            Ok(NodeTag::FreeNode) => assert!(self.free_list_len > 1),
-            Ok(NodeTag::LastFreeNode) => assert_eq!(self.free_list_len, 1),
+            Ok(NodeTag::FreeNode) => assert!(self.header.free_list_len > 1),
+            Ok(NodeTag::LastFreeNode) => assert_eq!(self.header.free_list_len, 1),
             _ => unreachable!(),
         };
 
-        self.free_list_head = cast_ref::<AnyNode, FreeNode>(node).next;
-        self.free_list_len -= 1;
+        self.header.free_list_head = cast_ref::<AnyNode, FreeNode>(node).next;
+        self.header.free_list_len -= 1;
         *node = *val;
         Ok(key)
     }
@@ -396,10 +472,6 @@ impl OrderTreeNodes {
         }
     }
 
-    pub fn is_full(&self) -> bool {
-        self.free_list_len <= 1 && (self.bump_index as usize) >= self.nodes.len() - 1
-    }
-
     /// When a node changes, the parents' child_earliest_expiry may need to be updated.
     ///
     /// This function walks up the `stack` of parents and applies the change where the
@@ -422,29 +494,6 @@ impl OrderTreeNodes {
             new_expiry = parent.earliest_expiry();
         }
     }
-
-    /// Returns the handle of the node with the lowest expiry timestamp, and this timestamp
-    pub fn find_earliest_expiry(&self, root: &OrderTreeRoot) -> Option<(NodeHandle, u64)> {
-        let mut current: NodeHandle = match root.node() {
-            Some(h) => h,
-            None => return None,
-        };
-
-        loop {
-            let contents = *self.node(current).unwrap();
-            match contents.case() {
-                None => unreachable!(),
-                Some(NodeRef::Inner(inner)) => {
-                    current = inner.children[(inner.child_earliest_expiry[0]
-                        > inner.child_earliest_expiry[1])
-                        as usize];
-                }
-                _ => {
-                    return Some((current, contents.earliest_expiry()));
-                }
-            };
-        }
-    }
 }
 
 #[cfg(test)]
@@ -453,21 +502,24 @@ mod tests {
     use super::*;
     use bytemuck::Zeroable;
 
-    fn new_order_tree(order_tree_type: OrderTreeType) -> OrderTreeNodes {
-        let mut ot = OrderTreeNodes::zeroed();
-        ot.order_tree_type = order_tree_type.into();
-        ot
+    fn new_order_tree(
+        order_tree_type: OrderTreeType,
+        capacity: usize,
+    ) -> (OrderTreeHeader, Vec<AnyNode>) {
+        let mut header = OrderTreeHeader::zeroed();
+        header.order_tree_type = order_tree_type.into();
+        (header, vec![AnyNode::zeroed(); capacity])
     }
 
-    fn verify_order_tree(order_tree: &OrderTreeNodes, root: &OrderTreeRoot) {
+    fn verify_order_tree(order_tree: OrderTreeRef, root: &OrderTreeRoot) {
         verify_order_tree_invariant(order_tree, root);
         verify_order_tree_iteration(order_tree, root);
         verify_order_tree_expiry(order_tree, root);
     }
 
     // check that BookSide binary tree key invariant holds
-    fn verify_order_tree_invariant(order_tree: &OrderTreeNodes, root: &OrderTreeRoot) {
-        fn recursive_check(order_tree: &OrderTreeNodes, h: NodeHandle) {
+    fn verify_order_tree_invariant(order_tree: OrderTreeRef, root: &OrderTreeRoot) {
+        fn recursive_check(order_tree: OrderTreeRef, h: NodeHandle) {
             if let NodeRef::Inner(&inner) = order_tree.node(h).unwrap().case().unwrap() {
                 let left = order_tree.node(inner.children[0]).unwrap().key().unwrap();
                 let right = order_tree.node(inner.children[1]).unwrap().key().unwrap();
@@ -492,7 +544,7 @@ mod tests {
     }
 
     // check that iteration of order tree has the right order and misses no leaves
-    fn verify_order_tree_iteration(order_tree: &OrderTreeNodes, root: &OrderTreeRoot) {
+    fn verify_order_tree_iteration(order_tree: OrderTreeRef, root: &OrderTreeRoot) {
         let mut total = 0;
         let ascending = order_tree.order_tree_type() == OrderTreeType::Asks;
         let mut last_key = if ascending { 0 } else { u128::MAX };
@@ -510,8 +562,8 @@ mod tests {
     }
 
     // check that BookSide::child_expiry invariant holds
-    fn verify_order_tree_expiry(order_tree: &OrderTreeNodes, root: &OrderTreeRoot) {
-        fn recursive_check(order_tree: &OrderTreeNodes, h: NodeHandle) {
+    fn verify_order_tree_expiry(order_tree: OrderTreeRef, root: &OrderTreeRoot) {
+        fn recursive_check(order_tree: OrderTreeRef, h: NodeHandle) {
             if let NodeRef::Inner(&inner) = order_tree.node(h).unwrap().case().unwrap() {
                 let left = order_tree
                     .node(inner.children[0])
@@ -537,40 +589,53 @@ mod tests {
 
     #[test]
     fn order_tree_expiry_manual() {
-        let mut bids = new_order_tree(OrderTreeType::Bids);
+        let (mut header, mut nodes) = new_order_tree(OrderTreeType::Bids, MAX_ORDERTREE_NODES);
+        let mut bids = OrderTreeRefMut {
+            header: &mut header,
+            nodes: &mut nodes,
+        };
         let new_expiring_leaf = |key: u128, expiry: u64| {
             LeafNode::new(0, key, Pubkey::default(), 0, expiry - 1, 1, -1, 0)
         };
 
         let mut root = OrderTreeRoot::zeroed();
 
-        assert!(bids.find_earliest_expiry(&root).is_none());
+        assert!(bids.to_ref().find_earliest_expiry(&root).is_none());
 
         bids.insert_leaf(&mut root, &new_expiring_leaf(0, 5000))
             .unwrap();
         assert_eq!(
-            bids.find_earliest_expiry(&root).unwrap(),
+            bids.to_ref().find_earliest_expiry(&root).unwrap(),
             (root.maybe_node, 5000)
         );
-        verify_order_tree(&bids, &root);
+        verify_order_tree(bids.to_ref(), &root);
 
         let (new4000_h, _) = bids
             .insert_leaf(&mut root, &new_expiring_leaf(1, 4000))
             .unwrap();
-        assert_eq!(bids.find_earliest_expiry(&root).unwrap(), (new4000_h, 4000));
-        verify_order_tree(&bids, &root);
+        assert_eq!(
+            bids.to_ref().find_earliest_expiry(&root).unwrap(),
+            (new4000_h, 4000)
+        );
+        verify_order_tree(bids.to_ref(), &root);
 
         let (_new4500_h, _) = bids
             .insert_leaf(&mut root, &new_expiring_leaf(2, 4500))
             .unwrap();
-        assert_eq!(bids.find_earliest_expiry(&root).unwrap(), (new4000_h, 4000));
-        verify_order_tree(&bids, &root);
+        assert_eq!(
+            bids.to_ref().find_earliest_expiry(&root).unwrap(),
+            (new4000_h, 4000)
+        );
+        verify_order_tree(bids.to_ref(), &root);
 
         let (new3500_h, _) = bids
             .insert_leaf(&mut root, &new_expiring_leaf(3, 3500))
             .unwrap();
-        assert_eq!(bids.find_earliest_expiry(&root).unwrap(), (new3500_h, 3500));
-        verify_order_tree(&bids, &root);
+        assert_eq!(
+            bids.to_ref().find_earliest_expiry(&root).unwrap(),
+            (new3500_h, 3500)
+        );
+        verify_order_tree(bids.to_ref(), &root);
         // the first two levels of the tree are innernodes, with 0;1 on one side and 2;3 on the other
         assert_eq!(
             bids.node_mut(root.maybe_node)
@@ -582,7 +647,7 @@ mod tests {
         );
 
         bids.remove_by_key(&mut root, 3).unwrap();
-        verify_order_tree(&bids, &root);
+        verify_order_tree(bids.to_ref(), &root);
         assert_eq!(
             bids.node_mut(root.maybe_node)
                 .unwrap()
@@ -591,10 +656,10 @@ mod tests {
                 .child_earliest_expiry,
             [4000, 4500]
         );
-        assert_eq!(bids.find_earliest_expiry(&root).unwrap().1, 4000);
+        assert_eq!(bids.to_ref().find_earliest_expiry(&root).unwrap().1, 4000);
 
         bids.remove_by_key(&mut root, 0).unwrap();
-        verify_order_tree(&bids, &root);
+        verify_order_tree(bids.to_ref(), &root);
         assert_eq!(
             bids.node_mut(root.maybe_node)
                 .unwrap()
@@ -603,15 +668,15 @@ mod tests {
                 .child_earliest_expiry,
             [4000, 4500]
         );
-        assert_eq!(bids.find_earliest_expiry(&root).unwrap().1, 4000);
+        assert_eq!(bids.to_ref().find_earliest_expiry(&root).unwrap().1, 4000);
 
         bids.remove_by_key(&mut root, 1).unwrap();
-        verify_order_tree(&bids, &root);
-        assert_eq!(bids.find_earliest_expiry(&root).unwrap().1, 4500);
+        verify_order_tree(bids.to_ref(), &root);
+        assert_eq!(bids.to_ref().find_earliest_expiry(&root).unwrap().1, 4500);
 
         bids.remove_by_key(&mut root, 2).unwrap();
-        verify_order_tree(&bids, &root);
-        assert!(bids.find_earliest_expiry(&root).is_none());
+        verify_order_tree(bids.to_ref(), &root);
+        assert!(bids.to_ref().find_earliest_expiry(&root).is_none());
     }
 
     #[test]
@@ -620,7 +685,11 @@ mod tests {
         let mut rng = rand::thread_rng();
 
         let mut root = OrderTreeRoot::zeroed();
-        let mut bids = new_order_tree(OrderTreeType::Bids);
+        let (mut header, mut nodes) = new_order_tree(OrderTreeType::Bids, MAX_ORDERTREE_NODES);
+        let mut bids = OrderTreeRefMut {
+            header: &mut header,
+            nodes: &mut nodes,
+        };
         let new_expiring_leaf = |key: u128, expiry: u64| {
             LeafNode::new(0, key, Pubkey::default(), 0, expiry - 1, 1, -1, 0)
         };
@@ -636,7 +705,7 @@ mod tests {
             keys.push(key);
             bids.insert_leaf(&mut root, &new_expiring_leaf(key, expiry))
                 .unwrap();
-            verify_order_tree(&bids, &root);
+            verify_order_tree(bids.to_ref(), &root);
         }
 
         // remove 50 at random
@@ -647,7 +716,34 @@ mod tests {
             let k = keys[rng.gen_range(0..keys.len())];
             bids.remove_by_key(&mut root, k).unwrap();
             keys.retain(|v| *v != k);
-            verify_order_tree(&bids, &root);
+            verify_order_tree(bids.to_ref(), &root);
+        }
+    }
+
+    #[test]
+    fn order_tree_small_capacity() {
+        let (mut header, mut nodes) = new_order_tree(OrderTreeType::Asks, 9);
+        let mut asks = OrderTreeRefMut {
+            header: &mut header,
+            nodes: &mut nodes,
+        };
+        let new_leaf = |key: u128| LeafNode::new(0, key, Pubkey::default(), 0, 1, 0, -1, 0);
+
+        // every leaf after the first also takes an inner node, so 9 nodes fit 5 leaves
+        let mut root = OrderTreeRoot::zeroed();
+        for key in 0..5 {
+            assert!(!asks.to_ref().is_full());
+            asks.insert_leaf(&mut root, &new_leaf(key)).unwrap();
         }
+        assert!(asks.to_ref().is_full());
+        assert!(asks.insert_leaf(&mut root, &new_leaf(5)).is_err());
+        verify_order_tree(asks.to_ref(), &root);
+        assert_eq!(root.leaf_count, 5);
+
+        asks.remove_by_key(&mut root, 0).unwrap();
+        assert!(!asks.to_ref().is_full());
+        asks.insert_leaf(&mut root, &new_leaf(5)).unwrap();
+        verify_order_tree(asks.to_ref(), &root);
+        assert_eq!(root.leaf_count, 5);
     }
 }
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+// Pyth receiver program, which owns the price update accounts
+declare_id!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// How many Wormhole guardian signatures were checked when posting the update
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    /// The timestamp of this price update in seconds
+    pub publish_time: i64,
+    /// The timestamp of the previous price update
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// A price update account of the Pyth pull oracle
+///
+/// Serialized with borsh, the verification level makes its length variable.
+#[account]
+#[derive(Debug)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    /// Slot at which the update was posted
+    pub posted_slot: u64,
+}
@@ -0,0 +1,82 @@
+use anchor_lang::{account, declare_id, zero_copy};
+use solana_program::pubkey::Pubkey;
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+#[cfg(feature = "devnet")]
+declare_id!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
+#[cfg(not(feature = "devnet"))]
+declare_id!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// Values of on-demand feeds are fixed point numbers with 18 decimals
+pub const PRECISION_SCALE: f64 = 1e18;
+
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default, Debug)]
+pub struct OracleSubmission {
+    pub oracle: Pubkey,
+    pub slot: u64,
+    pub landed_at: u64,
+    pub value: i128,
+}
+
+/// Aggregate of the latest oracle submissions
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default, Debug)]
+pub struct CurrentResult {
+    /// Median of the submissions
+    pub value: i128,
+    pub std_dev: i128,
+    pub mean: i128,
+    pub range: i128,
+    pub min_value: i128,
+    pub max_value: i128,
+    pub num_samples: u8,
+    pub submission_idx: u8,
+    pub padding1: [u8; 6],
+    /// Slot of the submissions the result was computed from
+    pub slot: u64,
+    pub min_slot: u64,
+    pub max_slot: u64,
+}
+
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default, Debug)]
+pub struct CompactResult {
+    pub std_dev: f32,
+    pub mean: f32,
+    pub slot: u64,
+}
+
+/// A Switchboard on-demand pull feed
+#[account(zero_copy(unsafe))]
+#[repr(packed)]
+#[derive(Debug)]
+pub struct PullFeedAccountData {
+    pub submissions: [OracleSubmission; 32],
+    pub authority: Pubkey,
+    pub queue: Pubkey,
+    pub feed_hash: [u8; 32],
+    pub initialized_at: i64,
+    pub permissions: u64,
+    pub max_variance: u64,
+    pub min_responses: u32,
+    pub name: [u8; 32],
+    pub padding1: [u8; 2],
+    pub historical_result_idx: u8,
+    pub min_sample_size: u8,
+    pub last_update_timestamp: i64,
+    pub lut_slot: u64,
+    pub reserved1: [u8; 32],
+    pub result: CurrentResult,
+    pub max_staleness: u32,
+    pub padding2: [u8; 12],
+    pub historical_results: [CompactResult; 32],
+    pub ebuf4: [u8; 8],
+    pub ebuf3: [u8; 24],
+    pub ebuf2: [u8; 256],
+}
+const_assert_eq!(size_of::<PullFeedAccountData>(), 3200);
@@ -2,6 +2,8 @@ use super::*;
 use anchor_lang::system_program;
 use anchor_spl::token;
 
+use crate::state::Market;
+
 pub fn token_transfer<
     'info,
     P: ToAccountInfo<'info>,
@@ -88,3 +90,41 @@ pub fn system_program_transfer<
         Ok(())
     }
 }
+
+/// Move lamports out of an account owned by the program, which can't go through the system
+/// program
+pub fn program_account_lamports_transfer<
+    'info,
+    A: ToAccountInfo<'info>,
+    L: ToAccountInfo<'info>,
+>(
+    amount: u64,
+    from: &A,
+    to: &L,
+) -> Result<()> {
+    if amount > 0 {
+        **from.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **to.to_account_info().try_borrow_mut_lamports()? += amount;
+    }
+    Ok(())
+}
+
+/// Pay the crank reward for `events` processed events out of the market's penalty pool
+///
+/// The reward stays in the pool if the receiver wouldn't be rent exempt afterwards, as the
+/// transaction would fail.
+pub fn pay_crank_reward<'info, A: ToAccountInfo<'info>, L: ToAccountInfo<'info>>(
+    market: &mut Market,
+    events: u64,
+    market_account: &A,
+    receiver: &L,
+) -> Result<()> {
+    let reward = market.crank_reward_for(events);
+    let receiver = receiver.to_account_info();
+    if reward == 0 || !Rent::get()?.is_exempt(receiver.lamports() + reward, receiver.data_len()) {
+        return Ok(());
+    }
+
+    market.penalty_pool -= reward;
+    program_account_lamports_transfer(reward, market_account, &receiver)
+}
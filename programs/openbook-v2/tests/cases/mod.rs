@@ -13,16 +13,28 @@ pub use super::program_test;
 pub use utils::assert_equal_fixed_f64 as assert_equal;
 
 mod test;
+mod test_auto_settle;
 mod test_crank;
 mod test_create_market;
 mod test_edit_order;
 mod test_fees;
 mod test_fill_or_kill_order;
+mod test_heartbeat;
 mod test_indexer;
+mod test_ioc;
+mod test_l2_depth;
+mod test_market_capacity;
 mod test_multiple_orders;
+mod test_open_orders_capacity;
 mod test_oracle_peg;
 mod test_order_types;
 mod test_permissioned;
 mod test_place_order_remaining;
+mod test_prune_expired_orders;
+mod test_quote_take_order;
+mod test_risk_limits;
 mod test_self_trade;
+mod test_session_delegate;
+mod test_settle_funds_batch;
 mod test_take_order;
+mod test_transfer_open_orders_account;
@@ -781,3 +781,196 @@ async fn test_expired_orders() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_withdraw() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    send_tx(
+        solana,
+        DepositInstruction {
+            owner,
+            market,
+            open_orders_account: account_1,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            base_amount: 10000,
+            quote_amount: 5000,
+        },
+    )
+    .await
+    .unwrap();
+
+    let balance_base = solana.token_account_balance(owner_token_0).await;
+    let balance_quote = solana.token_account_balance(owner_token_1).await;
+    let base_deposit_total = solana
+        .get_account::<Market>(market)
+        .await
+        .base_deposit_total;
+
+    let withdraw_ix = WithdrawInstruction {
+        owner,
+        market,
+        open_orders_account: account_1,
+        market_base_vault,
+        market_quote_vault,
+        user_base_account: owner_token_0,
+        user_quote_account: owner_token_1,
+        base_amount: 4000,
+        quote_amount: 0,
+    };
+    send_tx(solana, withdraw_ix).await.unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.base_free_native, 6000);
+        assert_eq!(open_orders_account_1.position.quote_free_native, 5000);
+        assert_eq!(
+            balance_base + 4000,
+            solana.token_account_balance(owner_token_0).await
+        );
+        assert_eq!(
+            balance_quote,
+            solana.token_account_balance(owner_token_1).await
+        );
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.base_deposit_total, base_deposit_total - 4000);
+    }
+
+    // can't withdraw more than the free funds
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            WithdrawInstruction {
+                owner,
+                market,
+                open_orders_account: account_1,
+                market_base_vault,
+                market_quote_vault,
+                user_base_account: owner_token_0,
+                user_quote_account: owner_token_1,
+                base_amount: 0,
+                quote_amount: 5001,
+            }
+        )
+        .await,
+        Some(OpenBookError::InsufficientFreeFunds.into())
+    );
+
+    send_tx(
+        solana,
+        WithdrawInstruction {
+            owner,
+            market,
+            open_orders_account: account_1,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            base_amount: 6000,
+            quote_amount: 5000,
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.base_free_native, 0);
+        assert_eq!(open_orders_account_1.position.quote_free_native, 0);
+        assert_eq!(
+            balance_base + 10000,
+            solana.token_account_balance(owner_token_0).await
+        );
+        assert_eq!(
+            balance_quote + 5000,
+            solana.token_account_balance(owner_token_1).await
+        );
+    }
+
+    // withdrawing collects the event heap penalty like settle_funds
+    let place_ask_ix = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, place_ask_ix.clone()).await.unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            ..place_ask_ix
+        },
+    )
+    .await
+    .unwrap();
+
+    let market_before = solana.get_account::<Market>(market).await;
+    assert_eq!(
+        solana
+            .get_account::<OpenOrdersAccount>(account_1)
+            .await
+            .position
+            .penalty_heap_count,
+        1
+    );
+
+    send_tx(
+        solana,
+        WithdrawInstruction {
+            owner,
+            market,
+            open_orders_account: account_1,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            base_amount: 0,
+            quote_amount: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.penalty_heap_count, 0);
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(
+            market.penalty_pool,
+            market_before.penalty_pool + market_before.penalty_event_heap
+        );
+    }
+
+    Ok(())
+}
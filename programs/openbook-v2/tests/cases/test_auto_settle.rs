@@ -0,0 +1,220 @@
+use super::*;
+
+#[tokio::test]
+async fn test_auto_settle() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        mints,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+    let owner_base_ata = solana
+        .create_associated_token_account(&owner.pubkey(), mints[0].pubkey)
+        .await;
+    let owner_quote_ata = solana
+        .create_associated_token_account(&owner.pubkey(), mints[1].pubkey)
+        .await;
+
+    send_tx(
+        solana,
+        SetAutoSettleInstruction {
+            enabled: true,
+            owner,
+            open_orders_account: account_1,
+        },
+    )
+    .await
+    .unwrap();
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    let place_ask_ix = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        ..place_bid_ix.clone()
+    };
+
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+    send_tx(solana, place_ask_ix.clone()).await.unwrap();
+
+    // token accounts other than the owner's associated ones aren't paid, the fill is only credited
+    send_tx(
+        solana,
+        ConsumeEventsAndSettleInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1],
+            user_token_accounts: vec![owner_token_0, owner_token_1],
+        },
+    )
+    .await
+    .unwrap();
+
+    let (maker_base_free, maker_quote_free) = {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.base_free_native, 100);
+        (
+            open_orders_account_1.position.base_free_native,
+            open_orders_account_1.position.quote_free_native,
+        )
+    };
+
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+    send_tx(solana, place_ask_ix.clone()).await.unwrap();
+
+    let balance_base = solana.token_account_balance(owner_base_ata).await;
+    let balance_quote = solana.token_account_balance(owner_quote_ata).await;
+    let market_before = solana.get_account::<Market>(market).await;
+
+    // only the funds credited by the fill are paid out, earlier free funds stay
+    send_tx(
+        solana,
+        ConsumeEventsAndSettleInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1],
+            user_token_accounts: vec![owner_base_ata, owner_quote_ata],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(
+            open_orders_account_1.position.base_free_native,
+            maker_base_free
+        );
+        // the earlier quote rebate was locked for the second bid
+        assert_eq!(open_orders_account_1.position.quote_free_native, 0);
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+
+        assert_eq!(
+            balance_base + maker_base_free,
+            solana.token_account_balance(owner_base_ata).await
+        );
+        assert_eq!(
+            balance_quote + maker_quote_free,
+            solana.token_account_balance(owner_quote_ata).await
+        );
+
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(
+            market.base_deposit_total,
+            market_before.base_deposit_total - maker_base_free
+        );
+    }
+
+    // makers that didn't opt in keep their funds on the account
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            ..place_ask_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    let balance_base = solana.token_account_balance(owner_base_ata).await;
+    send_tx(
+        solana,
+        ConsumeEventsAndSettleInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_2],
+            user_token_accounts: vec![owner_base_ata, owner_quote_ata],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(open_orders_account_2.position.base_free_native, 100);
+        assert_eq!(
+            balance_base,
+            solana.token_account_balance(owner_base_ata).await
+        );
+    }
+
+    // fills executed while matching are settled like consumed events when the
+    // settlement accounts are passed along with the maker
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+
+    let market_authority = solana.get_account::<Market>(market).await.market_authority;
+    let balance_base = solana.token_account_balance(owner_base_ata).await;
+    let market_before = solana.get_account::<Market>(market).await;
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            remainings: vec![
+                account_1,
+                market_authority,
+                market_base_vault,
+                market_quote_vault,
+                anchor_spl::token::ID,
+                owner_base_ata,
+                owner_quote_ata,
+            ],
+            ..place_ask_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(
+            open_orders_account_1.position.base_free_native,
+            maker_base_free
+        );
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+
+        // the taker's 100 base went straight to the maker's associated token account
+        assert_eq!(
+            balance_base + 100,
+            solana.token_account_balance(owner_base_ata).await
+        );
+
+        let market = solana.get_account::<Market>(market).await;
+        assert_eq!(market.base_deposit_total, market_before.base_deposit_total);
+    }
+
+    Ok(())
+}
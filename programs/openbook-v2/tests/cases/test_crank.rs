@@ -156,9 +156,9 @@ async fn test_skip_missing_accounts() -> Result<(), TransportError> {
     {
         let event_heap = solana.get_account_boxed::<EventHeap>(event_heap).await;
         assert_eq!(event_heap.header.count(), 3);
-        assert_eq!(fill_maker(event_heap.at_slot(0).unwrap()), maker_1);
-        assert_eq!(fill_maker(event_heap.at_slot(1).unwrap()), maker_2);
-        assert_eq!(fill_maker(event_heap.at_slot(2).unwrap()), maker_3);
+        assert_eq!(fill_maker(event_heap.view().at_slot(0).unwrap()), maker_1);
+        assert_eq!(fill_maker(event_heap.view().at_slot(1).unwrap()), maker_2);
+        assert_eq!(fill_maker(event_heap.view().at_slot(2).unwrap()), maker_3);
     }
 
     send_tx(
@@ -175,7 +175,7 @@ async fn test_skip_missing_accounts() -> Result<(), TransportError> {
     {
         let event_heap = solana.get_account_boxed::<EventHeap>(event_heap).await;
         assert_eq!(event_heap.header.count(), 1);
-        assert_eq!(fill_maker(event_heap.front().unwrap()), maker_1);
+        assert_eq!(fill_maker(event_heap.view().front().unwrap()), maker_1);
     }
 
     Ok(())
@@ -336,9 +336,9 @@ async fn test_crank_given_events() -> Result<(), TransportError> {
     {
         let event_heap = solana.get_account_boxed::<EventHeap>(event_heap).await;
         assert_eq!(event_heap.header.count(), 3);
-        assert_eq!(fill_maker(event_heap.at_slot(0).unwrap()), maker_1);
-        assert_eq!(fill_maker(event_heap.at_slot(1).unwrap()), maker_2);
-        assert_eq!(fill_maker(event_heap.at_slot(2).unwrap()), maker_3);
+        assert_eq!(fill_maker(event_heap.view().at_slot(0).unwrap()), maker_1);
+        assert_eq!(fill_maker(event_heap.view().at_slot(1).unwrap()), maker_2);
+        assert_eq!(fill_maker(event_heap.view().at_slot(2).unwrap()), maker_3);
     }
 
     send_tx(
@@ -356,7 +356,7 @@ async fn test_crank_given_events() -> Result<(), TransportError> {
     {
         let event_heap = solana.get_account_boxed::<EventHeap>(event_heap).await;
         assert_eq!(event_heap.header.count(), 1);
-        assert_eq!(fill_maker(event_heap.front().unwrap()), maker_2);
+        assert_eq!(fill_maker(event_heap.view().front().unwrap()), maker_2);
     }
 
     // is not possible to process slots > limit
@@ -397,3 +397,338 @@ fn fill_maker(anyevent: &AnyEvent) -> Pubkey {
     let event: &FillEvent = cast_ref(anyevent);
     event.maker
 }
+
+#[tokio::test]
+async fn test_crank_reward() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        crank_reward: 300,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+    let crank_reward_receiver = context.users[1].key.pubkey();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    let take_ix = || PlaceTakeOrderInstruction {
+        market,
+        signer: owner,
+        user_base_account: owner_token_0,
+        user_quote_account: owner_token_1,
+        market_base_vault,
+        market_quote_vault,
+        side: Side::Ask,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        open_orders_admin: None,
+    };
+
+    // every take order adding to the heap pays the penalty into the pool
+    let market_lamports = solana.get_account_lamports(market).await;
+    send_tx(solana, take_ix()).await.unwrap();
+    send_tx(solana, take_ix()).await.unwrap();
+    assert_eq!(
+        solana.get_account::<Market>(market).await.penalty_pool,
+        2 * PENALTY_EVENT_HEAP
+    );
+    assert_eq!(
+        solana.get_account_lamports(market).await,
+        market_lamports + 2 * PENALTY_EVENT_HEAP
+    );
+
+    // events skipped for missing accounts aren't rewarded
+    let receiver_lamports = solana.get_account_lamports(crank_reward_receiver).await;
+    send_tx(
+        solana,
+        ConsumeEventsWithRewardInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![],
+            crank_reward_receiver,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account_lamports(crank_reward_receiver).await,
+        receiver_lamports
+    );
+
+    send_tx(
+        solana,
+        ConsumeEventsWithRewardInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1],
+            crank_reward_receiver,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        solana.get_account_lamports(crank_reward_receiver).await,
+        receiver_lamports + 2 * 300
+    );
+    assert_eq!(
+        solana.get_account_lamports(market).await,
+        market_lamports + 2 * PENALTY_EVENT_HEAP - 2 * 300
+    );
+    assert_eq!(
+        solana.get_account::<Market>(market).await.penalty_pool,
+        2 * PENALTY_EVENT_HEAP - 2 * 300
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_crank_reward_per_event() -> Result<(), TransportError> {
+    // markets created before the penalty was configurable have it zeroed
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        penalty_event_heap: 0,
+        crank_reward: 300,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+    let crank_reward_receiver = context.users[1].key.pubkey();
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+    send_tx(solana, place_bid_ix).await.unwrap();
+
+    // a single take order filling both bids pays the penalty for each of its events
+    let market_lamports = solana.get_account_lamports(market).await;
+    send_tx(
+        solana,
+        PlaceTakeOrderInstruction {
+            market,
+            signer: owner,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            market_base_vault,
+            market_quote_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 10000,
+            open_orders_admin: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account::<Market>(market).await.penalty_pool,
+        2 * PENALTY_EVENT_HEAP
+    );
+    assert_eq!(
+        solana.get_account_lamports(market).await,
+        market_lamports + 2 * PENALTY_EVENT_HEAP
+    );
+
+    let receiver_lamports = solana.get_account_lamports(crank_reward_receiver).await;
+    send_tx(
+        solana,
+        ConsumeEventsWithRewardInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1],
+            crank_reward_receiver,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        solana.get_account_lamports(crank_reward_receiver).await,
+        receiver_lamports + 2 * 300
+    );
+    assert_eq!(
+        solana.get_account::<Market>(market).await.penalty_pool,
+        2 * PENALTY_EVENT_HEAP - 2 * 300
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_crank_reward() -> Result<(), TransportError> {
+    // markets created before crank rewards existed have them zeroed
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        crank_reward: 0,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    // only the collect fee admin can set the reward, up to the heap penalty
+    assert!(send_tx(
+        solana,
+        SetCrankRewardInstruction {
+            collect_fee_admin: owner,
+            market,
+            crank_reward: 300,
+        },
+    )
+    .await
+    .is_err());
+    assert_openbook_error(
+        &send_tx(
+            solana,
+            SetCrankRewardInstruction {
+                collect_fee_admin,
+                market,
+                crank_reward: PENALTY_EVENT_HEAP + 1,
+            },
+        )
+        .await,
+        OpenBookError::InvalidCrankReward.error_code(),
+        "crank reward above the penalty".to_string(),
+    );
+    send_tx(
+        solana,
+        SetCrankRewardInstruction {
+            collect_fee_admin,
+            market,
+            crank_reward: 300,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(solana.get_account::<Market>(market).await.crank_reward, 300);
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceTakeOrderInstruction {
+            market,
+            signer: owner,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            market_base_vault,
+            market_quote_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            open_orders_admin: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    // a reward that wouldn't make a new receiver rent exempt stays in the pool
+    let crank_reward_receiver = Pubkey::new_unique();
+    send_tx(
+        solana,
+        ConsumeEventsWithRewardInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1],
+            crank_reward_receiver,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(solana.get_account_lamports(crank_reward_receiver).await, 0);
+    assert_eq!(
+        solana.get_account::<Market>(market).await.penalty_pool,
+        PENALTY_EVENT_HEAP
+    );
+
+    Ok(())
+}
@@ -145,3 +145,94 @@ async fn test_with_wrong_oracle_types() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_with_wrong_pool_mints() -> Result<(), TransportError> {
+    let context = TestContextBuilder::new().start_default().await;
+    let solana = &context.solana.clone();
+
+    let payer = context.users[0].key;
+    let mints = &context.mints[0..=2];
+
+    // a mainnet raydium pool, its tokens aren't the ones of the market
+    let raydium_program: Pubkey = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK"
+        .parse()
+        .unwrap();
+    let pool_data = read_file(
+        find_file("resources/test/2QdhepnKRTLjjSqPL1PtKNwqrUkoLee5Gqs8bvZhRdMv.bin").unwrap(),
+    );
+    let pool = solana
+        .create_account_from_len(&raydium_program, pool_data.len())
+        .await;
+    solana.set_account_data(pool, &pool_data).await;
+
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateMarketInstruction {
+                payer,
+                market: TestKeypair::new(),
+                quote_lot_size: 100,
+                base_lot_size: 100,
+                base_mint: mints[0].pubkey,
+                quote_mint: mints[1].pubkey,
+                ..CreateMarketInstruction::with_new_book_and_heap(solana, Some(pool), None).await
+            },
+        )
+        .await,
+        Some(openbook_v2::error::OpenBookError::InvalidOraclePoolMints.into())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_crank_reward_above_penalty() -> Result<(), TransportError> {
+    let context = TestContextBuilder::new().start_default().await;
+    let solana = &context.solana.clone();
+
+    let payer = context.users[0].key;
+    let mints = &context.mints[0..=2];
+
+    let create_market_ix = |market, penalty_event_heap, crank_reward| async move {
+        CreateMarketInstruction {
+            payer,
+            market,
+            quote_lot_size: 100,
+            base_lot_size: 100,
+            base_mint: mints[0].pubkey,
+            quote_mint: mints[1].pubkey,
+            penalty_event_heap,
+            crank_reward,
+            ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+        }
+    };
+
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            create_market_ix(TestKeypair::new(), 100, 101).await
+        )
+        .await,
+        Some(openbook_v2::error::OpenBookError::InvalidCrankReward.into())
+    );
+
+    // a zero penalty stands for the default one
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            create_market_ix(TestKeypair::new(), 0, PENALTY_EVENT_HEAP + 1).await
+        )
+        .await,
+        Some(openbook_v2::error::OpenBookError::InvalidCrankReward.into())
+    );
+
+    send_tx(
+        solana,
+        create_market_ix(TestKeypair::new(), 0, PENALTY_EVENT_HEAP).await,
+    )
+    .await
+    .unwrap();
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use super::*;
+
+#[tokio::test]
+async fn test_heartbeat() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    let cancel_ix = || CancelOnMissedHeartbeatInstruction {
+        open_orders_account: account_1,
+        market,
+    };
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+
+    // without a timeout the orders stay on the book
+    solana.advance_clock(1000).await;
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, cancel_ix()).await,
+        Some(OpenBookError::HeartbeatNotMissed.into())
+    );
+
+    send_tx(
+        solana,
+        SetHeartbeatTimeoutInstruction {
+            owner,
+            open_orders_account: account_1,
+            timeout_seconds: 60,
+        },
+    )
+    .await
+    .unwrap();
+
+    solana.advance_clock(40).await;
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, cancel_ix()).await,
+        Some(OpenBookError::HeartbeatNotMissed.into())
+    );
+
+    // only the owner and delegates can keep the orders alive
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            HeartbeatInstruction {
+                signer: context.users[1].key,
+                open_orders_account: account_1,
+            },
+        )
+        .await,
+        Some(OpenBookError::NoOwnerOrDelegate.into())
+    );
+    send_tx(
+        solana,
+        HeartbeatInstruction {
+            signer: owner,
+            open_orders_account: account_1,
+        },
+    )
+    .await
+    .unwrap();
+
+    solana.advance_clock(40).await;
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, cancel_ix()).await,
+        Some(OpenBookError::HeartbeatNotMissed.into())
+    );
+
+    // anyone can cancel once the heartbeat is missed
+    solana.advance_clock(40).await;
+    send_tx(solana, cancel_ix()).await.unwrap();
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert!(!open_orders_account_1.position.has_open_orders());
+    }
+
+    // placing an order counts as a heartbeat
+    send_tx(solana, place_bid_ix).await.unwrap();
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, cancel_ix()).await,
+        Some(OpenBookError::HeartbeatNotMissed.into())
+    );
+    let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+    assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+
+    Ok(())
+}
@@ -44,6 +44,7 @@ async fn test_indexer() -> Result<(), TransportError> {
         let indexer = solana.get_account::<OpenOrdersIndexer>(indexer).await;
         assert_eq!(indexer.created_counter, 1);
         assert!(indexer.addresses.contains(&maker_1));
+        assert_eq!(*indexer.markets, vec![market]);
     }
 
     let (maker_2, maker_3) = {
@@ -94,6 +95,20 @@ async fn test_indexer() -> Result<(), TransportError> {
         assert!(indexer.addresses.contains(&maker_1));
         assert!(indexer.addresses.contains(&maker_3));
         assert!(indexer.addresses.contains(&maker_4));
+
+        assert_eq!(indexer.markets.len(), 3);
+        assert_eq!(
+            indexer
+                .open_orders_accounts_for_market(&market)
+                .collect::<Vec<_>>(),
+            vec![&maker_1, &maker_3, &maker_4]
+        );
+        assert_eq!(
+            indexer
+                .open_orders_accounts_for_market(&Pubkey::new_unique())
+                .count(),
+            0
+        );
     }
 
     Ok(())
@@ -150,6 +165,7 @@ async fn test_size_vector() -> Result<(), TransportError> {
 
         assert_eq!(indexer.created_counter, max);
         assert_eq!(indexer.addresses.len(), max as usize);
+        assert_eq!(indexer.markets.len(), max as usize);
         assert!(indexer.addresses.contains(&makers[(max - 1) as usize]));
         assert!(indexer.addresses.contains(&makers[(max / 2) as usize]));
         assert!(indexer.addresses.contains(&makers[1]));
@@ -164,6 +180,7 @@ async fn test_size_vector() -> Result<(), TransportError> {
             owner,
             payer: context.users[1].key,
             delegate: None,
+            capacity: MAX_OPEN_ORDERS as u16,
         },
     )
     .await
@@ -171,3 +188,110 @@ async fn test_size_vector() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_migrate_indexer() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        payer,
+        market,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let indexer = Pubkey::find_program_address(
+        &[b"OpenOrdersIndexer".as_ref(), owner.pubkey().as_ref()],
+        &openbook_v2::id(),
+    )
+    .0;
+
+    // the old layout is the current one without the trailing markets
+    let legacy_space = 8 + 1 + 4 + (4 + 2 * 32);
+    let data = solana.get_account_data(indexer).await.unwrap();
+    solana
+        .set_account_data(indexer, &data[..legacy_space])
+        .await;
+    {
+        let indexer = solana.get_account::<OpenOrdersIndexer>(indexer).await;
+        assert_eq!(indexer.addresses, vec![account_1, account_2]);
+        assert!(indexer.markets.is_empty());
+    }
+
+    // closing an account keeps the old layout
+    send_tx(
+        solana,
+        CloseOpenOrdersAccountInstruction {
+            account_num: 2,
+            market,
+            owner,
+            sol_destination: owner.pubkey(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account_data(indexer).await.unwrap().len(),
+        legacy_space - 32 + 4
+    );
+
+    // adding one starts recording markets, the older ones are unknown
+    let account_3 =
+        create_open_orders_account(solana, owner, market, 3, &context.users[1], None).await;
+    {
+        let indexer = solana.get_account::<OpenOrdersIndexer>(indexer).await;
+        assert_eq!(indexer.addresses, vec![account_1, account_3]);
+        assert_eq!(*indexer.markets, vec![Pubkey::default(), market]);
+        assert_eq!(
+            indexer
+                .open_orders_accounts_for_market(&market)
+                .collect::<Vec<_>>(),
+            vec![&account_3]
+        );
+    }
+    assert_eq!(
+        solana.get_account_data(indexer).await.unwrap().len(),
+        OpenOrdersIndexer::space(2)
+    );
+
+    // only indexed open orders accounts can be backfilled
+    create_open_orders_indexer(solana, &context.users[1], context.users[1].key, market).await;
+    let other_account = create_open_orders_account(
+        solana,
+        context.users[1].key,
+        market,
+        1,
+        &context.users[1],
+        None,
+    )
+    .await;
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            MigrateOpenOrdersIndexerInstruction {
+                owner: owner.pubkey(),
+                payer,
+                open_orders_accounts: vec![other_account],
+            }
+        )
+        .await,
+        Some(OpenBookError::InvalidOpenOrdersIndexerMigration.into())
+    );
+
+    send_tx(
+        solana,
+        MigrateOpenOrdersIndexerInstruction {
+            owner: owner.pubkey(),
+            payer,
+            open_orders_accounts: vec![account_1],
+        },
+    )
+    .await
+    .unwrap();
+    let indexer = solana.get_account::<OpenOrdersIndexer>(indexer).await;
+    assert_eq!(*indexer.markets, vec![market, market]);
+
+    Ok(())
+}
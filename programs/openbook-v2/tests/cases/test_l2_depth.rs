@@ -0,0 +1,142 @@
+use super::*;
+use openbook_v2::{L2Level, MAX_L2_DEPTH_LEVELS};
+
+#[tokio::test]
+async fn test_l2_depth() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        account_1,
+        account_2,
+        tokens,
+        collect_fee_admin,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // the oracle price is 10 lots
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots: 9,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+
+    assert_eq!(
+        get_l2_depth(solana, market, 10).await.unwrap(),
+        L2Depth::default()
+    );
+
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            max_base_lots: 2,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            price_lots: 8,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderPeggedInstruction {
+            open_orders_account: account_1,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_offset: -1,
+            peg_limit: 1000,
+            max_base_lots: 3,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+        },
+    )
+    .await
+    .unwrap();
+    let now_ts = solana.get_clock().await.unix_timestamp as u64;
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            price_lots: 7,
+            expiry_timestamp: now_ts + 10,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots: 12,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    let level = |price_lots, base_lots| L2Level {
+        price_lots,
+        base_lots,
+    };
+
+    // the pegged order is resolved to 9 and aggregated with the fixed orders
+    assert_eq!(
+        get_l2_depth(solana, market, 10).await.unwrap(),
+        L2Depth {
+            bids: vec![level(9, 6), level(8, 1), level(7, 1)],
+            asks: vec![level(12, 1)],
+        }
+    );
+    assert_eq!(
+        get_l2_depth(solana, market, 1).await.unwrap(),
+        L2Depth {
+            bids: vec![level(9, 6)],
+            asks: vec![level(12, 1)],
+        }
+    );
+
+    // expired orders are skipped
+    solana.advance_clock(20).await;
+    set_stub_oracle_price(solana, &tokens[0], collect_fee_admin, 1.0).await;
+    assert_eq!(
+        get_l2_depth(solana, market, 10).await.unwrap().bids,
+        vec![level(9, 6), level(8, 1)]
+    );
+
+    assert!(get_l2_depth(solana, market, MAX_L2_DEPTH_LEVELS + 1)
+        .await
+        .is_err());
+
+    Ok(())
+}
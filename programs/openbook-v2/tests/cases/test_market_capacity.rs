@@ -0,0 +1,328 @@
+use super::*;
+use openbook_v2::accounts_zerocopy::{split_dynamic_data, DynamicAccount};
+
+#[tokio::test]
+async fn test_create_market_capacity() -> Result<(), TransportError> {
+    let context = TestContextBuilder::new().start_default().await;
+    let solana = &context.solana.clone();
+
+    let payer = context.users[0].key;
+    let mints = &context.mints[0..=2];
+
+    let create_market_ix = |book_side_capacity: u32, event_heap_capacity: u16| async move {
+        CreateMarketInstruction {
+            payer,
+            market: TestKeypair::new(),
+            quote_lot_size: 100,
+            base_lot_size: 100,
+            base_mint: mints[0].pubkey,
+            quote_mint: mints[1].pubkey,
+            ..CreateMarketInstruction::with_new_book_and_heap_capacity(
+                solana,
+                None,
+                None,
+                book_side_capacity,
+                event_heap_capacity,
+            )
+            .await
+        }
+    };
+
+    // capacities need to be within limits
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            create_market_ix(MIN_ORDERTREE_NODES as u32 - 1, 8).await
+        )
+        .await,
+        Some(OpenBookError::InvalidBookSideCapacity.into())
+    );
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, create_market_ix(9, 0).await).await,
+        Some(OpenBookError::InvalidEventHeapCapacity.into())
+    );
+
+    // capacities need to match the size of the accounts
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateMarketInstruction {
+                book_side_capacity: 10,
+                ..create_market_ix(9, 8).await
+            }
+        )
+        .await,
+        Some(OpenBookError::InvalidBookSideCapacity.into())
+    );
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateMarketInstruction {
+                event_heap_capacity: 7,
+                ..create_market_ix(9, 8).await
+            }
+        )
+        .await,
+        Some(OpenBookError::InvalidEventHeapCapacity.into())
+    );
+
+    let market_ix = create_market_ix(9, 8).await;
+    let (bids, event_heap) = (market_ix.bids, market_ix.event_heap);
+    send_tx(solana, market_ix).await.unwrap();
+
+    let bids_data = solana.get_account_data(bids).await.unwrap();
+    assert_eq!(bids_data.len(), BookSide::space(9));
+    let event_heap_data = solana.get_account_data(event_heap).await.unwrap();
+    assert_eq!(event_heap_data.len(), EventHeap::space(8));
+    let (event_heap_header, _) = split_dynamic_data::<EventHeap>(&event_heap_data).unwrap();
+    assert_eq!(event_heap_header.count(), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_small_capacity_market() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        book_side_capacity: 9,
+        event_heap_capacity: 8,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+    let event_heap = solana.get_account::<Market>(market).await.event_heap;
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+
+    // 9 nodes hold 5 leaves
+    for i in 0..5 {
+        send_tx(
+            solana,
+            PlaceOrderInstruction {
+                price_lots: price_lots - 5 + i,
+                client_order_id: i as u64,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let leaf_count = |data: Vec<u8>| {
+        let (header, _) = split_dynamic_data::<BookSide>(&data).unwrap();
+        header.roots[0].leaf_count
+    };
+    assert_eq!(leaf_count(solana.get_account_data(bids).await.unwrap()), 5);
+
+    // a worse bid does not fit
+    assert!(send_tx(
+        solana,
+        PlaceOrderInstruction {
+            price_lots: price_lots - 6,
+            client_order_id: 5,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .is_err());
+
+    // a better bid replaces the worst one
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            client_order_id: 5,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(leaf_count(solana.get_account_data(bids).await.unwrap()), 5);
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 5);
+    }
+
+    // fills are pushed to the small event heap
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots: price_lots - 1,
+            max_base_lots: 2,
+            client_order_id: 6,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    let event_heap_data = solana.get_account_data(event_heap).await.unwrap();
+    let (event_heap_header, _) = split_dynamic_data::<EventHeap>(&event_heap_data).unwrap();
+    assert_eq!(event_heap_header.count(), 2);
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1],
+        },
+    )
+    .await
+    .unwrap();
+
+    let event_heap_data = solana.get_account_data(event_heap).await.unwrap();
+    let (event_heap_header, _) = split_dynamic_data::<EventHeap>(&event_heap_data).unwrap();
+    assert_eq!(event_heap_header.count(), 0);
+    assert_eq!(leaf_count(solana.get_account_data(bids).await.unwrap()), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_event_heap() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        event_heap_capacity: 4,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+    let event_heap = solana.get_account::<Market>(market).await.event_heap;
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    for i in 0..6 {
+        send_tx(
+            solana,
+            PlaceOrderInstruction {
+                client_order_id: i,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let place_ask_ix = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        ..place_bid_ix.clone()
+    };
+    let heap_count = || async {
+        let event_heap_data = solana.get_account_data(event_heap).await.unwrap();
+        let (event_heap_header, _) = split_dynamic_data::<EventHeap>(&event_heap_data).unwrap();
+        event_heap_header.count()
+    };
+
+    // fill the event heap
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            max_base_lots: 4,
+            ..place_ask_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(heap_count().await, 4);
+
+    // fills of makers that aren't passed can't be recorded anymore
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, place_ask_ix.clone()).await,
+        Some(OpenBookError::EventHeapFull.into())
+    );
+
+    // makers that are passed are filled right away
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            remainings: vec![account_1],
+            ..place_ask_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(heap_count().await, 4);
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 5);
+    }
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1],
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(heap_count().await, 0);
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use super::*;
+
+#[tokio::test]
+async fn test_open_orders_capacity() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+    let owner_quote_ata = context.users[0].token_accounts[1];
+    let owner_base_ata = context.users[0].token_accounts[0];
+
+    // capacity below MAX_OPEN_ORDERS is rejected
+    assert!(send_tx(
+        solana,
+        CreateOpenOrdersAccountInstruction {
+            account_num: 3,
+            market,
+            owner,
+            payer: context.users[1].key,
+            delegate: None,
+            capacity: MAX_OPEN_ORDERS as u16 - 1,
+        },
+    )
+    .await
+    .is_err());
+
+    // and so is one above what fits into a newly created account
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CreateOpenOrdersAccountInstruction {
+                account_num: 3,
+                market,
+                owner,
+                payer: context.users[1].key,
+                delegate: None,
+                capacity: MAX_OPEN_ORDERS_CREATION_CAPACITY as u16 + 1,
+            },
+        )
+        .await,
+        Some(OpenBookError::InvalidOpenOrdersCapacity.into())
+    );
+
+    let account_3 = send_tx(
+        solana,
+        CreateOpenOrdersAccountInstruction {
+            account_num: 3,
+            market,
+            owner,
+            payer: context.users[1].key,
+            delegate: None,
+            capacity: 30,
+        },
+    )
+    .await
+    .unwrap()
+    .open_orders_account;
+    assert_eq!(
+        solana.get_account_data(account_3).await.unwrap().len(),
+        OpenOrdersAccount::space_with_capacity(30)
+    );
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_3,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_quote_ata,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 20000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+
+    for client_order_id in 0..30 {
+        send_tx(
+            solana,
+            PlaceOrderInstruction {
+                client_order_id,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await
+        .unwrap();
+    }
+    assert!(send_tx(solana, place_bid_ix.clone()).await.is_err());
+
+    // growing is limited by the realloc limit of the runtime
+    let grow_ix = GrowOpenOrdersAccountInstruction {
+        open_orders_account: account_3,
+        owner,
+        payer: context.users[1].key,
+        capacity: 30 + MAX_OPEN_ORDERS_GROWTH as u16 + 1,
+    };
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, grow_ix.clone()).await,
+        Some(OpenBookError::InvalidOpenOrdersCapacity.into())
+    );
+    send_tx(
+        solana,
+        GrowOpenOrdersAccountInstruction {
+            capacity: 280,
+            ..grow_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account_data(account_3).await.unwrap().len(),
+        OpenOrdersAccount::space_with_capacity(280)
+    );
+
+    // shrinking is not possible
+    assert!(send_tx(
+        solana,
+        GrowOpenOrdersAccountInstruction {
+            capacity: 100,
+            ..grow_ix
+        },
+    )
+    .await
+    .is_err());
+
+    // the new slots are usable; place the best bid in slot 30
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            price_lots: price_lots + 1,
+            client_order_id: 30,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            user_token_account: owner_base_ata,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            order_type: PlaceOrderType::ImmediateOrCancel,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_3],
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_3 = solana.get_account::<OpenOrdersAccount>(account_3).await;
+        assert_eq!(open_orders_account_3.position.bids_base_lots, 30);
+        assert_eq!(open_orders_account_3.position.base_free_native, 100);
+    }
+
+    Ok(())
+}
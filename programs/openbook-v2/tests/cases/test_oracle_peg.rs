@@ -76,6 +76,63 @@ async fn test_oracle_peg_invalid_oracle() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_oracle_peg_fallback() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        account_1,
+        tokens,
+        collect_fee_admin,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize {
+        with_fallback_oracle: true,
+        ..TestNewMarketInitialize::default()
+    })
+    .await?;
+    let solana = &context.solana.clone();
+
+    // pegs above the limit are skipped, which shows which oracle was used
+    let place_pegged_ix = PlaceOrderPeggedInstruction {
+        open_orders_account: account_1,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_offset: -1,
+        peg_limit: 100,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 100_000,
+        client_order_id: 0,
+    };
+
+    // oracle_a is used while it's valid
+    set_stub_oracle_price(solana, &tokens[2], collect_fee_admin, 1000.0).await;
+    send_tx(solana, place_pegged_ix.clone()).await.unwrap();
+    assert_eq!(pegged_bids_count(solana, bids).await, 1);
+
+    // and oracle_b once it's stale
+    solana.advance_clock(200).await;
+    set_stub_oracle_price(solana, &tokens[2], collect_fee_admin, 1000.0).await;
+    send_tx(solana, place_pegged_ix.clone()).await.unwrap();
+    assert_eq!(pegged_bids_count(solana, bids).await, 1);
+
+    set_stub_oracle_price(solana, &tokens[2], collect_fee_admin, 1.0).await;
+    send_tx(solana, place_pegged_ix.clone()).await.unwrap();
+    assert_eq!(pegged_bids_count(solana, bids).await, 2);
+
+    // pegging fails if neither oracle is valid
+    solana.advance_clock(200).await;
+    assert!(send_tx(solana, place_pegged_ix).await.is_err());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_oracle_peg() -> Result<(), TransportError> {
     let market_base_lot_size = 10000;
@@ -516,6 +573,333 @@ async fn test_take_peg_invalid_oracle() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_oracle_peg_stale_and_low_confidence_oracle() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        account_1,
+        tokens,
+        collect_fee_admin,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let place_pegged_ix = PlaceOrderPeggedInstruction {
+        open_orders_account: account_1,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_offset: -1,
+        peg_limit: 100,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 100_000,
+        client_order_id: 0,
+    };
+    let set_oracle_ix = StubOracleSetTestInstruction {
+        mint: tokens[0].mint.pubkey,
+        owner: collect_fee_admin,
+        price: 1.0,
+        last_update_slot: solana.get_clock().await.slot,
+        last_update_ts: 0,
+        deviation: 0.0,
+    };
+
+    // the market's conf_filter is 0.1
+    send_tx(
+        solana,
+        StubOracleSetTestInstruction {
+            deviation: 0.2,
+            ..set_oracle_ix
+        },
+    )
+    .await
+    .unwrap();
+    assert!(send_tx(solana, place_pegged_ix.clone()).await.is_err());
+
+    send_tx(
+        solana,
+        StubOracleSetTestInstruction {
+            deviation: 0.05,
+            ..set_oracle_ix
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(solana, place_pegged_ix.clone()).await.unwrap();
+    assert_eq!(pegged_bids_count(solana, bids).await, 1);
+
+    // the market's max_staleness_slots is 100
+    send_tx(solana, set_oracle_ix).await.unwrap();
+    solana.advance_by_slots(100).await;
+    assert!(send_tx(solana, place_pegged_ix).await.is_err());
+    assert_eq!(pegged_bids_count(solana, bids).await, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_peg_legacy_oracle_config() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        account_1,
+        tokens,
+        collect_fee_admin,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // markets created before the newer oracle settings have them zeroed
+    let mut market_data = solana.get_account_data(market).await.unwrap();
+    let mut market_state = solana.get_account::<Market>(market).await;
+    market_state.oracle_config = OracleConfig {
+        conf_filter: market_state.oracle_config.conf_filter,
+        max_staleness_slots: market_state.oracle_config.max_staleness_slots,
+        ..bytemuck::Zeroable::zeroed()
+    };
+    market_data[8..].copy_from_slice(bytemuck::bytes_of(&market_state));
+    solana.set_account_data(market, &market_data).await;
+
+    // and don't check the publish time of the price
+    let last_update_ts = solana.get_clock().await.unix_timestamp;
+    solana.advance_clock(10).await;
+    send_tx(
+        solana,
+        StubOracleSetTestInstruction {
+            mint: tokens[0].mint.pubkey,
+            owner: collect_fee_admin,
+            price: 1.0,
+            last_update_slot: solana.get_clock().await.slot,
+            last_update_ts,
+            deviation: 0.0,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderPeggedInstruction {
+            open_orders_account: account_1,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_offset: -1,
+            peg_limit: 100,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 100_000,
+            client_order_id: 0,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(pegged_bids_count(solana, bids).await, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_take_peg_low_confidence_oracle() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        account_1,
+        account_2,
+        price_lots,
+        tokens,
+        collect_fee_admin,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    send_tx(
+        solana,
+        PlaceOrderPeggedInstruction {
+            open_orders_account: account_1,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_offset: -1,
+            peg_limit: 100,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 100_000,
+            client_order_id: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    let take_order_ix = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 100_000,
+        client_order_id: 6,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![account_1],
+    };
+    let set_oracle_ix = StubOracleSetTestInstruction {
+        mint: tokens[0].mint.pubkey,
+        owner: collect_fee_admin,
+        price: 1000.0,
+        last_update_slot: solana.get_clock().await.slot,
+        last_update_ts: 0,
+        deviation: 200.0,
+    };
+
+    // low confidence oracle, the order is posted without matching the pegged bid
+    send_tx(solana, set_oracle_ix.clone()).await.unwrap();
+    send_tx(solana, take_order_ix.clone()).await.unwrap();
+    {
+        let oo_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let oo_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(oo_1.position.bids_base_lots, 1);
+        assert_eq!(oo_2.position.asks_base_lots, 1);
+    }
+
+    // but it matches once the confidence is good enough
+    send_tx(
+        solana,
+        StubOracleSetTestInstruction {
+            deviation: 50.0,
+            ..set_oracle_ix
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(solana, take_order_ix).await.unwrap();
+    {
+        let oo = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(oo.position.bids_base_lots, 0);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_take_peg_stale_oracle() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        account_1,
+        account_2,
+        price_lots,
+        tokens,
+        collect_fee_admin,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    send_tx(
+        solana,
+        PlaceOrderPeggedInstruction {
+            open_orders_account: account_1,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_offset: -1,
+            peg_limit: 100,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 100_000,
+            client_order_id: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    let take_order_ix = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 100_000,
+        client_order_id: 6,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![account_1],
+    };
+
+    // the market's max_staleness_slots is 100, the price was last updated 101 slots ago
+    solana.advance_by_slots(101).await;
+    let now_slot = solana.get_clock().await.slot;
+    let set_oracle_ix = StubOracleSetTestInstruction {
+        mint: tokens[0].mint.pubkey,
+        owner: collect_fee_admin,
+        price: 1000.0,
+        last_update_slot: now_slot - 101,
+        last_update_ts: 0,
+        deviation: 0.0,
+    };
+
+    // stale oracle, the order is posted without matching the pegged bid
+    send_tx(solana, set_oracle_ix.clone()).await.unwrap();
+    send_tx(solana, take_order_ix.clone()).await.unwrap();
+    {
+        let oo_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let oo_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+        assert_eq!(oo_1.position.bids_base_lots, 1);
+        assert_eq!(oo_2.position.asks_base_lots, 1);
+    }
+
+    // but it matches once the price is at most 100 slots old
+    send_tx(
+        solana,
+        StubOracleSetTestInstruction {
+            last_update_slot: now_slot - 100,
+            ..set_oracle_ix
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(solana, take_order_ix).await.unwrap();
+    {
+        let oo = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(oo.position.bids_base_lots, 0);
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_oracle_peg_limit() -> Result<(), TransportError> {
     let market_base_lot_size = 10000;
@@ -907,3 +1291,8 @@ async fn assert_no_orders(solana: &SolanaCookie, account_1: Pubkey) {
         assert!(oo.client_id == 0);
     }
 }
+
+async fn pegged_bids_count(solana: &SolanaCookie, bids: Pubkey) -> u32 {
+    let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+    bids_data.roots[1].leaf_count
+}
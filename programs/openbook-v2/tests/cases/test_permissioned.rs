@@ -607,3 +607,229 @@ async fn test_delegate() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_delegate_permissions() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let delegate = context.users[2].key;
+    let account_3 = create_open_orders_account(
+        solana,
+        owner,
+        market,
+        3,
+        &context.users[0],
+        Some(delegate.pubkey()),
+    )
+    .await;
+    let delegate_token_1 = context.users[2].token_accounts[1];
+
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    // the delegate may only place orders worth up to one lot
+    let quote_lot_size = solana.get_account::<Market>(market).await.quote_lot_size;
+    let max_order_quote_native = (price_lots * quote_lot_size) as u64;
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            SetDelegatePermissionsInstruction {
+                owner,
+                open_orders_account: account_3,
+                permissions: DELEGATE_ALL_PERMISSIONS + 1,
+                max_order_quote_native,
+            },
+        )
+        .await,
+        Some(OpenBookError::InvalidDelegatePermissions.into())
+    );
+    send_tx(
+        solana,
+        SetDelegatePermissionsInstruction {
+            owner,
+            open_orders_account: account_3,
+            permissions: DELEGATE_PLACE_ORDERS,
+            max_order_quote_native,
+        },
+    )
+    .await
+    .unwrap();
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_3,
+        open_orders_admin: None,
+        market,
+        signer: delegate,
+        user_token_account: delegate_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 23,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            PlaceOrderInstruction {
+                max_base_lots: 2,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await,
+        Some(OpenBookError::DelegateOrderNotionalExceeded.into())
+    );
+
+    // cancelling and settling aren't granted
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CancelOrderByClientOrderIdInstruction {
+                signer: delegate,
+                market,
+                open_orders_account: account_3,
+                client_order_id: 23,
+            },
+        )
+        .await,
+        Some(OpenBookError::NoOwnerOrDelegate.into())
+    );
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            SettleFundsInstruction {
+                owner: delegate,
+                open_orders_account: account_3,
+                market,
+                market_base_vault,
+                market_quote_vault,
+                user_base_account: owner_token_0,
+                user_quote_account: owner_token_1,
+                referrer_account: None,
+            },
+        )
+        .await,
+        Some(OpenBookError::NoOwnerOrDelegate.into())
+    );
+
+    // the owner isn't restricted
+    send_tx(
+        solana,
+        CancelOrderByClientOrderIdInstruction {
+            signer: owner,
+            market,
+            open_orders_account: account_3,
+            client_order_id: 23,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            signer: owner,
+            user_token_account: owner_token_1,
+            max_base_lots: 2,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_3 = solana.get_account::<OpenOrdersAccount>(account_3).await;
+        assert_eq!(open_orders_account_3.position.bids_base_lots, 2);
+        assert_eq!(
+            open_orders_account_3.position.delegate_permissions(),
+            DELEGATE_PLACE_ORDERS
+        );
+    }
+
+    // 0 grants no permissions at all
+    send_tx(
+        solana,
+        SetDelegatePermissionsInstruction {
+            owner,
+            open_orders_account: account_3,
+            permissions: 0,
+            max_order_quote_native: 0,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, place_bid_ix.clone()).await,
+        Some(OpenBookError::NoOwnerOrDelegate.into())
+    );
+
+    // a new delegate starts with all permissions and no limit
+    let new_delegate = context.users[1].key;
+    send_tx(
+        solana,
+        SetDelegateInstruction {
+            owner,
+            open_orders_account: account_3,
+            delegate_account: Some(new_delegate.pubkey()),
+        },
+    )
+    .await
+    .unwrap();
+    {
+        let open_orders_account_3 = solana.get_account::<OpenOrdersAccount>(account_3).await;
+        assert_eq!(
+            open_orders_account_3.position.delegate_permissions(),
+            DELEGATE_ALL_PERMISSIONS
+        );
+        assert_eq!(
+            open_orders_account_3
+                .position
+                .delegate_max_order_quote_native,
+            0
+        );
+    }
+
+    // permissions need a delegate to apply to
+    send_tx(
+        solana,
+        SetDelegateInstruction {
+            owner,
+            open_orders_account: account_3,
+            delegate_account: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            SetDelegatePermissionsInstruction {
+                owner,
+                open_orders_account: account_3,
+                permissions: DELEGATE_ALL_PERMISSIONS,
+                max_order_quote_native: 0,
+            },
+        )
+        .await,
+        Some(OpenBookError::NoDelegate.into())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use super::*;
+use openbook_v2::accounts_zerocopy::split_dynamic_data;
+
+#[tokio::test]
+async fn test_prune_expired_orders() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        bids,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+    let event_heap = solana.get_account::<Market>(market).await.event_heap;
+    let crank_reward_receiver = context.users[1].key.pubkey();
+
+    let now_ts = solana.get_clock().await.unix_timestamp as u64;
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: now_ts + 10,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+
+    for i in 1..=3 {
+        send_tx(
+            solana,
+            PlaceOrderInstruction {
+                price_lots: price_lots - i,
+                client_order_id: i as u64,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await
+        .unwrap();
+    }
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            price_lots: price_lots - 4,
+            client_order_id: 4,
+            expiry_timestamp: 0,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    // fund the penalty pool with a take order
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots: price_lots + 1,
+            expiry_timestamp: 0,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceTakeOrderInstruction {
+            market,
+            signer: owner,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            market_base_vault,
+            market_quote_vault,
+            side: Side::Bid,
+            price_lots: price_lots + 1,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            open_orders_admin: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana.get_account::<Market>(market).await.penalty_pool,
+        PENALTY_EVENT_HEAP
+    );
+
+    let heap_count = || async {
+        let event_heap_data = solana.get_account_data(event_heap).await.unwrap();
+        let (event_heap_header, _) = split_dynamic_data::<EventHeap>(&event_heap_data).unwrap();
+        event_heap_header.count()
+    };
+    let leaf_count = || async {
+        let bids_data = solana.get_account_data(bids).await.unwrap();
+        let (header, _) = split_dynamic_data::<BookSide>(&bids_data).unwrap();
+        header.roots[0].leaf_count
+    };
+    assert_eq!(heap_count().await, 1);
+    assert_eq!(leaf_count().await, 4);
+
+    // nothing has expired yet
+    send_tx(
+        solana,
+        PruneExpiredOrdersInstruction {
+            penalty_payer: owner,
+            market,
+            limit: 255,
+            open_orders_accounts: vec![account_1],
+            crank_reward_receiver: Some(crank_reward_receiver),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(leaf_count().await, 4);
+
+    solana.advance_clock(20).await;
+
+    // makers passed as remaining accounts are refunded right away
+    let receiver_lamports = solana.get_account_lamports(crank_reward_receiver).await;
+    send_tx(
+        solana,
+        PruneExpiredOrdersInstruction {
+            penalty_payer: owner,
+            market,
+            limit: 2,
+            open_orders_accounts: vec![account_1],
+            crank_reward_receiver: Some(crank_reward_receiver),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(leaf_count().await, 2);
+    assert_eq!(heap_count().await, 1);
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 2);
+        assert_eq!(open_orders_account_1.position.penalty_heap_count, 0);
+    }
+    assert_eq!(
+        solana.get_account_lamports(crank_reward_receiver).await,
+        receiver_lamports + 2 * CRANK_REWARD
+    );
+    assert_eq!(
+        solana.get_account::<Market>(market).await.penalty_pool,
+        PENALTY_EVENT_HEAP - 2 * CRANK_REWARD
+    );
+
+    // the others are refunded through the event heap, paid for by the penalty payer
+    let market_lamports = solana.get_account_lamports(market).await;
+    let receiver_lamports = solana.get_account_lamports(crank_reward_receiver).await;
+    send_tx(
+        solana,
+        PruneExpiredOrdersInstruction {
+            penalty_payer: owner,
+            market,
+            limit: 255,
+            open_orders_accounts: vec![],
+            crank_reward_receiver: Some(crank_reward_receiver),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(leaf_count().await, 1);
+    assert_eq!(heap_count().await, 2);
+    assert_eq!(
+        solana.get_account::<Market>(market).await.penalty_pool,
+        2 * PENALTY_EVENT_HEAP - 3 * CRANK_REWARD
+    );
+    assert_eq!(
+        solana.get_account_lamports(market).await,
+        market_lamports + PENALTY_EVENT_HEAP - CRANK_REWARD
+    );
+    assert_eq!(
+        solana.get_account_lamports(crank_reward_receiver).await,
+        receiver_lamports + CRANK_REWARD
+    );
+
+    send_tx(
+        solana,
+        ConsumeEventsInstruction {
+            consume_events_admin: None,
+            market,
+            open_orders_accounts: vec![account_1, account_2],
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(heap_count().await, 0);
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+    }
+
+    Ok(())
+}
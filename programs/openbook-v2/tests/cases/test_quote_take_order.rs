@@ -0,0 +1,148 @@
+use super::*;
+
+#[tokio::test]
+async fn test_quote_take_order() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let place_ask_ix = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_0,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, place_ask_ix.clone()).await.unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            price_lots: price_lots + 1,
+            max_base_lots: 2,
+            ..place_ask_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    let quote_bid_ix = || QuoteTakeOrderInstruction {
+        market,
+        side: Side::Bid,
+        price_lots: price_lots + 1,
+        max_base_lots: 5,
+        max_quote_lots_including_fees: 100000,
+        order_type: PlaceOrderType::ImmediateOrCancel,
+    };
+
+    // three lots are available within the price limit
+    let quote = quote_take_order(solana, quote_bid_ix()).await.unwrap();
+    assert_eq!(quote.total_base_taken_native, 300);
+    assert!(quote.not_enough_liquidity);
+
+    // the book is left untouched by the quote
+    assert_eq!(
+        quote_take_order(solana, quote_bid_ix()).await.unwrap(),
+        quote
+    );
+
+    // the quote fills completely within the order limits
+    let partial_quote = quote_take_order(
+        solana,
+        QuoteTakeOrderInstruction {
+            max_base_lots: 2,
+            ..quote_bid_ix()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(partial_quote.total_base_taken_native, 200);
+    assert!(!partial_quote.not_enough_liquidity);
+
+    // fill or kill orders that can't be filled completely fail
+    assert!(quote_take_order(
+        solana,
+        QuoteTakeOrderInstruction {
+            order_type: PlaceOrderType::FillOrKill,
+            ..quote_bid_ix()
+        },
+    )
+    .await
+    .is_err());
+
+    // resting orders can't be quoted
+    assert!(quote_take_order(
+        solana,
+        QuoteTakeOrderInstruction {
+            order_type: PlaceOrderType::Limit,
+            ..quote_bid_ix()
+        },
+    )
+    .await
+    .is_err());
+
+    // the quote matches the outcome of the take order
+    let balance_base = solana.token_account_balance(owner_token_0).await;
+    let balance_quote = solana.token_account_balance(owner_token_1).await;
+
+    send_tx(
+        solana,
+        PlaceTakeOrderInstruction {
+            market,
+            signer: owner,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            market_base_vault,
+            market_quote_vault,
+            side: Side::Bid,
+            price_lots: price_lots + 1,
+            max_base_lots: 5,
+            max_quote_lots_including_fees: 100000,
+            open_orders_admin: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        balance_base + quote.total_base_taken_native,
+        solana.token_account_balance(owner_token_0).await
+    );
+    assert_eq!(
+        balance_quote - quote.total_quote_taken_native - quote.taker_fees_native,
+        solana.token_account_balance(owner_token_1).await
+    );
+
+    assert_eq!(
+        quote_take_order(solana, quote_bid_ix()).await.unwrap(),
+        TakeQuote {
+            not_enough_liquidity: true,
+            ..TakeQuote::default()
+        }
+    );
+
+    Ok(())
+}
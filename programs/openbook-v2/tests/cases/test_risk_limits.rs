@@ -0,0 +1,299 @@
+use super::*;
+
+#[tokio::test]
+async fn test_risk_limits() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let quote_lot_size = solana.get_account::<Market>(market).await.quote_lot_size;
+    let lot_quote_native = (price_lots * quote_lot_size) as u64;
+    let set_limits_ix = SetRiskLimitsInstruction {
+        owner,
+        open_orders_account: account_1,
+        max_base_lots_per_side: 3,
+        max_order_quote_native: 2 * lot_quote_native,
+        max_taker_quote_native_per_window: lot_quote_native + quote_lot_size as u64,
+        window_seconds: 100,
+    };
+
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            SetRiskLimitsInstruction {
+                max_base_lots_per_side: -1,
+                ..set_limits_ix.clone()
+            },
+        )
+        .await,
+        Some(OpenBookError::InvalidInputRiskLimits.into())
+    );
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            SetRiskLimitsInstruction {
+                window_seconds: 0,
+                ..set_limits_ix.clone()
+            },
+        )
+        .await,
+        Some(OpenBookError::InvalidInputRiskLimits.into())
+    );
+    send_tx(solana, set_limits_ix.clone()).await.unwrap();
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 2,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+
+    // single orders are capped by their value
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            PlaceOrderInstruction {
+                max_base_lots: 3,
+                ..place_bid_ix.clone()
+            },
+        )
+        .await,
+        Some(OpenBookError::RiskLimitOrderNotionalExceeded.into())
+    );
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            CancelAllAndPlaceOrdersInstruction {
+                open_orders_account: account_1,
+                open_orders_admin: None,
+                market,
+                signer: owner,
+                user_base_account: owner_token_0,
+                user_quote_account: owner_token_1,
+                orders_type: PlaceOrderType::Limit,
+                bids: vec![],
+                asks: vec![openbook_v2::PlaceMultipleOrdersArgs {
+                    price_lots: price_lots + 10,
+                    max_quote_lots_including_fees: 10000,
+                    expiry_timestamp: 0,
+                }],
+            },
+        )
+        .await,
+        Some(OpenBookError::RiskLimitOrderNotionalExceeded.into())
+    );
+
+    // and the open orders of each side by their size
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, place_bid_ix.clone()).await,
+        Some(OpenBookError::RiskLimitOpenBaseLotsExceeded.into())
+    );
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            max_base_lots: 1,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    // taker volume is capped per window
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_2,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots: price_lots + 1,
+            max_base_lots: 3,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    let take_ask_ix = PlaceOrderInstruction {
+        price_lots: price_lots + 1,
+        max_base_lots: 1,
+        order_type: PlaceOrderType::ImmediateOrCancel,
+        ..place_bid_ix.clone()
+    };
+    send_tx(solana, take_ask_ix.clone()).await.unwrap();
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, take_ask_ix.clone()).await,
+        Some(OpenBookError::RiskLimitTakerVolumeExceeded.into())
+    );
+
+    solana.advance_clock(100).await;
+    send_tx(solana, take_ask_ix.clone()).await.unwrap();
+
+    // limits can be lifted by the owner
+    send_tx(
+        solana,
+        SetRiskLimitsInstruction {
+            max_base_lots_per_side: 0,
+            max_order_quote_native: 0,
+            max_taker_quote_native_per_window: 0,
+            ..set_limits_ix
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(solana, take_ask_ix).await.unwrap();
+    send_tx(solana, place_bid_ix).await.unwrap();
+
+    let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+    assert_eq!(open_orders_account_1.position.bids_base_lots, 5);
+    assert_eq!(
+        open_orders_account_1.position.taker_volume,
+        3 * (lot_quote_native + quote_lot_size as u64) as u128
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_risk_limits_section() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let session_key = context.users[3].key;
+    let now_ts = solana.get_clock().await.unix_timestamp as u64;
+    send_tx(
+        solana,
+        SetDelegateInstruction {
+            delegate_account: Some(context.users[2].key.pubkey()),
+            owner,
+            open_orders_account: account_1,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        SetSessionDelegateInstruction {
+            authority: owner,
+            open_orders_account: account_1,
+            session_key: session_key.pubkey(),
+            expiry_timestamp: now_ts + 1000,
+        },
+    )
+    .await
+    .unwrap();
+
+    // the limits are stored in their own section, added when they're first set
+    let quote_lot_size = solana.get_account::<Market>(market).await.quote_lot_size;
+    let lot_quote_native = (price_lots * quote_lot_size) as u64;
+    let set_limits_ix = SetRiskLimitsInstruction {
+        owner,
+        open_orders_account: account_1,
+        max_base_lots_per_side: 0,
+        max_order_quote_native: lot_quote_native,
+        max_taker_quote_native_per_window: lot_quote_native,
+        window_seconds: 7 * 24 * 60 * 60,
+    };
+    let space = solana.get_account_data(account_1).await.unwrap().len();
+    send_tx(solana, set_limits_ix.clone()).await.unwrap();
+    send_tx(solana, set_limits_ix.clone()).await.unwrap();
+    assert_eq!(
+        solana.get_account_data(account_1).await.unwrap().len(),
+        space + std::mem::size_of::<RiskLimits>()
+    );
+
+    // and kept when growing the account
+    send_tx(
+        solana,
+        GrowOpenOrdersAccountInstruction {
+            open_orders_account: account_1,
+            owner,
+            payer: owner,
+            capacity: MAX_OPEN_ORDERS as u16 + 2,
+        },
+    )
+    .await
+    .unwrap();
+
+    // the session delegate moved past the limits
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        open_orders_admin: None,
+        market,
+        signer: session_key,
+        user_token_account: context.users[3].token_accounts[1],
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 2,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, place_bid_ix.clone()).await,
+        Some(OpenBookError::RiskLimitOrderNotionalExceeded.into())
+    );
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            max_base_lots: 1,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    // the owner can place orders as well
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            signer: owner,
+            user_token_account: owner_token_1,
+            max_base_lots: 1,
+            ..place_bid_ix
+        },
+    )
+    .await
+    .unwrap();
+
+    let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+    assert_eq!(open_orders_account_1.position.bids_base_lots, 2);
+    assert_eq!(open_orders_account_1.position.risk_limits_slots, 1);
+
+    Ok(())
+}
@@ -385,6 +385,163 @@ async fn test_self_trade_cancel_provide() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_self_trade_stp_group() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+    let owner_quote_ata = context.users[0].token_accounts[1];
+    let owner_base_ata = context.users[0].token_accounts[0];
+
+    for open_orders_account in [account_1, account_2] {
+        send_tx(
+            solana,
+            SetStpGroupInstruction {
+                stp_group: 7,
+                owner,
+                open_orders_account,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let place_ask_ix = PlaceOrderInstruction {
+        open_orders_account: account_2,
+        open_orders_admin: None,
+        market,
+        signer: owner,
+        user_token_account: owner_base_ata,
+        market_vault: market_base_vault,
+        side: Side::Ask,
+        price_lots: 1000,
+        max_base_lots: 2,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 1,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_1,
+        user_token_account: owner_quote_ata,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10004,
+        client_order_id: 2,
+        order_type: PlaceOrderType::Limit,
+        remainings: vec![account_2],
+        ..place_ask_ix.clone()
+    };
+
+    send_tx(solana, place_ask_ix.clone()).await.unwrap();
+
+    // the resting order of the other account in the group is cancelled
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            order_type: PlaceOrderType::ImmediateOrCancel,
+            self_trade_behavior: SelfTradeBehavior::CancelProvide,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 0);
+
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 0);
+        assert_eq!(open_orders_account_2.position.base_free_native, 200);
+        assert_eq!(open_orders_account_2.position.quote_free_native, 0);
+    }
+
+    send_tx(solana, place_ask_ix.clone()).await.unwrap();
+
+    // the taker order is cancelled, nothing is matched nor posted
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            self_trade_behavior: SelfTradeBehavior::CancelTake,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 0);
+
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 2);
+    }
+
+    // the maker account is needed to verify the group, otherwise orders match normally
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            self_trade_behavior: SelfTradeBehavior::CancelTake,
+            remainings: vec![],
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 100);
+
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 2);
+    }
+
+    // both orders are cancelled
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            self_trade_behavior: SelfTradeBehavior::CancelBoth,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        let open_orders_account_2 = solana.get_account::<OpenOrdersAccount>(account_2).await;
+
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+        assert_eq!(open_orders_account_1.position.base_free_native, 100);
+
+        // the previous fill is still waiting on the event heap
+        assert_eq!(open_orders_account_2.position.asks_base_lots, 1);
+        assert_eq!(open_orders_account_2.position.base_free_native, 300);
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_self_abort_transaction() -> Result<(), TransportError> {
     let TestInitialize {
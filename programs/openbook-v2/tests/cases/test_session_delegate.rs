@@ -0,0 +1,285 @@
+use super::*;
+
+#[tokio::test]
+async fn test_session_delegate() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        market,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let delegate = context.users[2].key;
+    let session_key = context.users[3].key;
+    let session_token_1 = context.users[3].token_accounts[1];
+    let account_3 = create_open_orders_account(
+        solana,
+        owner,
+        market,
+        3,
+        &context.users[0],
+        Some(delegate.pubkey()),
+    )
+    .await;
+
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    let place_bid_ix = PlaceOrderInstruction {
+        open_orders_account: account_3,
+        open_orders_admin: None,
+        market,
+        signer: session_key,
+        user_token_account: session_token_1,
+        market_vault: market_quote_vault,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots: 1,
+        max_quote_lots_including_fees: 10000,
+        client_order_id: 0,
+        expiry_timestamp: 0,
+        order_type: PlaceOrderType::Limit,
+        self_trade_behavior: SelfTradeBehavior::default(),
+        remainings: vec![],
+    };
+    let session_ix = |session_key: Pubkey, expiry_timestamp: u64| SetSessionDelegateInstruction {
+        authority: delegate,
+        open_orders_account: account_3,
+        session_key,
+        expiry_timestamp,
+    };
+
+    // the session key has no authority yet
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, place_bid_ix.clone()).await,
+        Some(OpenBookError::NoOwnerOrDelegate.into())
+    );
+
+    // the delegate adds it without the owner, growing the account by a slot
+    let space = solana.get_account_data(account_3).await.unwrap().len();
+    let now_ts = solana.get_clock().await.unix_timestamp as u64;
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, session_ix(session_key.pubkey(), now_ts)).await,
+        Some(OpenBookError::InvalidInputSessionExpiry.into())
+    );
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            session_ix(
+                session_key.pubkey(),
+                now_ts + MAX_SESSION_LIFETIME_SECONDS + 1
+            )
+        )
+        .await,
+        Some(OpenBookError::InvalidInputSessionExpiry.into())
+    );
+    send_tx(solana, session_ix(session_key.pubkey(), now_ts + 100))
+        .await
+        .unwrap();
+    assert_eq!(
+        solana.get_account_data(account_3).await.unwrap().len(),
+        space + std::mem::size_of::<SessionDelegate>()
+    );
+    send_tx(solana, place_bid_ix.clone()).await.unwrap();
+
+    // session delegates can't add other ones
+    assert!(send_tx(
+        solana,
+        SetSessionDelegateInstruction {
+            authority: session_key,
+            ..session_ix(Pubkey::new_unique(), now_ts + 100)
+        },
+    )
+    .await
+    .is_err());
+
+    // the slots are limited
+    for _ in 1..MAX_SESSION_DELEGATES {
+        send_tx(solana, session_ix(Pubkey::new_unique(), now_ts + 100))
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, session_ix(Pubkey::new_unique(), now_ts + 100))
+            .await,
+        Some(OpenBookError::SessionDelegatesFull.into())
+    );
+
+    // session delegates are kept when growing the account
+    send_tx(
+        solana,
+        GrowOpenOrdersAccountInstruction {
+            open_orders_account: account_3,
+            owner,
+            payer: owner,
+            capacity: MAX_OPEN_ORDERS as u16 + 2,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            client_order_id: 1,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    // the key loses its authority once expired
+    solana.advance_clock(200).await;
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, place_bid_ix.clone()).await,
+        Some(OpenBookError::NoOwnerOrDelegate.into())
+    );
+
+    // stale slots are reused
+    let now_ts = solana.get_clock().await.unix_timestamp as u64;
+    let space = solana.get_account_data(account_3).await.unwrap().len();
+    send_tx(solana, session_ix(session_key.pubkey(), now_ts + 100))
+        .await
+        .unwrap();
+    assert_eq!(
+        solana.get_account_data(account_3).await.unwrap().len(),
+        space
+    );
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            client_order_id: 2,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    // and keys can be revoked right away
+    send_tx(solana, session_ix(session_key.pubkey(), 0))
+        .await
+        .unwrap();
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, place_bid_ix.clone()).await,
+        Some(OpenBookError::NoOwnerOrDelegate.into())
+    );
+
+    // revoking the delegate revokes its session delegates too
+    send_tx(solana, session_ix(session_key.pubkey(), now_ts + 100))
+        .await
+        .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            client_order_id: 3,
+            ..place_bid_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        SetDelegateInstruction {
+            delegate_account: None,
+            owner,
+            open_orders_account: account_3,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            PlaceOrderInstruction {
+                client_order_id: 4,
+                ..place_bid_ix.clone()
+            }
+        )
+        .await,
+        Some(OpenBookError::NoOwnerOrDelegate.into())
+    );
+
+    // and a new delegate doesn't inherit them
+    send_tx(
+        solana,
+        SetDelegateInstruction {
+            delegate_account: Some(delegate.pubkey()),
+            owner,
+            open_orders_account: account_3,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(
+            solana,
+            PlaceOrderInstruction {
+                client_order_id: 4,
+                ..place_bid_ix.clone()
+            }
+        )
+        .await,
+        Some(OpenBookError::NoOwnerOrDelegate.into())
+    );
+
+    let open_orders_account_3 = solana.get_account::<OpenOrdersAccount>(account_3).await;
+    assert_eq!(open_orders_account_3.position.bids_base_lots, 4);
+    assert_eq!(
+        open_orders_account_3.position.session_delegate_slots as usize,
+        MAX_SESSION_DELEGATES
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_delegate_needs_all_permissions() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        market,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let delegate = context.users[2].key;
+    let account_3 = create_open_orders_account(
+        solana,
+        owner,
+        market,
+        3,
+        &context.users[0],
+        Some(delegate.pubkey()),
+    )
+    .await;
+    send_tx(
+        solana,
+        SetDelegatePermissionsInstruction {
+            owner,
+            open_orders_account: account_3,
+            permissions: DELEGATE_SETTLE_FUNDS,
+            max_order_quote_native: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    let now_ts = solana.get_clock().await.unix_timestamp as u64;
+    assert!(send_tx(
+        solana,
+        SetSessionDelegateInstruction {
+            authority: delegate,
+            open_orders_account: account_3,
+            session_key: Pubkey::new_unique(),
+            expiry_timestamp: now_ts + 100,
+        },
+    )
+    .await
+    .is_err());
+
+    Ok(())
+}
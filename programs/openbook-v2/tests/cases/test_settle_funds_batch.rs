@@ -0,0 +1,141 @@
+use super::*;
+
+#[tokio::test]
+async fn test_settle_funds_batch() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        payer,
+        mints,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+    let owner_token_2 = context.users[0].token_accounts[2];
+
+    // Second market sharing the quote token with the first one
+    let openbook_v2::accounts::CreateMarket {
+        market: market_2,
+        market_base_vault: market_2_base_vault,
+        market_quote_vault: market_2_quote_vault,
+        ..
+    } = send_tx(
+        solana,
+        CreateMarketInstruction {
+            collect_fee_admin: collect_fee_admin.pubkey(),
+            open_orders_admin: None,
+            close_market_admin: None,
+            payer,
+            market: TestKeypair::new(),
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maker_fee: -200,
+            taker_fee: 400,
+            base_mint: mints[2].pubkey,
+            quote_mint: mints[1].pubkey,
+            ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let account_3 =
+        create_open_orders_account(solana, owner, market_2, 3, &context.users[1], None).await;
+
+    let balances_before = (
+        solana.token_account_balance(owner_token_0).await,
+        solana.token_account_balance(owner_token_1).await,
+        solana.token_account_balance(owner_token_2).await,
+    );
+
+    send_tx(
+        solana,
+        DepositInstruction {
+            owner,
+            market,
+            open_orders_account: account_1,
+            market_base_vault,
+            market_quote_vault,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            base_amount: 1000,
+            quote_amount: 2000,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        DepositInstruction {
+            owner,
+            market: market_2,
+            open_orders_account: account_3,
+            market_base_vault: market_2_base_vault,
+            market_quote_vault: market_2_quote_vault,
+            user_base_account: owner_token_2,
+            user_quote_account: owner_token_1,
+            base_amount: 3000,
+            quote_amount: 4000,
+        },
+    )
+    .await
+    .unwrap();
+
+    // token accounts need to match the mints of the market
+    assert!(send_tx(
+        solana,
+        SettleFundsBatchInstruction {
+            owner,
+            settlements: vec![(account_1, owner_token_2, owner_token_1)],
+        },
+    )
+    .await
+    .is_err());
+
+    // only accounts of the owner's indexer can be settled
+    let other_owner = context.users[1].key;
+    assert!(send_tx(
+        solana,
+        SettleFundsBatchInstruction {
+            owner: other_owner,
+            settlements: vec![(account_1, owner_token_0, owner_token_1)],
+        },
+    )
+    .await
+    .is_err());
+
+    send_tx(
+        solana,
+        SettleFundsBatchInstruction {
+            owner,
+            settlements: vec![
+                (account_1, owner_token_0, owner_token_1),
+                (account_3, owner_token_2, owner_token_1),
+            ],
+        },
+    )
+    .await
+    .unwrap();
+
+    for account in [account_1, account_3] {
+        let open_orders_account = solana.get_account::<OpenOrdersAccount>(account).await;
+        assert_eq!(open_orders_account.position.base_free_native, 0);
+        assert_eq!(open_orders_account.position.quote_free_native, 0);
+    }
+    assert_eq!(
+        balances_before,
+        (
+            solana.token_account_balance(owner_token_0).await,
+            solana.token_account_balance(owner_token_1).await,
+            solana.token_account_balance(owner_token_2).await,
+        )
+    );
+
+    Ok(())
+}
@@ -289,6 +289,256 @@ async fn test_take_bid_order() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_take_exact_quote_in() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        price_lots,
+        tokens,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // Set the initial oracle price
+    set_stub_oracle_price(solana, &tokens[1], collect_fee_admin, 1000.0).await;
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 20000,
+
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    let balance_base = solana.token_account_balance(owner_token_0).await;
+    let balance_quote = solana.token_account_balance(owner_token_1).await;
+
+    // enough for one and a half lots, only one lot can be bought
+    send_tx(
+        solana,
+        PlaceTakeOrderExactQuoteInInstruction {
+            market,
+            signer: owner,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            market_base_vault,
+            market_quote_vault,
+            price_lots,
+            quote_amount_native: 150_005,
+            open_orders_admin: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+
+        assert_eq!(open_orders_account_1.position.asks_base_lots, 2);
+        assert_eq!(
+            balance_base + 100,
+            solana.token_account_balance(owner_token_0).await
+        );
+        assert_eq!(
+            balance_quote - 100020,
+            solana.token_account_balance(owner_token_1).await
+        );
+    }
+
+    // not enough to pay the taker fees of the remaining lot
+    send_tx(
+        solana,
+        PlaceTakeOrderExactQuoteInInstruction {
+            market,
+            signer: owner,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            market_base_vault,
+            market_quote_vault,
+            price_lots,
+            quote_amount_native: 100_030,
+            open_orders_admin: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        balance_quote - 100020,
+        solana.token_account_balance(owner_token_1).await
+    );
+
+    send_tx(
+        solana,
+        PlaceTakeOrderExactQuoteInInstruction {
+            market,
+            signer: owner,
+            user_base_account: owner_token_0,
+            user_quote_account: owner_token_1,
+            market_base_vault,
+            market_quote_vault,
+            price_lots,
+            quote_amount_native: 100_040,
+            open_orders_admin: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        balance_base + 200,
+        solana.token_account_balance(owner_token_0).await
+    );
+    assert_eq!(
+        balance_quote - 200040,
+        solana.token_account_balance(owner_token_1).await
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_take_exact_quote_in_boundary() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_base_vault,
+        market_quote_vault,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    // a base lot costs a single quote lot of 10 native, plus 1 native of taker fees
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_0,
+            market_vault: market_base_vault,
+            side: Side::Ask,
+            price_lots: 1,
+            max_base_lots: 10,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    let take_ix = PlaceTakeOrderExactQuoteInInstruction {
+        market,
+        signer: owner,
+        user_base_account: owner_token_0,
+        user_quote_account: owner_token_1,
+        market_base_vault,
+        market_quote_vault,
+        price_lots: 1,
+        quote_amount_native: 10,
+        open_orders_admin: None,
+    };
+
+    assert_eq!(
+        send_tx_and_get_ix_custom_error(solana, take_ix.clone()).await,
+        Some(OpenBookError::InvalidInputLots.into())
+    );
+
+    let balance_base = solana.token_account_balance(owner_token_0).await;
+    let balance_quote = solana.token_account_balance(owner_token_1).await;
+
+    // 21 native pay for 2 quote lots and their fees of 1 native
+    send_tx(
+        solana,
+        PlaceTakeOrderExactQuoteInInstruction {
+            quote_amount_native: 21,
+            ..take_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        balance_base + 200,
+        solana.token_account_balance(owner_token_0).await
+    );
+    assert_eq!(
+        balance_quote - 20,
+        solana.token_account_balance(owner_token_1).await
+    );
+
+    // 30 native aren't enough for 3 quote lots, their fees round up to 1 native
+    send_tx(
+        solana,
+        PlaceTakeOrderExactQuoteInInstruction {
+            quote_amount_native: 30,
+            ..take_ix.clone()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        balance_base + 400,
+        solana.token_account_balance(owner_token_0).await
+    );
+    assert_eq!(
+        balance_quote - 40,
+        solana.token_account_balance(owner_token_1).await
+    );
+
+    send_tx(
+        solana,
+        PlaceTakeOrderExactQuoteInInstruction {
+            quote_amount_native: 31,
+            ..take_ix
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        balance_base + 700,
+        solana.token_account_balance(owner_token_0).await
+    );
+    assert_eq!(
+        balance_quote - 70,
+        solana.token_account_balance(owner_token_1).await
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_negative_spread_ask() -> Result<(), TransportError> {
     let TestInitialize {
@@ -367,3 +617,154 @@ async fn test_negative_spread_ask() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_take_two_hop() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        collect_fee_admin,
+        owner,
+        payer,
+        mints,
+        owner_token_0,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+    let owner_token_2 = context.users[0].token_accounts[2];
+
+    // Second market sharing the quote token with the first one
+    let openbook_v2::accounts::CreateMarket {
+        market: market_2,
+        market_base_vault: market_2_base_vault,
+        ..
+    } = send_tx(
+        solana,
+        CreateMarketInstruction {
+            collect_fee_admin: collect_fee_admin.pubkey(),
+            open_orders_admin: None,
+            close_market_admin: None,
+            payer,
+            market: TestKeypair::new(),
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maker_fee: -200,
+            taker_fee: 400,
+            base_mint: mints[2].pubkey,
+            quote_mint: mints[1].pubkey,
+            ..CreateMarketInstruction::with_new_book_and_heap(solana, None, None).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let account_3 =
+        create_open_orders_account(solana, owner, market_2, 3, &context.users[1], None).await;
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_3,
+            open_orders_admin: None,
+            market: market_2,
+            signer: owner,
+            user_token_account: owner_token_2,
+            market_vault: market_2_base_vault,
+            side: Side::Ask,
+            price_lots: price_lots / 2,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 0,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    let balances_before = (
+        solana.token_account_balance(owner_token_0).await,
+        solana.token_account_balance(owner_token_1).await,
+        solana.token_account_balance(owner_token_2).await,
+    );
+
+    let swap_ix = PlaceTakeOrderTwoHopInstruction {
+        signer: owner,
+        market_1: market,
+        market_2,
+        user_input_account: owner_token_0,
+        user_intermediate_account: Some(owner_token_1),
+        user_output_account: owner_token_2,
+        amount_in_native: 100,
+        min_amount_out_native: 100,
+    };
+
+    // Only one base lot is for sale on the second market
+    assert!(send_tx(
+        solana,
+        PlaceTakeOrderTwoHopInstruction {
+            min_amount_out_native: 101,
+            ..swap_ix
+        },
+    )
+    .await
+    .is_err());
+
+    // Half of the intermediate quote isn't spent
+    assert!(send_tx(
+        solana,
+        PlaceTakeOrderTwoHopInstruction {
+            user_intermediate_account: None,
+            ..swap_ix
+        },
+    )
+    .await
+    .is_err());
+
+    send_tx(solana, swap_ix).await.unwrap();
+
+    // Sold 100 base for 100000 - 20 quote, bought 100 of the second base for 50000 + 10 quote
+    assert_eq!(
+        balances_before.0 - 100,
+        solana.token_account_balance(owner_token_0).await
+    );
+    assert_eq!(
+        balances_before.1 + 49970,
+        solana.token_account_balance(owner_token_1).await
+    );
+    assert_eq!(
+        balances_before.2 + 100,
+        solana.token_account_balance(owner_token_2).await
+    );
+
+    Ok(())
+}
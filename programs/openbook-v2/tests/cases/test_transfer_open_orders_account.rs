@@ -0,0 +1,195 @@
+use super::*;
+
+#[tokio::test]
+async fn test_transfer_open_orders_account() -> Result<(), TransportError> {
+    let TestInitialize {
+        context,
+        owner,
+        owner_token_1,
+        market,
+        market_quote_vault,
+        price_lots,
+        account_1,
+        account_2,
+        ..
+    } = TestContext::new_with_market(TestNewMarketInitialize::default()).await?;
+    let solana = &context.solana.clone();
+
+    let new_owner = context.users[1].key;
+    let delegate = context.users[2].key;
+    let new_owner_indexer =
+        create_open_orders_indexer(solana, &context.users[1], new_owner, market).await;
+    let owner_indexer = Pubkey::find_program_address(
+        &[b"OpenOrdersIndexer".as_ref(), owner.pubkey().as_ref()],
+        &openbook_v2::id(),
+    )
+    .0;
+
+    send_tx(
+        solana,
+        SetStpGroupInstruction {
+            stp_group: 1,
+            owner,
+            open_orders_account: account_1,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        SetDelegateInstruction {
+            owner,
+            open_orders_account: account_1,
+            delegate_account: Some(delegate.pubkey()),
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PlaceOrderInstruction {
+            open_orders_account: account_1,
+            open_orders_admin: None,
+            market,
+            signer: owner,
+            user_token_account: owner_token_1,
+            market_vault: market_quote_vault,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            max_quote_lots_including_fees: 10000,
+            client_order_id: 7,
+            expiry_timestamp: 0,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            remainings: vec![],
+        },
+    )
+    .await
+    .unwrap();
+
+    // only the owner can hand the account over
+    assert!(send_tx(
+        solana,
+        TransferOpenOrdersAccountInstruction {
+            owner: new_owner,
+            new_owner: owner,
+            open_orders_account: account_1,
+        },
+    )
+    .await
+    .is_err());
+
+    send_tx(
+        solana,
+        TransferOpenOrdersAccountInstruction {
+            owner,
+            new_owner,
+            open_orders_account: account_1,
+        },
+    )
+    .await
+    .unwrap();
+
+    {
+        let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+        assert_eq!(open_orders_account_1.owner, new_owner.pubkey());
+        assert!(open_orders_account_1.delegate.is_none());
+        assert_eq!(open_orders_account_1.stp_group, 0);
+        assert_eq!(open_orders_account_1.position.bids_base_lots, 1);
+
+        let owner_indexer = solana.get_account::<OpenOrdersIndexer>(owner_indexer).await;
+        assert!(!owner_indexer.addresses.contains(&account_1));
+        assert_eq!(owner_indexer.addresses.len(), owner_indexer.markets.len());
+
+        let new_owner_indexer = solana
+            .get_account::<OpenOrdersIndexer>(new_owner_indexer)
+            .await;
+        assert_eq!(
+            new_owner_indexer
+                .open_orders_accounts_for_market(&market)
+                .collect::<Vec<_>>(),
+            vec![&account_1]
+        );
+    }
+
+    // the previous owner and the delegate lost their authority
+    for signer in [owner, delegate] {
+        assert_eq!(
+            send_tx_and_get_ix_custom_error(
+                solana,
+                CancelOrderByClientOrderIdInstruction {
+                    signer,
+                    market,
+                    open_orders_account: account_1,
+                    client_order_id: 7,
+                },
+            )
+            .await,
+            Some(OpenBookError::NoOwnerOrDelegate.into())
+        );
+    }
+
+    // the resting order moved along
+    send_tx(
+        solana,
+        CancelOrderByClientOrderIdInstruction {
+            signer: new_owner,
+            market,
+            open_orders_account: account_1,
+            client_order_id: 7,
+        },
+    )
+    .await
+    .unwrap();
+    let open_orders_account_1 = solana.get_account::<OpenOrdersAccount>(account_1).await;
+    assert_eq!(open_orders_account_1.position.bids_base_lots, 0);
+
+    // a new owner without an indexer gets one
+    let other_owner = context.users[3].key;
+    let other_owner_indexer = Pubkey::find_program_address(
+        &[b"OpenOrdersIndexer".as_ref(), other_owner.pubkey().as_ref()],
+        &openbook_v2::id(),
+    );
+    assert!(solana
+        .get_account_data(other_owner_indexer.0)
+        .await
+        .is_none());
+    send_tx(
+        solana,
+        TransferOpenOrdersAccountInstruction {
+            owner,
+            new_owner: other_owner,
+            open_orders_account: account_2,
+        },
+    )
+    .await
+    .unwrap();
+    {
+        let indexer = solana
+            .get_account::<OpenOrdersIndexer>(other_owner_indexer.0)
+            .await;
+        assert_eq!(indexer.bump, other_owner_indexer.1);
+        assert_eq!(indexer.created_counter, 0);
+        assert_eq!(indexer.addresses, vec![account_2]);
+        assert_eq!(*indexer.markets, vec![market]);
+    }
+    assert_eq!(
+        solana
+            .get_account_data(other_owner_indexer.0)
+            .await
+            .unwrap()
+            .len(),
+        OpenOrdersIndexer::space(1)
+    );
+
+    // and can keep using it
+    let other_account =
+        create_open_orders_account(solana, other_owner, market, 1, &context.users[3], None).await;
+    let indexer = solana
+        .get_account::<OpenOrdersIndexer>(other_owner_indexer.0)
+        .await;
+    assert_eq!(indexer.addresses, vec![account_2, other_account]);
+
+    Ok(())
+}
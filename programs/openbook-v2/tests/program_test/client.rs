@@ -10,8 +10,10 @@ use std::sync::Arc;
 
 use super::solana::SolanaCookie;
 use super::utils::TestKeypair;
+use openbook_v2::accounts_zerocopy::DynamicAccount;
 use openbook_v2::{
-    state::*, PlaceMultipleOrdersArgs, PlaceOrderArgs, PlaceOrderPeggedArgs, PlaceTakeOrderArgs,
+    state::*, L2Depth, PlaceMultipleOrdersArgs, PlaceOrderArgs, PlaceOrderPeggedArgs,
+    PlaceTakeOrderArgs, PlaceTakeOrderExactQuoteInArgs, PlaceTakeOrderTwoHopArgs,
 };
 
 #[async_trait::async_trait(?Send)]
@@ -189,12 +191,57 @@ impl ClientInstruction for CreateOpenOrdersIndexerInstruction {
     }
 }
 
+#[derive(Clone)]
+pub struct MigrateOpenOrdersIndexerInstruction {
+    pub owner: Pubkey,
+    pub payer: TestKeypair,
+    pub open_orders_accounts: Vec<Pubkey>,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for MigrateOpenOrdersIndexerInstruction {
+    type Accounts = openbook_v2::accounts::MigrateOpenOrdersIndexer;
+    type Instruction = openbook_v2::instruction::MigrateOpenOrdersIndexer;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = openbook_v2::instruction::MigrateOpenOrdersIndexer {};
+
+        let open_orders_indexer = Pubkey::find_program_address(
+            &[b"OpenOrdersIndexer".as_ref(), self.owner.as_ref()],
+            &program_id,
+        )
+        .0;
+
+        let accounts = openbook_v2::accounts::MigrateOpenOrdersIndexer {
+            payer: self.payer.pubkey(),
+            owner: self.owner,
+            open_orders_indexer,
+            system_program: System::id(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        instruction.accounts.extend(
+            self.open_orders_accounts
+                .iter()
+                .map(|pubkey| AccountMeta::new_readonly(*pubkey, false)),
+        );
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.payer]
+    }
+}
+
 pub struct CreateOpenOrdersAccountInstruction {
     pub account_num: u32,
     pub market: Pubkey,
     pub owner: TestKeypair,
     pub payer: TestKeypair,
     pub delegate: Option<Pubkey>,
+    pub capacity: u16,
 }
 #[async_trait::async_trait(?Send)]
 impl ClientInstruction for CreateOpenOrdersAccountInstruction {
@@ -207,6 +254,7 @@ impl ClientInstruction for CreateOpenOrdersAccountInstruction {
         let program_id = openbook_v2::id();
         let instruction = openbook_v2::instruction::CreateOpenOrdersAccount {
             name: "test".to_string(),
+            capacity: self.capacity,
         };
 
         let open_orders_indexer = Pubkey::find_program_address(
@@ -244,6 +292,42 @@ impl ClientInstruction for CreateOpenOrdersAccountInstruction {
     }
 }
 
+#[derive(Clone)]
+pub struct GrowOpenOrdersAccountInstruction {
+    pub open_orders_account: Pubkey,
+    pub owner: TestKeypair,
+    pub payer: TestKeypair,
+    pub capacity: u16,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for GrowOpenOrdersAccountInstruction {
+    type Accounts = openbook_v2::accounts::GrowOpenOrdersAccount;
+    type Instruction = openbook_v2::instruction::GrowOpenOrdersAccount;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            capacity: self.capacity,
+        };
+
+        let accounts = Self::Accounts {
+            payer: self.payer.pubkey(),
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner, self.payer]
+    }
+}
+
 pub struct CloseOpenOrdersAccountInstruction {
     pub account_num: u32,
     pub market: Pubkey,
@@ -314,10 +398,14 @@ pub struct CreateMarketInstruction {
     pub base_lot_size: i64,
     pub maker_fee: i64,
     pub taker_fee: i64,
-    pub fee_penalty: u64,
+    pub penalty_event_heap: u64,
+    pub crank_reward: u64,
     pub settle_fee_flat: f32,
     pub settle_fee_amount_threshold: f32,
     pub time_expiry: i64,
+    pub book_side_capacity: u32,
+    pub event_heap_capacity: u16,
+    pub fallback_to_oracle_b: bool,
 }
 impl CreateMarketInstruction {
     pub async fn with_new_book_and_heap(
@@ -325,18 +413,41 @@ impl CreateMarketInstruction {
         oracle_a: Option<Pubkey>,
         oracle_b: Option<Pubkey>,
     ) -> Self {
+        Self::with_new_book_and_heap_capacity(
+            solana,
+            oracle_a,
+            oracle_b,
+            MAX_ORDERTREE_NODES as u32,
+            MAX_NUM_EVENTS,
+        )
+        .await
+    }
+
+    pub async fn with_new_book_and_heap_capacity(
+        solana: &SolanaCookie,
+        oracle_a: Option<Pubkey>,
+        oracle_b: Option<Pubkey>,
+        book_side_capacity: u32,
+        event_heap_capacity: u16,
+    ) -> Self {
+        let book_side_space = BookSide::space(book_side_capacity as usize);
+        let event_heap_space = EventHeap::space(event_heap_capacity as usize);
         CreateMarketInstruction {
             bids: solana
-                .create_account_for_type::<BookSide>(&openbook_v2::id())
+                .create_account_with_len(&openbook_v2::id(), book_side_space)
                 .await,
             asks: solana
-                .create_account_for_type::<BookSide>(&openbook_v2::id())
+                .create_account_with_len(&openbook_v2::id(), book_side_space)
                 .await,
             event_heap: solana
-                .create_account_for_type::<EventHeap>(&openbook_v2::id())
+                .create_account_with_len(&openbook_v2::id(), event_heap_space)
                 .await,
             oracle_a,
             oracle_b,
+            book_side_capacity,
+            event_heap_capacity,
+            penalty_event_heap: PENALTY_EVENT_HEAP,
+            crank_reward: CRANK_REWARD,
             ..CreateMarketInstruction::default()
         }
     }
@@ -356,12 +467,20 @@ impl ClientInstruction for CreateMarketInstruction {
             oracle_config: OracleConfigParams {
                 conf_filter: 0.1,
                 max_staleness_slots: Some(100),
+                max_staleness_seconds: None,
+                min_liquidity: None,
+                use_ema_price: false,
+                fallback_to_oracle_b: self.fallback_to_oracle_b,
             },
             quote_lot_size: self.quote_lot_size,
             base_lot_size: self.base_lot_size,
             maker_fee: self.maker_fee,
             taker_fee: self.taker_fee,
             time_expiry: self.time_expiry,
+            book_side_capacity: self.book_side_capacity,
+            event_heap_capacity: self.event_heap_capacity,
+            penalty_event_heap: self.penalty_event_heap,
+            crank_reward: self.crank_reward,
         };
 
         let event_authority =
@@ -628,6 +747,138 @@ impl ClientInstruction for PlaceTakeOrderInstruction {
     }
 }
 
+#[derive(Clone)]
+pub struct PlaceTakeOrderExactQuoteInInstruction {
+    pub open_orders_admin: Option<TestKeypair>,
+    pub market: Pubkey,
+    pub signer: TestKeypair,
+    pub market_base_vault: Pubkey,
+    pub market_quote_vault: Pubkey,
+    pub user_base_account: Pubkey,
+    pub user_quote_account: Pubkey,
+    pub price_lots: i64,
+    pub quote_amount_native: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PlaceTakeOrderExactQuoteInInstruction {
+    type Accounts = openbook_v2::accounts::PlaceTakeOrder;
+    type Instruction = openbook_v2::instruction::PlaceTakeOrderExactQuoteIn;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            args: PlaceTakeOrderExactQuoteInArgs {
+                price_lots: self.price_lots,
+                quote_amount_native: self.quote_amount_native,
+                order_type: PlaceOrderType::ImmediateOrCancel,
+                limit: 10,
+            },
+        };
+
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            open_orders_admin: self.open_orders_admin.map(|kp| kp.pubkey()),
+            market: self.market,
+            market_authority: market.market_authority,
+            bids: market.bids,
+            asks: market.asks,
+            event_heap: market.event_heap,
+            oracle_a: market.oracle_a.into(),
+            oracle_b: market.oracle_b.into(),
+            signer: self.signer.pubkey(),
+            penalty_payer: self.signer.pubkey(),
+            user_base_account: self.user_base_account,
+            user_quote_account: self.user_quote_account,
+            market_base_vault: self.market_base_vault,
+            market_quote_vault: self.market_quote_vault,
+            token_program: Token::id(),
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        let mut signers = vec![self.signer];
+        if let Some(open_orders_admin) = self.open_orders_admin {
+            signers.push(open_orders_admin);
+        }
+
+        signers
+    }
+}
+
+pub struct PlaceTakeOrderTwoHopInstruction {
+    pub signer: TestKeypair,
+    pub market_1: Pubkey,
+    pub market_2: Pubkey,
+    pub user_input_account: Pubkey,
+    pub user_intermediate_account: Option<Pubkey>,
+    pub user_output_account: Pubkey,
+    pub amount_in_native: u64,
+    pub min_amount_out_native: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PlaceTakeOrderTwoHopInstruction {
+    type Accounts = openbook_v2::accounts::PlaceTakeOrderTwoHop;
+    type Instruction = openbook_v2::instruction::PlaceTakeOrderTwoHop;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            args: PlaceTakeOrderTwoHopArgs {
+                amount_in_native: self.amount_in_native,
+                min_amount_out_native: self.min_amount_out_native,
+                limit: 10,
+            },
+        };
+
+        let mut hops = vec![];
+        for market_pk in [self.market_1, self.market_2] {
+            let market: Market = account_loader.load(&market_pk).await.unwrap();
+            hops.push(openbook_v2::accounts::TakeOrderHop {
+                market: market_pk,
+                market_authority: market.market_authority,
+                bids: market.bids,
+                asks: market.asks,
+                market_base_vault: market.market_base_vault,
+                market_quote_vault: market.market_quote_vault,
+                event_heap: market.event_heap,
+                oracle_a: market.oracle_a.into(),
+                oracle_b: market.oracle_b.into(),
+                open_orders_admin: None,
+            });
+        }
+        let hop_2 = hops.pop().unwrap();
+        let hop_1 = hops.pop().unwrap();
+
+        let accounts = Self::Accounts {
+            signer: self.signer.pubkey(),
+            penalty_payer: self.signer.pubkey(),
+            hop_1,
+            hop_2,
+            user_input_account: self.user_input_account,
+            user_intermediate_account: self.user_intermediate_account,
+            user_output_account: self.user_output_account,
+            token_program: Token::id(),
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.signer]
+    }
+}
+
 pub struct CancelOrderInstruction {
     pub open_orders_account: Pubkey,
     pub market: Pubkey,
@@ -737,6 +988,37 @@ impl ClientInstruction for CancelAllOrdersInstruction {
     }
 }
 
+pub struct CancelOnMissedHeartbeatInstruction {
+    pub open_orders_account: Pubkey,
+    pub market: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for CancelOnMissedHeartbeatInstruction {
+    type Accounts = openbook_v2::accounts::CancelOnMissedHeartbeat;
+    type Instruction = openbook_v2::instruction::CancelOnMissedHeartbeat;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { limit: 5 };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+        let accounts = Self::Accounts {
+            open_orders_account: self.open_orders_account,
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![]
+    }
+}
+
 #[derive(Clone)]
 pub struct ConsumeEventsInstruction {
     pub consume_events_admin: Option<TestKeypair>,
@@ -759,6 +1041,7 @@ impl ClientInstruction for ConsumeEventsInstruction {
             consume_events_admin: self.consume_events_admin.map(|kp| kp.pubkey()),
             market: self.market,
             event_heap: market.event_heap,
+            crank_reward_receiver: None,
         };
 
         let mut instruction = make_instruction(program_id, &accounts, instruction);
@@ -780,6 +1063,98 @@ impl ClientInstruction for ConsumeEventsInstruction {
     }
 }
 
+/// Consume events and have the crank reward paid to `crank_reward_receiver`
+pub struct ConsumeEventsWithRewardInstruction {
+    pub consume_events_admin: Option<TestKeypair>,
+    pub market: Pubkey,
+    pub open_orders_accounts: Vec<Pubkey>,
+    pub crank_reward_receiver: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for ConsumeEventsWithRewardInstruction {
+    type Accounts = openbook_v2::accounts::ConsumeEvents;
+    type Instruction = openbook_v2::instruction::ConsumeEvents;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { limit: 10 };
+
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+        let accounts = Self::Accounts {
+            consume_events_admin: self.consume_events_admin.map(|kp| kp.pubkey()),
+            market: self.market,
+            event_heap: market.event_heap,
+            crank_reward_receiver: Some(self.crank_reward_receiver),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        instruction.accounts.extend(
+            self.open_orders_accounts
+                .iter()
+                .map(|pubkey| AccountMeta::new(*pubkey, false)),
+        );
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        match self.consume_events_admin {
+            Some(consume_events_admin) => vec![consume_events_admin],
+            None => vec![],
+        }
+    }
+}
+
+/// Consume events and pass the accounts needed to pay out auto settling makers
+pub struct ConsumeEventsAndSettleInstruction {
+    pub consume_events_admin: Option<TestKeypair>,
+    pub market: Pubkey,
+    pub open_orders_accounts: Vec<Pubkey>,
+    pub user_token_accounts: Vec<Pubkey>,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for ConsumeEventsAndSettleInstruction {
+    type Accounts = openbook_v2::accounts::ConsumeEvents;
+    type Instruction = openbook_v2::instruction::ConsumeEvents;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { limit: 10 };
+
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+        let accounts = Self::Accounts {
+            consume_events_admin: self.consume_events_admin.map(|kp| kp.pubkey()),
+            market: self.market,
+            event_heap: market.event_heap,
+            crank_reward_receiver: None,
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        instruction.accounts.extend(
+            self.open_orders_accounts
+                .iter()
+                .chain([market.market_base_vault, market.market_quote_vault].iter())
+                .chain(self.user_token_accounts.iter())
+                .map(|pubkey| AccountMeta::new(*pubkey, false)),
+        );
+        instruction.accounts.extend([
+            AccountMeta::new_readonly(market.market_authority, false),
+            AccountMeta::new_readonly(Token::id(), false),
+        ]);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        match self.consume_events_admin {
+            Some(consume_events_admin) => vec![consume_events_admin],
+            None => vec![],
+        }
+    }
+}
+
 pub struct ConsumeGivenEventsInstruction {
     pub consume_events_admin: Option<TestKeypair>,
     pub market: Pubkey,
@@ -804,6 +1179,7 @@ impl ClientInstruction for ConsumeGivenEventsInstruction {
             consume_events_admin: self.consume_events_admin.map(|kp| kp.pubkey()),
             market: self.market,
             event_heap: market.event_heap,
+            crank_reward_receiver: None,
         };
 
         let mut instruction = make_instruction(program_id, &accounts, instruction);
@@ -871,11 +1247,62 @@ impl ClientInstruction for SettleFundsInstruction {
     }
 }
 
-#[derive(Clone)]
-pub struct SettleFundsExpiredInstruction {
-    pub close_market_admin: TestKeypair,
+pub struct SettleFundsBatchInstruction {
     pub owner: TestKeypair,
-    pub open_orders_account: Pubkey,
+    /// Open orders accounts with the base and quote token accounts receiving their funds
+    pub settlements: Vec<(Pubkey, Pubkey, Pubkey)>,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SettleFundsBatchInstruction {
+    type Accounts = openbook_v2::accounts::SettleFundsBatch;
+    type Instruction = openbook_v2::instruction::SettleFundsBatch;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+        let open_orders_indexer = Pubkey::find_program_address(
+            &[b"OpenOrdersIndexer".as_ref(), self.owner.pubkey().as_ref()],
+            &program_id,
+        )
+        .0;
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            penalty_payer: self.owner.pubkey(),
+            open_orders_indexer,
+            token_program: Token::id(),
+            system_program: System::id(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        for (open_orders_account, user_base_account, user_quote_account) in &self.settlements {
+            let account: OpenOrdersAccount =
+                account_loader.load(open_orders_account).await.unwrap();
+            let market: Market = account_loader.load(&account.market).await.unwrap();
+            instruction.accounts.extend([
+                AccountMeta::new(*open_orders_account, false),
+                AccountMeta::new(account.market, false),
+                AccountMeta::new_readonly(market.market_authority, false),
+                AccountMeta::new(market.market_base_vault, false),
+                AccountMeta::new(market.market_quote_vault, false),
+                AccountMeta::new(*user_base_account, false),
+                AccountMeta::new(*user_quote_account, false),
+            ]);
+        }
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+#[derive(Clone)]
+pub struct SettleFundsExpiredInstruction {
+    pub close_market_admin: TestKeypair,
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
     pub market: Pubkey,
     pub market_base_vault: Pubkey,
     pub market_quote_vault: Pubkey,
@@ -955,6 +1382,38 @@ impl ClientInstruction for SweepFeesInstruction {
     }
 }
 
+pub struct SetCrankRewardInstruction {
+    pub collect_fee_admin: TestKeypair,
+    pub market: Pubkey,
+    pub crank_reward: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetCrankRewardInstruction {
+    type Accounts = openbook_v2::accounts::SetCrankReward;
+    type Instruction = openbook_v2::instruction::SetCrankReward;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            crank_reward: self.crank_reward,
+        };
+
+        let accounts = Self::Accounts {
+            collect_fee_admin: self.collect_fee_admin.pubkey(),
+            market: self.market,
+        };
+        let instruction = make_instruction(program_id, &accounts, instruction);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.collect_fee_admin]
+    }
+}
+
 pub struct DepositInstruction {
     pub open_orders_account: Pubkey,
     pub market: Pubkey,
@@ -1000,6 +1459,55 @@ impl ClientInstruction for DepositInstruction {
     }
 }
 
+pub struct WithdrawInstruction {
+    pub open_orders_account: Pubkey,
+    pub market: Pubkey,
+    pub market_base_vault: Pubkey,
+    pub market_quote_vault: Pubkey,
+    pub user_base_account: Pubkey,
+    pub user_quote_account: Pubkey,
+    pub owner: TestKeypair,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for WithdrawInstruction {
+    type Accounts = openbook_v2::accounts::Withdraw;
+    type Instruction = openbook_v2::instruction::Withdraw;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            base_amount: self.base_amount,
+            quote_amount: self.quote_amount,
+        };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            penalty_payer: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+            market: self.market,
+            market_authority: market.market_authority,
+            market_base_vault: self.market_base_vault,
+            market_quote_vault: self.market_quote_vault,
+            user_base_account: self.user_base_account,
+            user_quote_account: self.user_quote_account,
+            token_program: Token::id(),
+            system_program: System::id(),
+        };
+        let instruction = make_instruction(program_id, &accounts, instruction);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
 pub struct StubOracleSetInstruction {
     pub mint: Pubkey,
     pub owner: TestKeypair,
@@ -1041,6 +1549,56 @@ impl ClientInstruction for StubOracleSetInstruction {
     }
 }
 
+#[derive(Clone)]
+pub struct StubOracleSetTestInstruction {
+    pub mint: Pubkey,
+    pub owner: TestKeypair,
+    pub price: f64,
+    pub last_update_slot: u64,
+    pub last_update_ts: i64,
+    pub deviation: f64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for StubOracleSetTestInstruction {
+    type Accounts = openbook_v2::accounts::StubOracleSet;
+    type Instruction = openbook_v2::instruction::StubOracleSetTest;
+
+    async fn to_instruction(
+        &self,
+        _loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            price: self.price,
+            last_update_slot: self.last_update_slot,
+            last_update_ts: self.last_update_ts,
+            deviation: self.deviation,
+        };
+
+        let oracle = Pubkey::find_program_address(
+            &[
+                b"StubOracle".as_ref(),
+                self.owner.pubkey().as_ref(),
+                self.mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            oracle,
+            owner: self.owner.pubkey(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
 pub struct StubOracleCreate {
     pub mint: Pubkey,
     pub owner: TestKeypair,
@@ -1228,6 +1786,172 @@ impl ClientInstruction for PruneOrdersInstruction {
     }
 }
 
+pub struct PruneExpiredOrdersInstruction {
+    pub penalty_payer: TestKeypair,
+    pub market: Pubkey,
+    pub limit: u8,
+    pub open_orders_accounts: Vec<Pubkey>,
+    pub crank_reward_receiver: Option<Pubkey>,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PruneExpiredOrdersInstruction {
+    type Accounts = openbook_v2::accounts::PruneExpiredOrders;
+    type Instruction = openbook_v2::instruction::PruneExpiredOrders;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction { limit: self.limit };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            penalty_payer: self.penalty_payer.pubkey(),
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+            event_heap: market.event_heap,
+            crank_reward_receiver: self.crank_reward_receiver,
+            system_program: System::id(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, instruction);
+        instruction.accounts.extend(
+            self.open_orders_accounts
+                .iter()
+                .map(|pubkey| AccountMeta::new(*pubkey, false)),
+        );
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.penalty_payer]
+    }
+}
+
+pub struct GetL2DepthInstruction {
+    pub market: Pubkey,
+    pub max_levels: u8,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for GetL2DepthInstruction {
+    type Accounts = openbook_v2::accounts::GetL2Depth;
+    type Instruction = openbook_v2::instruction::GetL2Depth;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            max_levels: self.max_levels,
+        };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+            oracle_a: market.oracle_a.into(),
+            oracle_b: market.oracle_b.into(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![]
+    }
+}
+
+/// Simulate `get_l2_depth` and decode its return data
+pub async fn get_l2_depth(
+    solana: &SolanaCookie,
+    market: Pubkey,
+    max_levels: u8,
+) -> std::result::Result<L2Depth, BanksClientError> {
+    let (_, instruction) = GetL2DepthInstruction { market, max_levels }
+        .to_instruction(solana)
+        .await;
+    simulate_return_data(solana, instruction).await
+}
+
+pub struct QuoteTakeOrderInstruction {
+    pub market: Pubkey,
+    pub side: Side,
+    pub price_lots: i64,
+    pub max_base_lots: i64,
+    pub max_quote_lots_including_fees: i64,
+    pub order_type: PlaceOrderType,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for QuoteTakeOrderInstruction {
+    type Accounts = openbook_v2::accounts::QuoteTakeOrder;
+    type Instruction = openbook_v2::instruction::QuoteTakeOrder;
+    async fn to_instruction(
+        &self,
+        account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            args: PlaceTakeOrderArgs {
+                side: self.side,
+                price_lots: self.price_lots,
+                max_base_lots: self.max_base_lots,
+                max_quote_lots_including_fees: self.max_quote_lots_including_fees,
+                order_type: self.order_type,
+                limit: 10,
+            },
+        };
+        let market: Market = account_loader.load(&self.market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            market: self.market,
+            bids: market.bids,
+            asks: market.asks,
+            event_heap: market.event_heap,
+            oracle_a: market.oracle_a.into(),
+            oracle_b: market.oracle_b.into(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![]
+    }
+}
+
+/// Simulate `quote_take_order` and decode its return data
+pub async fn quote_take_order(
+    solana: &SolanaCookie,
+    ix: QuoteTakeOrderInstruction,
+) -> std::result::Result<TakeQuote, BanksClientError> {
+    let (_, instruction) = ix.to_instruction(solana).await;
+    simulate_return_data(solana, instruction).await
+}
+
+async fn simulate_return_data<T: AnchorDeserialize>(
+    solana: &SolanaCookie,
+    instruction: instruction::Instruction,
+) -> std::result::Result<T, BanksClientError> {
+    let return_data = solana
+        .simulate_transaction_return_data(&[instruction])
+        .await?;
+
+    // trailing zero bytes are trimmed off the return data, all zero data returns none at all
+    let mut data = match return_data {
+        Some(return_data) => {
+            assert_eq!(return_data.program_id, openbook_v2::id());
+            return_data.data
+        }
+        None => vec![],
+    };
+    data.resize(solana_program::program::MAX_RETURN_DATA, 0);
+    Ok(T::deserialize(&mut &data[..]).unwrap())
+}
+
 pub struct SetDelegateInstruction {
     pub delegate_account: Option<Pubkey>,
     pub owner: TestKeypair,
@@ -1259,6 +1983,284 @@ impl ClientInstruction for SetDelegateInstruction {
     }
 }
 
+pub struct SetDelegatePermissionsInstruction {
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
+    pub permissions: u8,
+    pub max_order_quote_native: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetDelegatePermissionsInstruction {
+    type Accounts = openbook_v2::accounts::SetDelegatePermissions;
+    type Instruction = openbook_v2::instruction::SetDelegatePermissions;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            permissions: self.permissions,
+            max_order_quote_native: self.max_order_quote_native,
+        };
+
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct TransferOpenOrdersAccountInstruction {
+    pub owner: TestKeypair,
+    pub new_owner: TestKeypair,
+    pub open_orders_account: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for TransferOpenOrdersAccountInstruction {
+    type Accounts = openbook_v2::accounts::TransferOpenOrdersAccount;
+    type Instruction = openbook_v2::instruction::TransferOpenOrdersAccount;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+
+        let open_orders_indexer = |owner: Pubkey| {
+            Pubkey::find_program_address(
+                &[b"OpenOrdersIndexer".as_ref(), owner.as_ref()],
+                &program_id,
+            )
+            .0
+        };
+        let accounts = Self::Accounts {
+            payer: self.new_owner.pubkey(),
+            owner: self.owner.pubkey(),
+            new_owner: self.new_owner.pubkey(),
+            open_orders_indexer: open_orders_indexer(self.owner.pubkey()),
+            new_open_orders_indexer: open_orders_indexer(self.new_owner.pubkey()),
+            open_orders_account: self.open_orders_account,
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner, self.new_owner]
+    }
+}
+
+pub struct SetSessionDelegateInstruction {
+    pub authority: TestKeypair,
+    pub open_orders_account: Pubkey,
+    pub session_key: Pubkey,
+    pub expiry_timestamp: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetSessionDelegateInstruction {
+    type Accounts = openbook_v2::accounts::SetSessionDelegate;
+    type Instruction = openbook_v2::instruction::SetSessionDelegate;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            session_key: self.session_key,
+            expiry_timestamp: self.expiry_timestamp,
+        };
+
+        let accounts = Self::Accounts {
+            payer: self.authority.pubkey(),
+            authority: self.authority.pubkey(),
+            open_orders_account: self.open_orders_account,
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.authority]
+    }
+}
+
+pub struct SetStpGroupInstruction {
+    pub stp_group: u16,
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetStpGroupInstruction {
+    type Accounts = openbook_v2::accounts::SetStpGroup;
+    type Instruction = openbook_v2::instruction::SetStpGroup;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            stp_group: self.stp_group,
+        };
+
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct SetAutoSettleInstruction {
+    pub enabled: bool,
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetAutoSettleInstruction {
+    type Accounts = openbook_v2::accounts::SetAutoSettle;
+    type Instruction = openbook_v2::instruction::SetAutoSettle;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            enabled: self.enabled,
+        };
+
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+#[derive(Clone)]
+pub struct SetRiskLimitsInstruction {
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
+    pub max_base_lots_per_side: i64,
+    pub max_order_quote_native: u64,
+    pub max_taker_quote_native_per_window: u64,
+    pub window_seconds: u32,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetRiskLimitsInstruction {
+    type Accounts = openbook_v2::accounts::SetRiskLimits;
+    type Instruction = openbook_v2::instruction::SetRiskLimits;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            max_base_lots_per_side: self.max_base_lots_per_side,
+            max_order_quote_native: self.max_order_quote_native,
+            max_taker_quote_native_per_window: self.max_taker_quote_native_per_window,
+            window_seconds: self.window_seconds,
+        };
+
+        let accounts = Self::Accounts {
+            payer: self.owner.pubkey(),
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct SetHeartbeatTimeoutInstruction {
+    pub owner: TestKeypair,
+    pub open_orders_account: Pubkey,
+    pub timeout_seconds: u16,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for SetHeartbeatTimeoutInstruction {
+    type Accounts = openbook_v2::accounts::SetHeartbeatTimeout;
+    type Instruction = openbook_v2::instruction::SetHeartbeatTimeout;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {
+            timeout_seconds: self.timeout_seconds,
+        };
+
+        let accounts = Self::Accounts {
+            owner: self.owner.pubkey(),
+            open_orders_account: self.open_orders_account,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct HeartbeatInstruction {
+    pub signer: TestKeypair,
+    pub open_orders_account: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for HeartbeatInstruction {
+    type Accounts = openbook_v2::accounts::Heartbeat;
+    type Instruction = openbook_v2::instruction::Heartbeat;
+    async fn to_instruction(
+        &self,
+        _account_loader: impl ClientAccountLoader + 'async_trait,
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = openbook_v2::id();
+        let instruction = Self::Instruction {};
+
+        let accounts = Self::Accounts {
+            signer: self.signer.pubkey(),
+            open_orders_account: self.open_orders_account,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.signer]
+    }
+}
+
 #[derive(Clone)]
 pub struct EditOrderInstruction {
     pub open_orders_account: Pubkey,
@@ -5,7 +5,9 @@ use std::{sync::Arc, sync::RwLock};
 
 use fixed::types::I80F48;
 use log::*;
-use openbook_v2::state::Market;
+use openbook_v2::state::{
+    Market, CRANK_REWARD, MAX_NUM_EVENTS, MAX_ORDERTREE_NODES, PENALTY_EVENT_HEAP,
+};
 use solana_program::{program_option::COption, program_pack::Pack};
 use solana_program_test::*;
 use solana_sdk::pubkey::Pubkey;
@@ -279,7 +281,8 @@ pub struct TestContext {
 }
 
 pub struct TestNewMarketInitialize {
-    pub fee_penalty: u64,
+    pub penalty_event_heap: u64,
+    pub crank_reward: u64,
     pub quote_lot_size: i64,
     pub base_lot_size: i64,
     pub maker_fee: i64,
@@ -289,13 +292,17 @@ pub struct TestNewMarketInitialize {
     pub consume_events_admin_bool: bool,
     pub time_expiry: i64,
     pub with_oracle: bool,
+    pub with_fallback_oracle: bool,
     pub payer_as_delegate: bool,
+    pub book_side_capacity: u32,
+    pub event_heap_capacity: u16,
 }
 
 impl Default for TestNewMarketInitialize {
     fn default() -> TestNewMarketInitialize {
         TestNewMarketInitialize {
-            fee_penalty: 0,
+            penalty_event_heap: PENALTY_EVENT_HEAP,
+            crank_reward: CRANK_REWARD,
             quote_lot_size: 10,
             base_lot_size: 100,
             maker_fee: -200,
@@ -305,7 +312,10 @@ impl Default for TestNewMarketInitialize {
             consume_events_admin_bool: false,
             time_expiry: 0,
             with_oracle: true,
+            with_fallback_oracle: false,
             payer_as_delegate: false,
+            book_side_capacity: MAX_ORDERTREE_NODES as u32,
+            event_heap_capacity: MAX_NUM_EVENTS,
         }
     }
 }
@@ -358,6 +368,11 @@ impl TestContext {
         } else {
             None
         };
+        let fallback_oracle = if args.with_fallback_oracle {
+            Some(tokens[2].oracle)
+        } else {
+            None
+        };
 
         let openbook_v2::accounts::CreateMarket {
             market,
@@ -380,9 +395,18 @@ impl TestContext {
                 taker_fee: args.taker_fee,
                 base_mint: mints[0].pubkey,
                 quote_mint: mints[1].pubkey,
-                fee_penalty: args.fee_penalty,
+                penalty_event_heap: args.penalty_event_heap,
+                crank_reward: args.crank_reward,
                 time_expiry: args.time_expiry,
-                ..CreateMarketInstruction::with_new_book_and_heap(solana, oracle, None).await
+                fallback_to_oracle_b: args.with_fallback_oracle,
+                ..CreateMarketInstruction::with_new_book_and_heap_capacity(
+                    solana,
+                    oracle,
+                    fallback_oracle,
+                    args.book_side_capacity,
+                    args.event_heap_capacity,
+                )
+                .await
             },
         )
         .await
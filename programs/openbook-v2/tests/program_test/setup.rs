@@ -5,6 +5,7 @@ use anchor_lang::prelude::*;
 use super::client::*;
 use super::solana::SolanaCookie;
 use super::{send_tx, MintCookie, TestKeypair, UserCookie};
+use openbook_v2::state::MAX_OPEN_ORDERS;
 
 #[derive(Clone)]
 pub struct Token {
@@ -92,6 +93,7 @@ pub async fn create_open_orders_account(
             owner,
             payer: payer.key,
             delegate,
+            capacity: MAX_OPEN_ORDERS as u16,
         },
     )
     .await
@@ -15,6 +15,7 @@ use solana_sdk::{
     pubkey::Pubkey,
     signature::{Keypair, Signer},
     transaction::Transaction,
+    transaction_context::TransactionReturnData,
 };
 use spl_token::*;
 
@@ -83,6 +84,29 @@ impl SolanaCookie {
         result
     }
 
+    /// Simulate a transaction signed by the payer and return its return data
+    pub async fn simulate_transaction_return_data(
+        &self,
+        instructions: &[Instruction],
+    ) -> Result<Option<TransactionReturnData>, BanksClientError> {
+        let mut context = self.context.borrow_mut();
+
+        let mut transaction =
+            Transaction::new_with_payer(instructions, Some(&context.payer.pubkey()));
+        transaction.sign(&[&context.payer], context.last_blockhash);
+
+        let simulation = context
+            .banks_client
+            .simulate_transaction(transaction)
+            .await?;
+        if let Some(Err(err)) = simulation.result {
+            return Err(BanksClientError::TransactionError(err));
+        }
+        Ok(simulation
+            .simulation_details
+            .and_then(|details| details.return_data))
+    }
+
     pub async fn get_clock(&self) -> solana_program::clock::Clock {
         self.context
             .borrow_mut()
@@ -152,8 +176,12 @@ impl SolanaCookie {
     }
 
     pub async fn create_account_for_type<T>(&self, owner: &Pubkey) -> Pubkey {
+        self.create_account_with_len(owner, 8 + std::mem::size_of::<T>())
+            .await
+    }
+
+    pub async fn create_account_with_len(&self, owner: &Pubkey, len: usize) -> Pubkey {
         let key = TestKeypair::new();
-        let len = 8 + std::mem::size_of::<T>();
         let rent = self.rent.minimum_balance(len);
         let create_account_instr = solana_sdk::system_instruction::create_account(
             &self.context.borrow().payer.pubkey(),
@@ -242,6 +270,29 @@ impl SolanaCookie {
         )
     }
 
+    /// Overwrite the data of an account, funded with the rent of the new length
+    pub async fn set_account_data(&self, address: Pubkey, data: &[u8]) {
+        let mut context = self.context.borrow_mut();
+        let mut account = context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        account.data = data.to_vec();
+        account.lamports = self.rent.minimum_balance(data.len());
+        context.set_account(&address, &account.into());
+    }
+
+    pub async fn get_account_lamports(&self, address: Pubkey) -> u64 {
+        self.context
+            .borrow_mut()
+            .banks_client
+            .get_balance(address)
+            .await
+            .unwrap()
+    }
+
     pub async fn get_account_opt<T: AccountDeserialize>(&self, address: Pubkey) -> Option<T> {
         let data = self.get_account_data(address).await?;
         let mut data_slice: &[u8] = &data;
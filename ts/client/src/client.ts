@@ -67,8 +67,10 @@ export function nameToString(name: number[]): string {
   return utf8.decode(new Uint8Array(name)).split('\x00')[0];
 }
 
-const BooksideSpace = 90944 + 8;
-const EventHeapSpace = 91280 + 8;
+// Account sizes including the 8 byte discriminator, for a given number of order tree
+// nodes or events
+const booksideSpace = (capacity: number): number => 8 + 832 + 88 * capacity;
+const eventHeapSpace = (capacity: number): number => 8 + 80 + 152 * capacity;
 
 export const OPENBOOK_PROGRAM_ID = new PublicKey(
   'opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb',
@@ -226,21 +228,29 @@ export class OpenBookV2Client {
     oracleConfigParams: OracleConfigParams = {
       confFilter: 0.1,
       maxStalenessSlots: 100,
+      maxStalenessSeconds: null,
+      minLiquidity: null,
+      useEmaPrice: false,
+      fallbackToOracleB: false,
     },
     market = Keypair.generate(),
     collectFeeAdmin?: PublicKey,
+    bookSideCapacity = 1024,
+    eventHeapCapacity = 600,
+    penaltyEventHeap = new BN(500),
+    crankReward = new BN(100),
   ): Promise<[TransactionInstruction[], Signer[]]> {
     const [bidIx, bidsKeypair] = await this.createProgramAccountIx(
       payer,
-      BooksideSpace,
+      booksideSpace(bookSideCapacity),
     );
     const [askIx, askKeypair] = await this.createProgramAccountIx(
       payer,
-      BooksideSpace,
+      booksideSpace(bookSideCapacity),
     );
     const [eventHeapIx, eventHeapKeypair] = await this.createProgramAccountIx(
       payer,
-      EventHeapSpace,
+      eventHeapSpace(eventHeapCapacity),
     );
 
     const [marketAuthority] = PublicKey.findProgramAddressSync(
@@ -274,6 +284,10 @@ export class OpenBookV2Client {
         makerFee,
         takerFee,
         timeExpiry,
+        bookSideCapacity,
+        eventHeapCapacity,
+        penaltyEventHeap,
+        crankReward,
       )
       .accounts({
         market: market.publicKey,
@@ -427,6 +441,7 @@ export class OpenBookV2Client {
     owner: PublicKey = this.walletPk,
     delegateAccount: PublicKey | null,
     openOrdersIndexer?: PublicKey | null,
+    capacity = 24,
   ): Promise<[TransactionInstruction[], PublicKey]> {
     const ixs: TransactionInstruction[] = [];
     let accountIndex = new BN(1);
@@ -453,7 +468,7 @@ export class OpenBookV2Client {
 
     ixs.push(
       await this.program.methods
-        .createOpenOrdersAccount(name)
+        .createOpenOrdersAccount(name, capacity)
         .accounts({
           openOrdersIndexer,
           openOrdersAccount,
@@ -475,12 +490,15 @@ export class OpenBookV2Client {
     name: string,
     owner: Keypair = payer,
     delegateAccount: PublicKey | null = null,
+    capacity = 24,
   ): Promise<PublicKey> {
     const [ixs, openOrdersAccount] = await this.createOpenOrdersIx(
       market,
       name,
       owner.publicKey,
       delegateAccount,
+      null,
+      capacity,
     );
     const additionalSigners = [payer];
     if (owner !== payer) {
@@ -980,6 +998,7 @@ export class OpenBookV2Client {
     market: MarketAccount,
     limit: BN,
     remainingAccounts: PublicKey[],
+    crankRewardReceiver: PublicKey | null = this.walletPk,
   ): Promise<TransactionInstruction> {
     const accountsMeta: AccountMeta[] = remainingAccounts.map((remaining) => ({
       pubkey: remaining,
@@ -999,6 +1018,7 @@ export class OpenBookV2Client {
         eventHeap: market.eventHeap,
         market: marketPublicKey,
         consumeEventsAdmin,
+        crankRewardReceiver,
       })
       .remainingAccounts(accountsMeta)
       .instruction();
@@ -1030,6 +1050,7 @@ export class OpenBookV2Client {
         eventHeap: market.eventHeap,
         market: marketPublicKey,
         consumeEventsAdmin: market.consumeEventsAdmin.key,
+        crankRewardReceiver: this.walletPk,
       })
       .remainingAccounts(accountsMeta)
       .instruction();
@@ -1042,6 +1063,7 @@ export class OpenBookV2Client {
     market: MarketAccount,
     slots: BN[],
     remainingAccounts: PublicKey[],
+    crankRewardReceiver: PublicKey | null = this.walletPk,
   ): Promise<TransactionInstruction> {
     const accountsMeta: AccountMeta[] = remainingAccounts.map((remaining) => ({
       pubkey: remaining,
@@ -1054,6 +1076,7 @@ export class OpenBookV2Client {
         eventHeap: market.eventHeap,
         market: marketPublicKey,
         consumeEventsAdmin: market.consumeEventsAdmin.key,
+        crankRewardReceiver,
       })
       .remainingAccounts(accountsMeta)
       .instruction();
@@ -6,6 +6,22 @@ export type OpenbookV2 = {
       name: 'createMarket';
       docs: [
         'Create a [`Market`](crate::state::Market) for a given token pair.',
+        '',
+        'The `bids`, `asks` and `event_heap` accounts are created by the client beforehand, sized',
+        'for `book_side_capacity` order tree nodes and `event_heap_capacity` events. See',
+        '[`DynamicAccount::space`](crate::accounts_zerocopy::DynamicAccount::space); the default',
+        'capacities are [`MAX_ORDERTREE_NODES`](crate::state::MAX_ORDERTREE_NODES) and',
+        '[`MAX_NUM_EVENTS`](crate::state::MAX_NUM_EVENTS).',
+        '',
+        'Takers pay `penalty_event_heap` lamports for every event they add to the event heap,',
+        "which fund a reward of `crank_reward` lamports per event consumed. The reward can't",
+        'exceed the penalty. The defaults are [`PENALTY_EVENT_HEAP`](crate::state::PENALTY_EVENT_HEAP),',
+        'which is also used when `penalty_event_heap` is 0, and',
+        '[`CRANK_REWARD`](crate::state::CRANK_REWARD).',
+        '',
+        "AMM pool oracles must trade the market's tokens: a single pool, or each pool when",
+        'falling back to `oracle_b`, has the base as first and the quote as second token. When',
+        'dividing the prices, the base and quote pools share their second token.',
       ];
       accounts: [
         {
@@ -154,6 +170,22 @@ export type OpenbookV2 = {
           name: 'timeExpiry';
           type: 'i64';
         },
+        {
+          name: 'bookSideCapacity';
+          type: 'u32';
+        },
+        {
+          name: 'eventHeapCapacity';
+          type: 'u16';
+        },
+        {
+          name: 'penaltyEventHeap';
+          type: 'u64';
+        },
+        {
+          name: 'crankReward';
+          type: 'u64';
+        },
       ];
     },
     {
@@ -259,10 +291,50 @@ export type OpenbookV2 = {
       ];
       args: [];
     },
+    {
+      name: 'migrateOpenOrdersIndexer';
+      docs: [
+        'Backfill the markets of an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer)',
+        'created before it recorded them, growing the account paid by the `payer`.',
+        '',
+        'Pass any of the indexed open orders accounts as remaining accounts, large indexers can',
+        'be migrated over several transactions. Anyone can migrate an indexer.',
+      ];
+      accounts: [
+        {
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'openOrdersIndexer';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
     {
       name: 'createOpenOrdersAccount';
       docs: [
-        'Create an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+        'Create an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) with room for',
+        '`capacity` open orders.',
+        '',
+        '`capacity` must be at least [`MAX_OPEN_ORDERS`](crate::state::MAX_OPEN_ORDERS). As',
+        'accounts created by a program are limited to 10KiB, use',
+        '[`grow_open_orders_account`](crate::openbook_v2::grow_open_orders_account) to go',
+        'beyond [`MAX_OPEN_ORDERS_CREATION_CAPACITY`](crate::state::MAX_OPEN_ORDERS_CREATION_CAPACITY)',
+        '(248) open orders.',
       ];
       accounts: [
         {
@@ -307,6 +379,46 @@ export type OpenbookV2 = {
           name: 'name';
           type: 'string';
         },
+        {
+          name: 'capacity';
+          type: 'u16';
+        },
+      ];
+    },
+    {
+      name: 'growOpenOrdersAccount';
+      docs: [
+        'Grow an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) to have room for',
+        '`capacity` open orders, at most [`MAX_OPEN_ORDERS_GROWTH`](crate::state::MAX_OPEN_ORDERS_GROWTH)',
+        '(256) more than it had before.',
+      ];
+      accounts: [
+        {
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'capacity';
+          type: 'u16';
+        },
       ];
     },
     {
@@ -1023,38 +1135,24 @@ export type OpenbookV2 = {
       ];
     },
     {
-      name: 'consumeEvents';
+      name: 'placeTakeOrderExactQuoteIn';
       docs: [
-        'Process up to `limit` [events](crate::state::AnyEvent).',
-        '',
-        "When a user places a 'take' order, they do not know beforehand which",
-        "market maker will have placed the 'make' order that they get executed",
-        "against. This prevents them from passing in a market maker's",
-        '[`OpenOrdersAccount`](crate::state::OpenOrdersAccount), which is needed',
-        'to credit/debit the relevant tokens to/from the maker. As such, Openbook',
-        "uses a 'crank' system, where `place_order` only emits events, and",
-        '`consume_events` handles token settlement.',
-        '',
-        'Currently, there are two types of events: [`FillEvent`](crate::state::FillEvent)s',
-        'and [`OutEvent`](crate::state::OutEvent)s.',
+        'Place a take order buying as much base as possible for `quote_amount_native`,',
+        'taker fees included.',
         '',
-        'A `FillEvent` is emitted when an order is filled, and it is handled by',
-        'debiting whatever the taker is selling from the taker and crediting',
-        'it to the maker, and debiting whatever the taker is buying from the',
-        'maker and crediting it to the taker. Note that *no tokens are moved*,',
-        "these are just debits and credits to each party's [`Position`](crate::state::Position).",
-        '',
-        'An `OutEvent` is emitted when a limit order needs to be removed from',
-        'the book during a `place_order` invocation, and it is handled by',
-        'crediting whatever the maker would have sold (quote token in a bid,',
-        'base token in an ask) back to the maker.',
+        'Only the spent quote is transferred from the user, the unspent remainder (e.g. lot',
+        "rounding dust) stays in the user's token account.",
       ];
       accounts: [
         {
-          name: 'consumeEventsAdmin';
-          isMut: false;
+          name: 'signer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'penaltyPayer';
+          isMut: true;
           isSigner: true;
-          isOptional: true;
         },
         {
           name: 'market';
@@ -1062,32 +1160,27 @@ export type OpenbookV2 = {
           isSigner: false;
         },
         {
-          name: 'eventHeap';
+          name: 'marketAuthority';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
           isMut: true;
           isSigner: false;
         },
-      ];
-      args: [
         {
-          name: 'limit';
-          type: 'u64';
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
         },
-      ];
-    },
-    {
-      name: 'consumeGivenEvents';
-      docs: [
-        'Process the [events](crate::state::AnyEvent) at the given positions.',
-      ];
-      accounts: [
         {
-          name: 'consumeEventsAdmin';
-          isMut: false;
-          isSigner: true;
-          isOptional: true;
+          name: 'marketBaseVault';
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: 'market';
+          name: 'marketQuoteVault';
           isMut: true;
           isSigner: false;
         },
@@ -1096,117 +1189,437 @@ export type OpenbookV2 = {
           isMut: true;
           isSigner: false;
         },
-      ];
-      args: [
         {
-          name: 'slots';
-          type: {
-            vec: 'u64';
-          };
+          name: 'userBaseAccount';
+          isMut: true;
+          isSigner: false;
         },
-      ];
-    },
-    {
-      name: 'cancelOrder';
-      docs: [
-        'Cancel an order by its `order_id`.',
-        '',
-        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
-        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
-      ];
-      accounts: [
         {
-          name: 'signer';
-          isMut: false;
-          isSigner: true;
+          name: 'userQuoteAccount';
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: 'openOrdersAccount';
-          isMut: true;
+          name: 'oracleA';
+          isMut: false;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: 'market';
+          name: 'oracleB';
           isMut: false;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: 'bids';
-          isMut: true;
+          name: 'tokenProgram';
+          isMut: false;
           isSigner: false;
         },
         {
-          name: 'asks';
-          isMut: true;
+          name: 'systemProgram';
+          isMut: false;
           isSigner: false;
         },
+        {
+          name: 'openOrdersAdmin';
+          isMut: false;
+          isSigner: true;
+          isOptional: true;
+        },
       ];
       args: [
         {
-          name: 'orderId';
-          type: 'u128';
+          name: 'args';
+          type: {
+            defined: 'PlaceTakeOrderExactQuoteInArgs';
+          };
         },
       ];
     },
     {
-      name: 'cancelOrderByClientOrderId';
+      name: 'placeTakeOrderTwoHop';
       docs: [
-        'Cancel an order by its `client_order_id`.',
+        'Swap through two markets sharing a token in a single instruction, e.g. A->USDC->B.',
         '',
-        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
-        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+        'The tokens received from a take order on the first market are used as input of a take',
+        'order on the second one. Fails if less than `min_amount_out_native` would be received.',
       ];
       accounts: [
         {
           name: 'signer';
-          isMut: false;
+          isMut: true;
           isSigner: true;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'penaltyPayer';
           isMut: true;
-          isSigner: false;
+          isSigner: true;
         },
         {
-          name: 'market';
-          isMut: false;
-          isSigner: false;
+          name: 'hop1';
+          accounts: [
+            {
+              name: 'market';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'marketAuthority';
+              isMut: false;
+              isSigner: false;
+            },
+            {
+              name: 'bids';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'asks';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'marketBaseVault';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'marketQuoteVault';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'eventHeap';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'oracleA';
+              isMut: false;
+              isSigner: false;
+              isOptional: true;
+            },
+            {
+              name: 'oracleB';
+              isMut: false;
+              isSigner: false;
+              isOptional: true;
+            },
+            {
+              name: 'openOrdersAdmin';
+              isMut: false;
+              isSigner: true;
+              isOptional: true;
+            },
+          ];
         },
         {
-          name: 'bids';
+          name: 'hop2';
+          accounts: [
+            {
+              name: 'market';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'marketAuthority';
+              isMut: false;
+              isSigner: false;
+            },
+            {
+              name: 'bids';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'asks';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'marketBaseVault';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'marketQuoteVault';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'eventHeap';
+              isMut: true;
+              isSigner: false;
+            },
+            {
+              name: 'oracleA';
+              isMut: false;
+              isSigner: false;
+              isOptional: true;
+            },
+            {
+              name: 'oracleB';
+              isMut: false;
+              isSigner: false;
+              isOptional: true;
+            },
+            {
+              name: 'openOrdersAdmin';
+              isMut: false;
+              isSigner: true;
+              isOptional: true;
+            },
+          ];
+        },
+        {
+          name: 'userInputAccount';
           isMut: true;
           isSigner: false;
         },
         {
-          name: 'asks';
+          name: 'userIntermediateAccount';
           isMut: true;
           isSigner: false;
+          isOptional: true;
+          docs: [
+            "Receives the part of the first hop's output that couldn't be used in the second hop,",
+            'only required if there is any',
+          ];
         },
-      ];
-      args: [
         {
-          name: 'clientOrderId';
-          type: 'u64';
+          name: 'userOutputAccount';
+          isMut: true;
+          isSigner: false;
         },
-      ];
-      returns: 'i64';
-    },
-    {
-      name: 'cancelAllOrders';
-      docs: ['Cancel up to `limit` orders, optionally filtering by side'];
-      accounts: [
         {
-          name: 'signer';
+          name: 'tokenProgram';
           isMut: false;
-          isSigner: true;
-        },
-        {
-          name: 'openOrdersAccount';
-          isMut: true;
           isSigner: false;
         },
         {
-          name: 'market';
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'args';
+          type: {
+            defined: 'PlaceTakeOrderTwoHopArgs';
+          };
+        },
+      ];
+    },
+    {
+      name: 'consumeEvents';
+      docs: [
+        'Process up to `limit` [events](crate::state::AnyEvent).',
+        '',
+        "When a user places a 'take' order, they do not know beforehand which",
+        "market maker will have placed the 'make' order that they get executed",
+        "against. This prevents them from passing in a market maker's",
+        '[`OpenOrdersAccount`](crate::state::OpenOrdersAccount), which is needed',
+        'to credit/debit the relevant tokens to/from the maker. As such, Openbook',
+        "uses a 'crank' system, where `place_order` only emits events, and",
+        '`consume_events` handles token settlement.',
+        '',
+        'Currently, there are two types of events: [`FillEvent`](crate::state::FillEvent)s',
+        'and [`OutEvent`](crate::state::OutEvent)s.',
+        '',
+        'A `FillEvent` is emitted when an order is filled, and it is handled by',
+        'debiting whatever the taker is selling from the taker and crediting',
+        'it to the maker, and debiting whatever the taker is buying from the',
+        'maker and crediting it to the taker. Note that *no tokens are moved*,',
+        "these are just debits and credits to each party's [`Position`](crate::state::Position).",
+        'Makers that opted into [`auto_settle`](crate::state::Position::auto_settle) are paid the',
+        'funds credited by the fill directly if the market authority, the vaults, the token program',
+        "and the associated token accounts of the maker's owner are passed as remaining accounts. The same holds",
+        'for fills executed right away while a taker order is matched.',
+        '',
+        'An `OutEvent` is emitted when a limit order needs to be removed from',
+        'the book during a `place_order` invocation, and it is handled by',
+        'crediting whatever the maker would have sold (quote token in a bid,',
+        'base token in an ask) back to the maker.',
+        '',
+        'The `crank_reward_receiver` is paid [`crank_reward`](crate::state::Market::crank_reward)',
+        "lamports for every processed event, as long as the market's",
+        '[`penalty_pool`](crate::state::Market::penalty_pool) covers it. The reward stays in the',
+        'pool if it would leave the receiver below rent exemption.',
+      ];
+      accounts: [
+        {
+          name: 'consumeEventsAdmin';
+          isMut: false;
+          isSigner: true;
+          isOptional: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'eventHeap';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'crankRewardReceiver';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u64';
+        },
+      ];
+    },
+    {
+      name: 'consumeGivenEvents';
+      docs: [
+        'Process the [events](crate::state::AnyEvent) at the given positions.',
+        '',
+        "All positions must be below the capacity of the market's event heap.",
+      ];
+      accounts: [
+        {
+          name: 'consumeEventsAdmin';
+          isMut: false;
+          isSigner: true;
+          isOptional: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'eventHeap';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'crankRewardReceiver';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [
+        {
+          name: 'slots';
+          type: {
+            vec: 'u64';
+          };
+        },
+      ];
+    },
+    {
+      name: 'cancelOrder';
+      docs: [
+        'Cancel an order by its `order_id`.',
+        '',
+        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
+        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ];
+      accounts: [
+        {
+          name: 'signer';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'orderId';
+          type: 'u128';
+        },
+      ];
+    },
+    {
+      name: 'cancelOrderByClientOrderId';
+      docs: [
+        'Cancel an order by its `client_order_id`.',
+        '',
+        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
+        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ];
+      accounts: [
+        {
+          name: 'signer';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'clientOrderId';
+          type: 'u64';
+        },
+      ];
+      returns: 'i64';
+    },
+    {
+      name: 'cancelAllOrders';
+      docs: ['Cancel up to `limit` orders, optionally filtering by side'];
+      accounts: [
+        {
+          name: 'signer';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
           isMut: false;
           isSigner: false;
         },
@@ -1236,6 +1649,42 @@ export type OpenbookV2 = {
         },
       ];
     },
+    {
+      name: 'cancelOnMissedHeartbeat';
+      docs: [
+        'Cancel up to `limit` orders of an open orders account whose',
+        '[`heartbeat_timeout_seconds`](crate::state::Position::heartbeat_timeout_seconds) elapsed',
+        'without a heartbeat. Permissionless.',
+      ];
+      accounts: [
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u8';
+        },
+      ];
+    },
     {
       name: 'deposit';
       docs: [
@@ -1361,12 +1810,19 @@ export type OpenbookV2 = {
       ];
     },
     {
-      name: 'settleFunds';
-      docs: ['Withdraw any available tokens.'];
+      name: 'withdraw';
+      docs: [
+        "Withdraw a certain amount of `base` and `quote` lamports from one's",
+        '[`Position`](crate::state::Position), leaving the rest available for placing orders.',
+        '',
+        "The amounts can't exceed the free funds of the position. Use `settle_funds` to also",
+        'claim referrer rebates. Outstanding event heap penalties are collected from the',
+        '`penalty_payer` like in `settle_funds`.',
+      ];
       accounts: [
         {
           name: 'owner';
-          isMut: true;
+          isMut: false;
           isSigner: true;
         },
         {
@@ -1409,12 +1865,6 @@ export type OpenbookV2 = {
           isMut: true;
           isSigner: false;
         },
-        {
-          name: 'referrerAccount';
-          isMut: true;
-          isSigner: false;
-          isOptional: true;
-        },
         {
           name: 'tokenProgram';
           isMut: false;
@@ -1426,8 +1876,125 @@ export type OpenbookV2 = {
           isSigner: false;
         },
       ];
-      args: [];
-    },
+      args: [
+        {
+          name: 'baseAmount';
+          type: 'u64';
+        },
+        {
+          name: 'quoteAmount';
+          type: 'u64';
+        },
+      ];
+    },
+    {
+      name: 'settleFunds';
+      docs: ['Withdraw any available tokens.'];
+      accounts: [
+        {
+          name: 'owner';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'penaltyPayer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketAuthority';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'marketBaseVault';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'marketQuoteVault';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'userBaseAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'userQuoteAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'referrerAccount';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'tokenProgram';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'settleFundsBatch';
+      docs: [
+        'Withdraw any available tokens of several open orders accounts across markets.',
+        '',
+        "Every open orders account must be listed in the owner's",
+        '[`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) and is passed as a group of',
+        '[`SETTLE_FUNDS_BATCH_GROUP_LEN`](crate::accounts_ix::SETTLE_FUNDS_BATCH_GROUP_LEN)',
+        'remaining accounts: the open orders account, its market, the market authority, the base',
+        'and quote vaults and the base and quote token accounts receiving the funds.',
+      ];
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'penaltyPayer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersIndexer';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'tokenProgram';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
     {
       name: 'settleFundsExpired';
       docs: [
@@ -1542,60 +2109,93 @@ export type OpenbookV2 = {
       args: [];
     },
     {
-      name: 'setDelegate';
+      name: 'setCrankReward';
       docs: [
-        'Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.',
+        'Set the [`crank_reward`](crate::state::Market::crank_reward) paid for every processed',
+        "event, as the market's collect fee admin. It can't exceed the event heap penalty.",
       ];
       accounts: [
         {
-          name: 'owner';
-          isMut: true;
+          name: 'collectFeeAdmin';
+          isMut: false;
           isSigner: true;
         },
         {
-          name: 'openOrdersAccount';
+          name: 'market';
           isMut: true;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: 'delegateAccount';
-          isMut: false;
-          isSigner: false;
-          isOptional: true;
+          name: 'crankReward';
+          type: 'u64';
         },
       ];
-      args: [];
     },
     {
-      name: 'setMarketExpired';
+      name: 'transferOpenOrdersAccount';
       docs: [
-        'Set market to expired before pruning orders and closing the market (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        'Move an open orders account, including its resting orders, to a new owner.',
+        '',
+        'Both owners sign, the account moves between their',
+        "[`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer)s. The new owner's indexer is",
+        "created, paid by the `payer`, if it doesn't exist yet. All delegates, including session",
+        'delegates, are removed and the [`stp_group`](crate::state::OpenOrdersAccount::stp_group)',
+        'is reset to none. The balances, auto settle, risk limits and heartbeat timeout carry over',
+        'and can be changed by the new owner.',
       ];
       accounts: [
         {
-          name: 'closeMarketAdmin';
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'owner';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'newOwner';
           isMut: false;
           isSigner: true;
         },
         {
-          name: 'market';
+          name: 'openOrdersIndexer';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'newOpenOrdersIndexer';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'openOrdersAccount';
           isMut: true;
           isSigner: false;
         },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
       ];
       args: [];
     },
     {
-      name: 'pruneOrders';
+      name: 'setDelegate';
       docs: [
-        'Remove orders from the book when the market is expired (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        'Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.',
+        '',
+        'A new delegate starts with all permissions and no order limit. Changing or removing the',
+        'delegate revokes all its session delegates.',
       ];
       accounts: [
         {
-          name: 'closeMarketAdmin';
-          isMut: false;
+          name: 'owner';
+          isMut: true;
           isSigner: true;
         },
         {
@@ -1604,30 +2204,58 @@ export type OpenbookV2 = {
           isSigner: false;
         },
         {
-          name: 'market';
+          name: 'delegateAccount';
           isMut: false;
           isSigner: false;
+          isOptional: true;
         },
+      ];
+      args: [];
+    },
+    {
+      name: 'setDelegatePermissions';
+      docs: [
+        'Restrict the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders',
+        'account.',
+        '',
+        '`permissions` is a bitmask of the `DELEGATE_*` actions the delegate may take, 0 for none',
+        'and `DELEGATE_ALL_PERMISSIONS` for all of them. A non-zero `max_order_quote_native`',
+        "limits the value of the delegate's orders. Both are reset when the delegate changes.",
+      ];
+      accounts: [
         {
-          name: 'bids';
-          isMut: true;
-          isSigner: false;
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
         },
         {
-          name: 'asks';
+          name: 'openOrdersAccount';
           isMut: true;
           isSigner: false;
         },
       ];
       args: [
         {
-          name: 'limit';
+          name: 'permissions';
           type: 'u8';
         },
+        {
+          name: 'maxOrderQuoteNative';
+          type: 'u64';
+        },
       ];
     },
     {
-      name: 'stubOracleCreate';
+      name: 'setSessionDelegate';
+      docs: [
+        'Add, renew or revoke a session delegate of an open orders account, as the owner or a',
+        '[`delegate`](crate::state::OpenOrdersAccount::delegate) with all permissions.',
+        '',
+        'Session delegates have the permissions of the delegate until `expiry_timestamp`, which',
+        'can be at most [`MAX_SESSION_LIFETIME_SECONDS`](crate::state::MAX_SESSION_LIFETIME_SECONDS)',
+        'ahead, 0 revokes the key. They lose their authority when the delegate changes or is',
+        'removed. The account is grown by a slot if needed, paid by the `payer`.',
+      ];
       accounts: [
         {
           name: 'payer';
@@ -1635,20 +2263,15 @@ export type OpenbookV2 = {
           isSigner: true;
         },
         {
-          name: 'owner';
+          name: 'authority';
           isMut: false;
           isSigner: true;
         },
         {
-          name: 'oracle';
+          name: 'openOrdersAccount';
           isMut: true;
           isSigner: false;
         },
-        {
-          name: 'mint';
-          isMut: false;
-          isSigner: false;
-        },
         {
           name: 'systemProgram';
           isMut: false;
@@ -1657,13 +2280,24 @@ export type OpenbookV2 = {
       ];
       args: [
         {
-          name: 'price';
-          type: 'f64';
+          name: 'sessionKey';
+          type: 'publicKey';
+        },
+        {
+          name: 'expiryTimestamp';
+          type: 'u64';
         },
       ];
     },
     {
-      name: 'stubOracleClose';
+      name: 'setStpGroup';
+      docs: [
+        'Update the [`stp_group`](crate::state::OpenOrdersAccount::stp_group) of an open orders',
+        'account. Use 0 to leave the group.',
+        '',
+        'Orders of the other accounts in the group are only recognized when those accounts are',
+        'passed as remaining accounts of the taking instruction, otherwise they match normally.',
+      ];
       accounts: [
         {
           name: 'owner';
@@ -1671,25 +2305,52 @@ export type OpenbookV2 = {
           isSigner: true;
         },
         {
-          name: 'oracle';
+          name: 'openOrdersAccount';
           isMut: true;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: 'solDestination';
-          isMut: true;
-          isSigner: false;
+          name: 'stpGroup';
+          type: 'u16';
         },
+      ];
+    },
+    {
+      name: 'setAutoSettle';
+      docs: [
+        'Opt in or out of having maker fills of an open orders account transferred directly to the',
+        "owner's associated token accounts during [`consume_events`](crate::openbook_v2::consume_events).",
+      ];
+      accounts: [
         {
-          name: 'tokenProgram';
+          name: 'owner';
           isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
           isSigner: false;
         },
       ];
-      args: [];
+      args: [
+        {
+          name: 'enabled';
+          type: 'bool';
+        },
+      ];
     },
     {
-      name: 'stubOracleSet';
+      name: 'setHeartbeatTimeout';
+      docs: [
+        'Let anyone cancel the orders of an open orders account once no heartbeat was recorded',
+        'for `timeout_seconds`, 0 disables it.',
+        '',
+        'Placing, cancelling, settling and withdrawing record heartbeats, as does',
+        '[`heartbeat`](crate::openbook_v2::heartbeat).',
+      ];
       accounts: [
         {
           name: 'owner';
@@ -1697,27 +2358,442 @@ export type OpenbookV2 = {
           isSigner: true;
         },
         {
-          name: 'oracle';
+          name: 'openOrdersAccount';
           isMut: true;
           isSigner: false;
         },
       ];
       args: [
         {
-          name: 'price';
-          type: 'f64';
+          name: 'timeoutSeconds';
+          type: 'u16';
         },
       ];
     },
-  ];
-  accounts: [
     {
-      name: 'market';
-      type: {
-        kind: 'struct';
-        fields: [
-          {
-            name: 'bump';
+      name: 'heartbeat';
+      docs: [
+        'Record a heartbeat of the owner or a delegate, keeping the orders of the account on the',
+        'book for another heartbeat timeout.',
+      ];
+      accounts: [
+        {
+          name: 'signer';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'setRiskLimits';
+      docs: [
+        "Set the owner's risk limits of an open orders account, enforced whenever an order is",
+        'placed. A limit of 0 disables it.',
+        '',
+        '`max_taker_quote_native_per_window` caps the taker volume within windows of',
+        "`window_seconds`. The account is grown to store the limits the first time they're set,",
+        'paid by the `payer`.',
+      ];
+      accounts: [
+        {
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'maxBaseLotsPerSide';
+          type: 'i64';
+        },
+        {
+          name: 'maxOrderQuoteNative';
+          type: 'u64';
+        },
+        {
+          name: 'maxTakerQuoteNativePerWindow';
+          type: 'u64';
+        },
+        {
+          name: 'windowSeconds';
+          type: 'u32';
+        },
+      ];
+    },
+    {
+      name: 'setMarketExpired';
+      docs: [
+        'Set market to expired before pruning orders and closing the market (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ];
+      accounts: [
+        {
+          name: 'closeMarketAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'pruneOrders';
+      docs: [
+        'Remove orders from the book when the market is expired (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ];
+      accounts: [
+        {
+          name: 'closeMarketAdmin';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'openOrdersAccount';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u8';
+        },
+      ];
+    },
+    {
+      name: 'pruneExpiredOrders';
+      docs: [
+        'Remove up to `limit` expired orders from the book, earliest expiry first on each side.',
+        '',
+        'Makers whose [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) is passed as a',
+        'remaining account are refunded right away. The other makers are refunded through an',
+        '[`OutEvent`](crate::state::OutEvent), for which the `penalty_payer` pays the event heap',
+        'penalty like a taker would. Pruning stops at the first order that needs an event once the',
+        'heap is full.',
+        '',
+        "The `crank_reward_receiver` is paid the market's",
+        '[`crank_reward`](crate::state::Market::crank_reward) for every removed order.',
+      ];
+      accounts: [
+        {
+          name: 'penaltyPayer';
+          isMut: true;
+          isSigner: true;
+          docs: [
+            'Pays the event heap penalty for the orders refunded through the event heap',
+          ];
+        },
+        {
+          name: 'market';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'eventHeap';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'crankRewardReceiver';
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'limit';
+          type: 'u8';
+        },
+      ];
+    },
+    {
+      name: 'quoteTakeOrder';
+      docs: [
+        'Match a take order against the book without changing it and return the expected',
+        '[`TakeQuote`](crate::state::TakeQuote) through the return data.',
+        '',
+        "Fails like `place_take_order` would, e.g. for fill or kill orders that can't be filled",
+        "or when the event heap is full. Pass the makers' open orders accounts as remaining",
+        'accounts to quote with the same accounts as the take order.',
+      ];
+      accounts: [
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'eventHeap';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'oracleA';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'oracleB';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [
+        {
+          name: 'args';
+          type: {
+            defined: 'PlaceTakeOrderArgs';
+          };
+        },
+      ];
+    },
+    {
+      name: 'getL2Depth';
+      docs: [
+        'Return the aggregated price levels of the book as [`L2Depth`] through the return data.',
+        '',
+        'Up to `max_levels` levels per side, best price first. Expired orders are skipped and',
+        'oracle pegged orders are resolved at the current oracle price, or skipped when the',
+        'oracle is not usable.',
+      ];
+      accounts: [
+        {
+          name: 'market';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'bids';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'asks';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'oracleA';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: 'oracleB';
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [
+        {
+          name: 'maxLevels';
+          type: 'u8';
+        },
+      ];
+    },
+    {
+      name: 'stubOracleCreate';
+      accounts: [
+        {
+          name: 'payer';
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'oracle';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'mint';
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: 'systemProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'price';
+          type: 'f64';
+        },
+      ];
+    },
+    {
+      name: 'stubOracleClose';
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'oracle';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'solDestination';
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: 'tokenProgram';
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: 'stubOracleSet';
+      docs: [
+        'Set the price of a stub oracle, last updated at the current slot and time with no',
+        'deviation.',
+      ];
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'oracle';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'price';
+          type: 'f64';
+        },
+      ];
+    },
+    {
+      name: 'stubOracleSetTest';
+      docs: [
+        'Set every field of a stub oracle read by the price checks, for tests of stale',
+        '(`last_update_slot`, `last_update_ts`) and low confidence (`deviation`) oracles.',
+      ];
+      accounts: [
+        {
+          name: 'owner';
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: 'oracle';
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: 'price';
+          type: 'f64';
+        },
+        {
+          name: 'lastUpdateSlot';
+          type: 'u64';
+        },
+        {
+          name: 'lastUpdateTs';
+          type: 'i64';
+        },
+        {
+          name: 'deviation';
+          type: 'f64';
+        },
+      ];
+    },
+  ];
+  accounts: [
+    {
+      name: 'market';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bump';
             docs: ['PDA bump'];
             type: 'u8';
           },
@@ -1810,6 +2886,11 @@ export type OpenbookV2 = {
           },
           {
             name: 'oracleB';
+            docs: [
+              'Divides the price of oracle_a, e.g. BTC/USD by USDC/USD for a BTC/USDC market. With',
+              '`oracle_config.fallback_to_oracle_b` it is instead a price of the base in quote on its own,',
+              'only used when oracle_a is stale or fails the confidence filter.',
+            ];
             type: {
               defined: 'NonZeroPubkeyOption';
             };
@@ -1927,10 +3008,49 @@ export type OpenbookV2 = {
             name: 'quoteDepositTotal';
             type: 'u64';
           },
+          {
+            name: 'penaltyEventHeap';
+            docs: [
+              'Lamports a taker pays for every event it adds to the event heap. 0 stands for',
+              '`PENALTY_EVENT_HEAP`, as on markets created before this was configurable.',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'crankReward';
+            docs: [
+              'Lamports paid out of the penalty pool for every event processed by consume_events,',
+              'at most the heap penalty per event',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'penaltyPool';
+            docs: [
+              "Penalty lamports held by the market account that weren't paid out as crank rewards yet",
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'oracleAFeedId';
+            docs: [
+              'Pyth pull feeds the price updates of oracle_a and oracle_b must be for, zeroed for',
+              'other oracle types',
+            ];
+            type: {
+              array: ['u8', 32];
+            };
+          },
+          {
+            name: 'oracleBFeedId';
+            type: {
+              array: ['u8', 32];
+            };
+          },
           {
             name: 'reserved';
             type: {
-              array: ['u8', 128];
+              array: ['u8', 40];
             };
           },
         ];
@@ -1974,10 +3094,12 @@ export type OpenbookV2 = {
             type: 'u8';
           },
           {
-            name: 'padding';
-            type: {
-              array: ['u8', 2];
-            };
+            name: 'stpGroup';
+            docs: [
+              'Self-trade prevention group, 0 means none. Orders of accounts of the same owner',
+              "sharing a group are subject to the taker's SelfTradeBehavior.",
+            ];
+            type: 'u16';
           },
           {
             name: 'position';
@@ -2018,6 +3140,15 @@ export type OpenbookV2 = {
               vec: 'publicKey';
             };
           },
+          {
+            name: 'markets';
+            docs: [
+              'Market of the open orders account at the same index of `addresses`',
+            ];
+            type: {
+              defined: 'IndexedMarkets';
+            };
+          },
         ];
       };
     },
@@ -2076,223 +3207,508 @@ export type OpenbookV2 = {
             };
           },
           {
-            name: 'reservedRoots';
-            type: {
-              array: [
-                {
-                  defined: 'OrderTreeRoot';
-                },
-                4,
-              ];
-            };
+            name: 'reservedRoots';
+            type: {
+              array: [
+                {
+                  defined: 'OrderTreeRoot';
+                },
+                4,
+              ];
+            };
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 256];
+            };
+          },
+          {
+            name: 'nodes';
+            type: {
+              defined: 'OrderTreeNodes';
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'eventHeap';
+      docs: [
+        'Container for the different EventTypes.',
+        '',
+        'Events are stored in an array of nodes. Free nodes are connected by a single-linked list',
+        'starting at free_head while used nodes form a circular doubly-linked list starting at',
+        'used_head.',
+        '',
+        'This is the layout at the default capacity of `MAX_NUM_EVENTS` nodes. Accounts can be',
+        'created with other capacities and are accessed through `EventHeapView`.',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'header';
+            type: {
+              defined: 'EventHeapHeader';
+            };
+          },
+          {
+            name: 'nodes';
+            type: {
+              array: [
+                {
+                  defined: 'EventNode';
+                },
+                600,
+              ];
+            };
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 64];
+            };
+          },
+        ];
+      };
+    },
+  ];
+  types: [
+    {
+      name: 'NonZeroPubkeyOption';
+      docs: [
+        'Like `Option`, but implemented for `Pubkey` to be used with `zero_copy`',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'key';
+            type: 'publicKey';
+          },
+        ];
+      };
+    },
+    {
+      name: 'Position';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bidsBaseLots';
+            docs: ['Base lots in open bids'];
+            type: 'i64';
+          },
+          {
+            name: 'asksBaseLots';
+            docs: ['Base lots in open asks'];
+            type: 'i64';
+          },
+          {
+            name: 'baseFreeNative';
+            type: 'u64';
+          },
+          {
+            name: 'quoteFreeNative';
+            type: 'u64';
+          },
+          {
+            name: 'lockedMakerFees';
+            type: 'u64';
+          },
+          {
+            name: 'referrerRebatesAvailable';
+            type: 'u64';
+          },
+          {
+            name: 'penaltyHeapCount';
+            docs: [
+              "Count of events added to the heap, each paying the market's heap penalty on settlement",
+              'To avoid this, send remaining accounts in order to process the events',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'makerVolume';
+            docs: [
+              'Cumulative maker volume in quote native units (display only)',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'takerVolume';
+            docs: [
+              'Cumulative taker volume in quote native units (display only)',
+            ];
+            type: 'u128';
+          },
+          {
+            name: 'bidsQuoteLots';
+            docs: ['Quote lots in open bids'];
+            type: 'i64';
+          },
+          {
+            name: 'autoSettle';
+            docs: [
+              "Opt-in to have the funds credited by a fill transferred to the owner's associated token",
+              'accounts right when the fill is executed, if the crank or taker provides them. 0 means',
+              'disabled.',
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'delegateRevokedPermissions';
+            docs: [
+              'Bitmask of the `DELEGATE_*` actions the delegate may not take. Stored inverted so',
+              'delegates set before permissions existed keep all of them.',
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'sessionDelegateSlots';
+            docs: [
+              'Number of session delegate slots stored at the end of the account',
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'riskLimitsSlots';
+            docs: [
+              'Number of [`RiskLimits`] stored before the session delegates, 0 or 1',
+            ];
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 2];
+            };
+          },
+          {
+            name: 'heartbeatTimeoutSeconds';
+            docs: [
+              'Orders can be cancelled by anyone once no heartbeat was recorded for this long.',
+              '0 means disabled.',
+            ];
+            type: 'u16';
+          },
+          {
+            name: 'delegateMaxOrderQuoteNative';
+            docs: [
+              'Largest quote native value, taken plus posted, of an order placed by the delegate.',
+              '0 means unlimited.',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'heartbeatTimestamp';
+            docs: ['Last time the owner or a delegate used the account'];
+            type: 'u64';
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 40];
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: 'OpenOrder';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'id';
+            type: 'u128';
+          },
+          {
+            name: 'clientId';
+            type: 'u64';
+          },
+          {
+            name: 'lockedPrice';
+            docs: ["Price at which user's assets were locked"];
+            type: 'i64';
+          },
+          {
+            name: 'isFree';
+            type: 'u8';
+          },
+          {
+            name: 'sideAndTree';
+            type: 'u8';
           },
           {
-            name: 'reserved';
+            name: 'padding';
             type: {
-              array: ['u8', 256];
+              array: ['u8', 6];
             };
           },
+        ];
+      };
+    },
+    {
+      name: 'SessionDelegate';
+      docs: [
+        'Short-lived delegate, e.g. a hot key of a trading bot, with the permissions of the delegate',
+      ];
+      type: {
+        kind: 'struct';
+        fields: [
           {
-            name: 'nodes';
-            type: {
-              defined: 'OrderTreeNodes';
-            };
+            name: 'key';
+            type: 'publicKey';
+          },
+          {
+            name: 'expiryTimestamp';
+            docs: [
+              'Unix timestamp from which on the key has no authority anymore',
+            ];
+            type: 'u64';
           },
         ];
       };
     },
     {
-      name: 'eventHeap';
+      name: 'RiskLimits';
       docs: [
-        'Container for the different EventTypes.',
-        '',
-        'Events are stored in a fixed-array of nodes. Free nodes are connected by a single-linked list',
-        'starting at free_head while used nodes form a circular doubly-linked list starting at',
-        'used_head.',
+        'Owner-set limits on the orders of an account, stored after the open order slots once set',
       ];
       type: {
         kind: 'struct';
         fields: [
           {
-            name: 'header';
-            type: {
-              defined: 'EventHeapHeader';
-            };
+            name: 'maxBaseLotsPerSide';
+            docs: [
+              'Cap on base lots in open orders on each side. 0 means unlimited.',
+            ];
+            type: 'i64';
           },
           {
-            name: 'nodes';
+            name: 'maxOrderQuoteNative';
+            docs: [
+              'Cap on the quote native value, taken plus posted, of a single order. 0 means unlimited.',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'maxTakerQuoteNativePerWindow';
+            docs: [
+              'Cap on quote native taker volume per window. 0 means unlimited.',
+            ];
+            type: 'u64';
+          },
+          {
+            name: 'windowSeconds';
+            docs: [
+              'Length of the window `max_taker_quote_native_per_window` applies to',
+            ];
+            type: 'u32';
+          },
+          {
+            name: 'padding';
             type: {
-              array: [
-                {
-                  defined: 'EventNode';
-                },
-                600,
-              ];
+              array: ['u8', 4];
             };
           },
+          {
+            name: 'windowStartTimestamp';
+            docs: ['Start of the current window'];
+            type: 'u64';
+          },
+          {
+            name: 'windowTakerQuoteNative';
+            docs: [
+              'Taker volume in quote native units since the start of the current window',
+            ];
+            type: 'u64';
+          },
           {
             name: 'reserved';
             type: {
-              array: ['u8', 64];
+              array: ['u8', 8];
             };
           },
         ];
       };
     },
-  ];
-  types: [
     {
-      name: 'NonZeroPubkeyOption';
+      name: 'IndexedMarkets';
       docs: [
-        'Like `Option`, but implemented for `Pubkey` to be used with `zero_copy`',
+        'Trailing section of [`OpenOrdersIndexer`], missing from indexers created before markets',
+        'were recorded.',
+        '',
+        'Such indexers hold no markets until the next open orders account is added, after which the',
+        'markets of the older accounts are the default pubkey until `migrate_open_orders_indexer`',
+        'backfills them.',
       ];
       type: {
         kind: 'struct';
         fields: [
           {
-            name: 'key';
-            type: 'publicKey';
+            name: 'keys';
+            type: {
+              vec: 'publicKey';
+            };
           },
         ];
       };
     },
     {
-      name: 'Position';
+      name: 'OracleConfig';
       type: {
         kind: 'struct';
         fields: [
           {
-            name: 'bidsBaseLots';
-            docs: ['Base lots in open bids'];
-            type: 'i64';
+            name: 'confFilter';
+            type: 'f64';
           },
           {
-            name: 'asksBaseLots';
-            docs: ['Base lots in open asks'];
+            name: 'maxStalenessSlots';
             type: 'i64';
           },
           {
-            name: 'baseFreeNative';
-            type: 'u64';
-          },
-          {
-            name: 'quoteFreeNative';
-            type: 'u64';
-          },
-          {
-            name: 'lockedMakerFees';
-            type: 'u64';
-          },
-          {
-            name: 'referrerRebatesAvailable';
+            name: 'maxStalenessSeconds';
+            docs: [
+              "Maximum age of the oracle's publish time, 0 disables the check. Markets created before",
+              'the field existed have it zeroed.',
+            ];
             type: 'u64';
           },
           {
-            name: 'penaltyHeapCount';
+            name: 'minLiquidity';
             docs: [
-              'Count of ixs when events are added to the heap',
-              'To avoid this, send remaining accounts in order to process the events',
+              'Minimum in range liquidity of AMM pool oracles, 0 disables the check',
             ];
             type: 'u64';
           },
           {
-            name: 'makerVolume';
+            name: 'useEmaPrice';
             docs: [
-              'Cumulative maker volume in quote native units (display only)',
+              "Use the oracle's EMA price instead of its spot price when 1",
             ];
-            type: 'u128';
+            type: 'u8';
           },
           {
-            name: 'takerVolume';
+            name: 'fallbackToOracleB';
             docs: [
-              'Cumulative taker volume in quote native units (display only)',
+              'Use oracle_b on its own when oracle_a is invalid instead of dividing by it when 1',
             ];
-            type: 'u128';
-          },
-          {
-            name: 'bidsQuoteLots';
-            docs: ['Quote lots in open bids'];
-            type: 'i64';
+            type: 'u8';
           },
           {
             name: 'reserved';
             type: {
-              array: ['u8', 64];
+              array: ['u8', 54];
             };
           },
         ];
       };
     },
     {
-      name: 'OpenOrder';
+      name: 'OracleConfigParams';
       type: {
         kind: 'struct';
         fields: [
           {
-            name: 'id';
-            type: 'u128';
+            name: 'confFilter';
+            type: 'f32';
           },
           {
-            name: 'clientId';
-            type: 'u64';
+            name: 'maxStalenessSlots';
+            type: {
+              option: 'u32';
+            };
           },
           {
-            name: 'lockedPrice';
-            docs: ["Price at which user's assets were locked"];
-            type: 'i64';
+            name: 'maxStalenessSeconds';
+            type: {
+              option: 'u32';
+            };
           },
           {
-            name: 'isFree';
-            type: 'u8';
+            name: 'minLiquidity';
+            type: {
+              option: 'u64';
+            };
           },
           {
-            name: 'sideAndTree';
-            type: 'u8';
+            name: 'useEmaPrice';
+            type: 'bool';
           },
           {
-            name: 'padding';
-            type: {
-              array: ['u8', 6];
-            };
+            name: 'fallbackToOracleB';
+            type: 'bool';
           },
         ];
       };
     },
     {
-      name: 'OracleConfig';
+      name: 'TakeQuote';
+      docs: ['Expected outcome of a take order, see `Orderbook::quote_order`'];
       type: {
         kind: 'struct';
         fields: [
           {
-            name: 'confFilter';
-            type: 'f64';
+            name: 'totalBaseTakenNative';
+            type: 'u64';
           },
           {
-            name: 'maxStalenessSlots';
-            type: 'i64';
+            name: 'totalQuoteTakenNative';
+            type: 'u64';
           },
           {
-            name: 'reserved';
-            type: {
-              array: ['u8', 72];
-            };
+            name: 'takerFeesNative';
+            type: 'u64';
+          },
+          {
+            name: 'notEnoughLiquidity';
+            type: 'bool';
           },
         ];
       };
     },
     {
-      name: 'OracleConfigParams';
+      name: 'BookSideHeader';
+      docs: ['The fields of a BookSide account in front of its nodes'];
       type: {
         kind: 'struct';
         fields: [
           {
-            name: 'confFilter';
-            type: 'f32';
+            name: 'roots';
+            type: {
+              array: [
+                {
+                  defined: 'OrderTreeRoot';
+                },
+                2,
+              ];
+            };
           },
           {
-            name: 'maxStalenessSlots';
+            name: 'reservedRoots';
             type: {
-              option: 'u32';
+              array: [
+                {
+                  defined: 'OrderTreeRoot';
+                },
+                4,
+              ];
+            };
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 256];
+            };
+          },
+          {
+            name: 'orderTree';
+            type: {
+              defined: 'OrderTreeHeader';
             };
           },
         ];
@@ -2393,10 +3809,14 @@ export type OpenbookV2 = {
             name: 'makerSlot';
             type: 'u8';
           },
+          {
+            name: 'makerSlotHi';
+            type: 'u8';
+          },
           {
             name: 'padding';
             type: {
-              array: ['u8', 4];
+              array: ['u8', 3];
             };
           },
           {
@@ -2404,7 +3824,7 @@ export type OpenbookV2 = {
             type: 'u64';
           },
           {
-            name: 'seqNum';
+            name: 'marketSeqNum';
             type: 'u64';
           },
           {
@@ -2465,10 +3885,14 @@ export type OpenbookV2 = {
             name: 'ownerSlot';
             type: 'u8';
           },
+          {
+            name: 'ownerSlotHi';
+            type: 'u8';
+          },
           {
             name: 'padding0';
             type: {
-              array: ['u8', 5];
+              array: ['u8', 4];
             };
           },
           {
@@ -2572,7 +3996,9 @@ export type OpenbookV2 = {
           },
           {
             name: 'ownerSlot';
-            docs: ["Index into the owning OpenOrdersAccount's OpenOrders"];
+            docs: [
+              "Index into the owning OpenOrdersAccount's OpenOrders, low byte",
+            ];
             type: 'u8';
           },
           {
@@ -2583,10 +4009,18 @@ export type OpenbookV2 = {
             ];
             type: 'u16';
           },
+          {
+            name: 'ownerSlotHi';
+            docs: [
+              "High byte of the index into the owning OpenOrdersAccount's OpenOrders,",
+              'see owner_slot_index()',
+            ];
+            type: 'u8';
+          },
           {
             name: 'padding';
             type: {
-              array: ['u8', 4];
+              array: ['u8', 3];
             };
           },
           {
@@ -2664,9 +4098,9 @@ export type OpenbookV2 = {
     {
       name: 'OrderTreeNodes';
       docs: [
-        'A binary tree on AnyNode::key()',
+        'Layout of the nodes of a BookSide at the default capacity',
         '',
-        'The key encodes the price in the top 64 bits.',
+        'BookSide accounts can hold any number of nodes, see OrderTreeHeader and OrderTreeRef.',
       ];
       type: {
         kind: 'struct';
@@ -2713,6 +4147,43 @@ export type OpenbookV2 = {
         ];
       };
     },
+    {
+      name: 'OrderTreeHeader';
+      docs: ['The fields of OrderTreeNodes in front of the nodes'];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'orderTreeType';
+            type: 'u8';
+          },
+          {
+            name: 'padding';
+            type: {
+              array: ['u8', 3];
+            };
+          },
+          {
+            name: 'bumpIndex';
+            type: 'u32';
+          },
+          {
+            name: 'freeListLen';
+            type: 'u32';
+          },
+          {
+            name: 'freeListHead';
+            type: 'u32';
+          },
+          {
+            name: 'reserved';
+            type: {
+              array: ['u8', 512];
+            };
+          },
+        ];
+      };
+    },
     {
       name: 'I80F48';
       docs: [
@@ -2889,6 +4360,93 @@ export type OpenbookV2 = {
         ];
       };
     },
+    {
+      name: 'PlaceTakeOrderExactQuoteInArgs';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'priceLots';
+            type: 'i64';
+          },
+          {
+            name: 'quoteAmountNative';
+            type: 'u64';
+          },
+          {
+            name: 'orderType';
+            type: {
+              defined: 'PlaceOrderType';
+            };
+          },
+          {
+            name: 'limit';
+            type: 'u8';
+          },
+        ];
+      };
+    },
+    {
+      name: 'PlaceTakeOrderTwoHopArgs';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'amountInNative';
+            type: 'u64';
+          },
+          {
+            name: 'minAmountOutNative';
+            type: 'u64';
+          },
+          {
+            name: 'limit';
+            type: 'u8';
+          },
+        ];
+      };
+    },
+    {
+      name: 'L2Level';
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'priceLots';
+            type: 'i64';
+          },
+          {
+            name: 'baseLots';
+            type: 'i64';
+          },
+        ];
+      };
+    },
+    {
+      name: 'L2Depth';
+      docs: ['Return data of `get_l2_depth`'];
+      type: {
+        kind: 'struct';
+        fields: [
+          {
+            name: 'bids';
+            type: {
+              vec: {
+                defined: 'L2Level';
+              };
+            };
+          },
+          {
+            name: 'asks';
+            type: {
+              vec: {
+                defined: 'L2Level';
+              };
+            };
+          },
+        ];
+      };
+    },
     {
       name: 'OracleType';
       type: {
@@ -2909,6 +4467,15 @@ export type OpenbookV2 = {
           {
             name: 'RaydiumCLMM';
           },
+          {
+            name: 'PythPull';
+          },
+          {
+            name: 'SwitchboardOnDemand';
+          },
+          {
+            name: 'OrcaWhirlpool';
+          },
         ];
       };
     },
@@ -3026,7 +4593,8 @@ export type OpenbookV2 = {
     {
       name: 'SelfTradeBehavior';
       docs: [
-        'Self trade behavior controls how taker orders interact with resting limit orders of the same account.',
+        'Self trade behavior controls how taker orders interact with resting limit orders of the same account,',
+        'or of another account of the same owner sharing a non-zero `stp_group`.',
         'This setting has no influence on placing a resting or oracle pegged limit order that does not match',
         "immediately, instead it's the responsibility of the user to correctly configure his taker orders.",
       ];
@@ -3042,6 +4610,12 @@ export type OpenbookV2 = {
           {
             name: 'AbortTransaction';
           },
+          {
+            name: 'CancelTake';
+          },
+          {
+            name: 'CancelBoth';
+          },
         ];
       };
     },
@@ -3186,6 +4760,31 @@ export type OpenbookV2 = {
         },
       ];
     },
+    {
+      name: 'WithdrawLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'signer';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'baseAmount';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'quoteAmount';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
     {
       name: 'FillLog';
       fields: [
@@ -3201,7 +4800,7 @@ export type OpenbookV2 = {
         },
         {
           name: 'makerSlot';
-          type: 'u8';
+          type: 'u16';
           index: false;
         },
         {
@@ -3220,129 +4819,291 @@ export type OpenbookV2 = {
           index: false;
         },
         {
-          name: 'maker';
+          name: 'maker';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'makerClientOrderId';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'makerFee';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'makerTimestamp';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'taker';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'takerClientOrderId';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'takerFeeCeil';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'price';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'quantity';
+          type: 'i64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'MarketMetaDataLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'name';
+          type: 'string';
+          index: false;
+        },
+        {
+          name: 'baseMint';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'quoteMint';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'baseDecimals';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'quoteDecimals';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'baseLotSize';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'quoteLotSize';
+          type: 'i64';
+          index: false;
+        },
+        {
+          name: 'useEmaPrice';
+          type: 'bool';
+          index: false;
+        },
+        {
+          name: 'fallbackToOracleB';
+          type: 'bool';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'TotalOrderFillEvent';
+      fields: [
+        {
+          name: 'side';
+          type: 'u8';
+          index: false;
+        },
+        {
+          name: 'taker';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'totalQuantityPaid';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'totalQuantityReceived';
+          type: 'u64';
+          index: false;
+        },
+        {
+          name: 'fees';
+          type: 'u64';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SetDelegateLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'delegate';
+          type: {
+            option: 'publicKey';
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SetDelegatePermissionsLog';
+      fields: [
+        {
+          name: 'openOrdersAccount';
           type: 'publicKey';
           index: false;
         },
         {
-          name: 'makerClientOrderId';
-          type: 'u64';
+          name: 'permissions';
+          type: 'u8';
           index: false;
         },
         {
-          name: 'makerFee';
+          name: 'maxOrderQuoteNative';
           type: 'u64';
           index: false;
         },
+      ];
+    },
+    {
+      name: 'TransferOpenOrdersAccountLog';
+      fields: [
         {
-          name: 'makerTimestamp';
-          type: 'u64';
+          name: 'openOrdersAccount';
+          type: 'publicKey';
           index: false;
         },
         {
-          name: 'taker';
+          name: 'owner';
           type: 'publicKey';
           index: false;
         },
         {
-          name: 'takerClientOrderId';
-          type: 'u64';
+          name: 'newOwner';
+          type: 'publicKey';
           index: false;
         },
+      ];
+    },
+    {
+      name: 'SetSessionDelegateLog';
+      fields: [
         {
-          name: 'takerFeeCeil';
-          type: 'u64';
+          name: 'openOrdersAccount';
+          type: 'publicKey';
           index: false;
         },
         {
-          name: 'price';
-          type: 'i64';
+          name: 'sessionKey';
+          type: 'publicKey';
           index: false;
         },
         {
-          name: 'quantity';
-          type: 'i64';
+          name: 'expiryTimestamp';
+          type: 'u64';
           index: false;
         },
       ];
     },
     {
-      name: 'MarketMetaDataLog';
+      name: 'SetAutoSettleLog';
       fields: [
         {
-          name: 'market';
+          name: 'openOrdersAccount';
           type: 'publicKey';
           index: false;
         },
         {
-          name: 'name';
-          type: 'string';
-          index: false;
-        },
-        {
-          name: 'baseMint';
-          type: 'publicKey';
+          name: 'enabled';
+          type: 'bool';
           index: false;
         },
+      ];
+    },
+    {
+      name: 'SetHeartbeatTimeoutLog';
+      fields: [
         {
-          name: 'quoteMint';
+          name: 'openOrdersAccount';
           type: 'publicKey';
           index: false;
         },
         {
-          name: 'baseDecimals';
-          type: 'u8';
-          index: false;
-        },
-        {
-          name: 'quoteDecimals';
-          type: 'u8';
+          name: 'timeoutSeconds';
+          type: 'u16';
           index: false;
         },
+      ];
+    },
+    {
+      name: 'CancelOnMissedHeartbeatLog';
+      fields: [
         {
-          name: 'baseLotSize';
-          type: 'i64';
+          name: 'openOrdersAccount';
+          type: 'publicKey';
           index: false;
         },
         {
-          name: 'quoteLotSize';
-          type: 'i64';
+          name: 'heartbeatTimestamp';
+          type: 'u64';
           index: false;
         },
       ];
     },
     {
-      name: 'TotalOrderFillEvent';
+      name: 'SetRiskLimitsLog';
       fields: [
         {
-          name: 'side';
-          type: 'u8';
+          name: 'openOrdersAccount';
+          type: 'publicKey';
           index: false;
         },
         {
-          name: 'taker';
-          type: 'publicKey';
+          name: 'maxBaseLotsPerSide';
+          type: 'i64';
           index: false;
         },
         {
-          name: 'totalQuantityPaid';
+          name: 'maxOrderQuoteNative';
           type: 'u64';
           index: false;
         },
         {
-          name: 'totalQuantityReceived';
+          name: 'maxTakerQuoteNativePerWindow';
           type: 'u64';
           index: false;
         },
         {
-          name: 'fees';
-          type: 'u64';
+          name: 'windowSeconds';
+          type: 'u32';
           index: false;
         },
       ];
     },
     {
-      name: 'SetDelegateLog';
+      name: 'SetStpGroupLog';
       fields: [
         {
           name: 'openOrdersAccount';
@@ -3350,10 +5111,23 @@ export type OpenbookV2 = {
           index: false;
         },
         {
-          name: 'delegate';
-          type: {
-            option: 'publicKey';
-          };
+          name: 'stpGroup';
+          type: 'u16';
+          index: false;
+        },
+      ];
+    },
+    {
+      name: 'SetCrankRewardLog';
+      fields: [
+        {
+          name: 'market';
+          type: 'publicKey';
+          index: false;
+        },
+        {
+          name: 'crankReward';
+          type: 'u64';
           index: false;
         },
       ];
@@ -3573,161 +5347,1036 @@ export type OpenbookV2 = {
       msg: 'The `consume_events_admin` required by this market to sign all instructions that consume events is missing or is not valid';
     },
     {
-      code: 6019;
-      name: 'InvalidMarketVault';
-      msg: 'Provided `market_vault` is invalid';
+      code: 6019;
+      name: 'InvalidMarketVault';
+      msg: 'Provided `market_vault` is invalid';
+    },
+    {
+      code: 6020;
+      name: 'IndexerActiveOO';
+      msg: 'Cannot be closed due to the existence of open orders accounts';
+    },
+    {
+      code: 6021;
+      name: 'OraclePegInvalidOracleState';
+      msg: 'Cannot place a peg order due to invalid oracle state';
+    },
+    {
+      code: 6022;
+      name: 'UnknownOracleType';
+      msg: 'oracle type cannot be determined';
+    },
+    {
+      code: 6023;
+      name: 'OracleConfidence';
+      msg: 'an oracle does not reach the confidence threshold';
+    },
+    {
+      code: 6024;
+      name: 'OracleStale';
+      msg: 'an oracle is stale';
+    },
+    {
+      code: 6025;
+      name: 'OrderIdNotFound';
+      msg: 'Order id not found on the orderbook';
+    },
+    {
+      code: 6026;
+      name: 'EventHeapContainsElements';
+      msg: "Event heap contains elements and market can't be closed";
+    },
+    {
+      code: 6027;
+      name: 'InvalidOrderPostIOC';
+      msg: 'ImmediateOrCancel is not a PostOrderType';
+    },
+    {
+      code: 6028;
+      name: 'InvalidOrderPostMarket';
+      msg: 'Market is not a PostOrderType';
+    },
+    {
+      code: 6029;
+      name: 'WouldSelfTrade';
+      msg: 'would self trade';
+    },
+    {
+      code: 6030;
+      name: 'MarketHasExpired';
+      msg: 'The Market has already expired.';
+    },
+    {
+      code: 6031;
+      name: 'InvalidPriceLots';
+      msg: 'Price lots should be greater than zero';
+    },
+    {
+      code: 6032;
+      name: 'InvalidOraclePrice';
+      msg: 'Oracle price above market limits';
+    },
+    {
+      code: 6033;
+      name: 'MarketHasNotExpired';
+      msg: 'The Market has not expired yet.';
+    },
+    {
+      code: 6034;
+      name: 'NoOwnerOrDelegate';
+      msg: 'No correct owner or delegate.';
+    },
+    {
+      code: 6035;
+      name: 'NoOwner';
+      msg: 'No correct owner';
+    },
+    {
+      code: 6036;
+      name: 'OpenOrdersFull';
+      msg: 'No free order index in open orders account';
+    },
+    {
+      code: 6037;
+      name: 'BookContainsElements';
+      msg: 'Book contains elements';
+    },
+    {
+      code: 6038;
+      name: 'OpenOrdersOrderNotFound';
+      msg: 'Could not find order in user account';
+    },
+    {
+      code: 6039;
+      name: 'InvalidPostAmount';
+      msg: 'Amount to post above book limits';
+    },
+    {
+      code: 6040;
+      name: 'DisabledOraclePeg';
+      msg: 'Oracle peg orders are not enabled for this market';
+    },
+    {
+      code: 6041;
+      name: 'NonEmptyMarket';
+      msg: 'Cannot close a non-empty market';
+    },
+    {
+      code: 6042;
+      name: 'NonEmptyOpenOrdersPosition';
+      msg: 'Cannot close a non-empty open orders account';
+    },
+    {
+      code: 6043;
+      name: 'WouldExecutePartially';
+      msg: 'Fill-Or-Kill order would generate a partial execution';
+    },
+    {
+      code: 6044;
+      name: 'InvalidTwoHopRoute';
+      msg: "The markets and token accounts don't form a valid two hop route";
+    },
+    {
+      code: 6045;
+      name: 'TwoHopAmountOutBelowMinimum';
+      msg: 'The two hop swap would receive less than the minimum amount out';
+    },
+    {
+      code: 6046;
+      name: 'InvalidOpenOrdersCapacity';
+      msg: 'Invalid open orders account capacity';
+    },
+    {
+      code: 6047;
+      name: 'InvalidBookSideCapacity';
+      msg: 'Invalid book side capacity';
+    },
+    {
+      code: 6048;
+      name: 'InvalidEventHeapCapacity';
+      msg: 'Invalid event heap capacity';
+    },
+    {
+      code: 6049;
+      name: 'InvalidInputDepthLevels';
+      msg: 'Depth levels above return data limit';
+    },
+    {
+      code: 6050;
+      name: 'InsufficientFreeFunds';
+      msg: 'Amount exceeds the free funds of the position';
+    },
+    {
+      code: 6051;
+      name: 'InvalidSettleBatchAccounts';
+      msg: "The remaining accounts don't describe open orders accounts of the owner's indexer";
+    },
+    {
+      code: 6052;
+      name: 'EventHeapFull';
+      msg: "The event heap is full and the maker's open orders account wasn't passed";
+    },
+    {
+      code: 6053;
+      name: 'InvalidDelegatePermissions';
+      msg: 'Delegate permissions have unknown bits set';
+    },
+    {
+      code: 6054;
+      name: 'DelegateOrderNotionalExceeded';
+      msg: "Order value exceeds the delegate's limit";
+    },
+    {
+      code: 6055;
+      name: 'InvalidInputSessionExpiry';
+    },
+    {
+      code: 6056;
+      name: 'SessionDelegatesFull';
+      msg: 'No session delegate slot left';
+    },
+    {
+      code: 6057;
+      name: 'InvalidInputRiskLimits';
+      msg: "Risk limits can't be negative and a taker volume limit needs a window";
     },
     {
-      code: 6020;
-      name: 'IndexerActiveOO';
-      msg: 'Cannot be closed due to the existence of open orders accounts';
+      code: 6058;
+      name: 'RiskLimitOpenBaseLotsExceeded';
+      msg: "Open orders on this side exceed the account's base lots limit";
     },
     {
-      code: 6021;
-      name: 'OraclePegInvalidOracleState';
-      msg: 'Cannot place a peg order due to invalid oracle state';
+      code: 6059;
+      name: 'RiskLimitOrderNotionalExceeded';
+      msg: "Order value exceeds the account's limit";
     },
     {
-      code: 6022;
-      name: 'UnknownOracleType';
-      msg: 'oracle type cannot be determined';
+      code: 6060;
+      name: 'RiskLimitTakerVolumeExceeded';
+      msg: "Taker volume exceeds the account's limit for the current window";
     },
     {
-      code: 6023;
-      name: 'OracleConfidence';
-      msg: 'an oracle does not reach the confidence threshold';
+      code: 6061;
+      name: 'HeartbeatNotMissed';
+      msg: "The account's heartbeat timeout hasn't elapsed";
     },
     {
-      code: 6024;
-      name: 'OracleStale';
-      msg: 'an oracle is stale';
+      code: 6062;
+      name: 'InvalidOracleVerificationLevel';
+      msg: "The Pyth price update isn't fully verified";
     },
     {
-      code: 6025;
-      name: 'OrderIdNotFound';
-      msg: 'Order id not found on the orderbook';
+      code: 6063;
+      name: 'OracleEmaPriceUnavailable';
+      msg: "The oracle doesn't provide an EMA price";
     },
     {
-      code: 6026;
-      name: 'EventHeapContainsElements';
-      msg: "Event heap contains elements and market can't be closed";
+      code: 6064;
+      name: 'InvalidOracleFallback';
+      msg: 'Falling back to oracle b requires a second oracle';
     },
     {
-      code: 6027;
-      name: 'InvalidOrderPostIOC';
-      msg: 'ImmediateOrCancel is not a PostOrderType';
+      code: 6065;
+      name: 'InvalidCrankReward';
+      msg: "The crank reward can't exceed the event heap penalty";
     },
     {
-      code: 6028;
-      name: 'InvalidOrderPostMarket';
-      msg: 'Market is not a PostOrderType';
+      code: 6066;
+      name: 'InvalidOpenOrdersIndexerMigration';
+      msg: "The open orders account isn't part of the indexer";
     },
     {
-      code: 6029;
-      name: 'WouldSelfTrade';
-      msg: 'would self trade';
+      code: 6067;
+      name: 'InvalidOraclePoolMints';
+      msg: "The tokens of the oracle pool don't match the market";
     },
     {
-      code: 6030;
-      name: 'MarketHasExpired';
-      msg: 'The Market has already expired.';
+      code: 6068;
+      name: 'NoDelegate';
+      msg: 'The open orders account has no delegate';
     },
     {
-      code: 6031;
-      name: 'InvalidPriceLots';
-      msg: 'Price lots should be greater than zero';
+      code: 6069;
+      name: 'OpenOrdersIndexerFull';
+      msg: 'The open orders indexer holds the maximum number of accounts';
     },
     {
-      code: 6032;
-      name: 'InvalidOraclePrice';
-      msg: 'Oracle price above market limits';
+      code: 6070;
+      name: 'InvalidOracleFeed';
+      msg: 'The Pyth price update is for a different feed';
     },
+  ];
+};
+
+export const IDL: OpenbookV2 = {
+  version: '0.1.0',
+  name: 'openbook_v2',
+  instructions: [
     {
-      code: 6033;
-      name: 'MarketHasNotExpired';
-      msg: 'The Market has not expired yet.';
+      name: 'createMarket',
+      docs: [
+        'Create a [`Market`](crate::state::Market) for a given token pair.',
+        '',
+        'The `bids`, `asks` and `event_heap` accounts are created by the client beforehand, sized',
+        'for `book_side_capacity` order tree nodes and `event_heap_capacity` events. See',
+        '[`DynamicAccount::space`](crate::accounts_zerocopy::DynamicAccount::space); the default',
+        'capacities are [`MAX_ORDERTREE_NODES`](crate::state::MAX_ORDERTREE_NODES) and',
+        '[`MAX_NUM_EVENTS`](crate::state::MAX_NUM_EVENTS).',
+        '',
+        'Takers pay `penalty_event_heap` lamports for every event they add to the event heap,',
+        "which fund a reward of `crank_reward` lamports per event consumed. The reward can't",
+        'exceed the penalty. The defaults are [`PENALTY_EVENT_HEAP`](crate::state::PENALTY_EVENT_HEAP),',
+        'which is also used when `penalty_event_heap` is 0, and',
+        '[`CRANK_REWARD`](crate::state::CRANK_REWARD).',
+        '',
+        "AMM pool oracles must trade the market's tokens: a single pool, or each pool when",
+        'falling back to `oracle_b`, has the base as first and the quote as second token. When',
+        'dividing the prices, the base and quote pools share their second token.',
+      ],
+      accounts: [
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'marketAuthority',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+          docs: [
+            'Accounts are initialized by client,',
+            'anchor discriminator is set first when ix exits,',
+          ],
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'marketBaseVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'baseMint',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'quoteMint',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'associatedTokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'collectFeeAdmin',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'consumeEventsAdmin',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'closeMarketAdmin',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'eventAuthority',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'program',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'name',
+          type: 'string',
+        },
+        {
+          name: 'oracleConfig',
+          type: {
+            defined: 'OracleConfigParams',
+          },
+        },
+        {
+          name: 'quoteLotSize',
+          type: 'i64',
+        },
+        {
+          name: 'baseLotSize',
+          type: 'i64',
+        },
+        {
+          name: 'makerFee',
+          type: 'i64',
+        },
+        {
+          name: 'takerFee',
+          type: 'i64',
+        },
+        {
+          name: 'timeExpiry',
+          type: 'i64',
+        },
+        {
+          name: 'bookSideCapacity',
+          type: 'u32',
+        },
+        {
+          name: 'eventHeapCapacity',
+          type: 'u16',
+        },
+        {
+          name: 'penaltyEventHeap',
+          type: 'u64',
+        },
+        {
+          name: 'crankReward',
+          type: 'u64',
+        },
+      ],
     },
     {
-      code: 6034;
-      name: 'NoOwnerOrDelegate';
-      msg: 'No correct owner or delegate.';
+      name: 'closeMarket',
+      docs: [
+        'Close a [`Market`](crate::state::Market) (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ],
+      accounts: [
+        {
+          name: 'closeMarketAdmin',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      code: 6035;
-      name: 'NoOwner';
-      msg: 'No correct owner';
+      name: 'createOpenOrdersIndexer',
+      docs: [
+        'Create an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      code: 6036;
-      name: 'OpenOrdersFull';
-      msg: 'No free order index in open orders account';
+      name: 'closeOpenOrdersIndexer',
+      docs: [
+        'Close an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
+      ],
+      accounts: [
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      code: 6037;
-      name: 'BookContainsElements';
-      msg: 'Book contains elements';
+      name: 'migrateOpenOrdersIndexer',
+      docs: [
+        'Backfill the markets of an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer)',
+        'created before it recorded them, growing the account paid by the `payer`.',
+        '',
+        'Pass any of the indexed open orders accounts as remaining accounts, large indexers can',
+        'be migrated over several transactions. Anyone can migrate an indexer.',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      code: 6038;
-      name: 'OpenOrdersOrderNotFound';
-      msg: 'Could not find order in user account';
+      name: 'createOpenOrdersAccount',
+      docs: [
+        'Create an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) with room for',
+        '`capacity` open orders.',
+        '',
+        '`capacity` must be at least [`MAX_OPEN_ORDERS`](crate::state::MAX_OPEN_ORDERS). As',
+        'accounts created by a program are limited to 10KiB, use',
+        '[`grow_open_orders_account`](crate::openbook_v2::grow_open_orders_account) to go',
+        'beyond [`MAX_OPEN_ORDERS_CREATION_CAPACITY`](crate::state::MAX_OPEN_ORDERS_CREATION_CAPACITY)',
+        '(248) open orders.',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'delegateAccount',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'name',
+          type: 'string',
+        },
+        {
+          name: 'capacity',
+          type: 'u16',
+        },
+      ],
     },
     {
-      code: 6039;
-      name: 'InvalidPostAmount';
-      msg: 'Amount to post above book limits';
+      name: 'growOpenOrdersAccount',
+      docs: [
+        'Grow an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) to have room for',
+        '`capacity` open orders, at most [`MAX_OPEN_ORDERS_GROWTH`](crate::state::MAX_OPEN_ORDERS_GROWTH)',
+        '(256) more than it had before.',
+      ],
+      accounts: [
+        {
+          name: 'payer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'capacity',
+          type: 'u16',
+        },
+      ],
     },
     {
-      code: 6040;
-      name: 'DisabledOraclePeg';
-      msg: 'Oracle peg orders are not enabled for this market';
+      name: 'closeOpenOrdersAccount',
+      docs: [
+        'Close an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ],
+      accounts: [
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersIndexer',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'solDestination',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
     {
-      code: 6041;
-      name: 'NonEmptyMarket';
-      msg: 'Cannot close a non-empty market';
+      name: 'placeOrder',
+      docs: [
+        'Place an order.',
+        '',
+        'Different types of orders have different effects on the order book,',
+        'as described in [`PlaceOrderType`](crate::state::PlaceOrderType).',
+        '',
+        '`price_lots` refers to the price in lots: the number of quote lots',
+        'per base lot. It is ignored for `PlaceOrderType::Market` orders.',
+        '',
+        '`expiry_timestamp` is a unix timestamp for when this order should',
+        'expire. If 0 is passed in, the order will never expire. If the time',
+        'is in the past, the instruction is skipped. Timestamps in the future',
+        'are reduced to now + 65,535s.',
+        '',
+        '`limit` determines the maximum number of orders from the book to fill,',
+        'and can be used to limit CU spent. When the limit is reached, processing',
+        'stops and the instruction succeeds.',
+      ],
+      accounts: [
+        {
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'userTokenAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'args',
+          type: {
+            defined: 'PlaceOrderArgs',
+          },
+        },
+      ],
+      returns: {
+        option: 'u128',
+      },
     },
     {
-      code: 6042;
-      name: 'NonEmptyOpenOrdersPosition';
-      msg: 'Cannot close a non-empty open orders account';
+      name: 'editOrder',
+      docs: ['Edit an order.'],
+      accounts: [
+        {
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'userTokenAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'clientOrderId',
+          type: 'u64',
+        },
+        {
+          name: 'expectedCancelSize',
+          type: 'i64',
+        },
+        {
+          name: 'placeOrder',
+          type: {
+            defined: 'PlaceOrderArgs',
+          },
+        },
+      ],
+      returns: {
+        option: 'u128',
+      },
     },
     {
-      code: 6043;
-      name: 'WouldExecutePartially';
-      msg: 'Fill-Or-Kill order would generate a partial execution';
+      name: 'editOrderPegged',
+      docs: ['Edit an order pegged.'],
+      accounts: [
+        {
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'openOrdersAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'userTokenAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'clientOrderId',
+          type: 'u64',
+        },
+        {
+          name: 'expectedCancelSize',
+          type: 'i64',
+        },
+        {
+          name: 'placeOrder',
+          type: {
+            defined: 'PlaceOrderPeggedArgs',
+          },
+        },
+      ],
+      returns: {
+        option: 'u128',
+      },
     },
-  ];
-};
-
-export const IDL: OpenbookV2 = {
-  version: '0.1.0',
-  name: 'openbook_v2',
-  instructions: [
     {
-      name: 'createMarket',
-      docs: [
-        'Create a [`Market`](crate::state::Market) for a given token pair.',
-      ],
+      name: 'placeOrders',
+      docs: ['Place multiple orders'],
       accounts: [
         {
-          name: 'market',
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
           isMut: true,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: 'marketAuthority',
+          name: 'openOrdersAdmin',
           isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'userQuoteAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'userBaseAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: true,
           isSigner: false,
         },
         {
           name: 'bids',
           isMut: true,
           isSigner: false,
-          docs: [
-            'Accounts are initialized by client,',
-            'anchor discriminator is set first when ix exits,',
-          ],
         },
         {
           name: 'asks',
@@ -3740,9 +6389,9 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'payer',
+          name: 'marketQuoteVault',
           isMut: true,
-          isSigner: true,
+          isSigner: false,
         },
         {
           name: 'marketBaseVault',
@@ -3750,33 +6399,115 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
-          isMut: true,
+          name: 'oracleA',
+          isMut: false,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'baseMint',
+          name: 'oracleB',
           isMut: false,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'quoteMint',
+          name: 'tokenProgram',
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
+        {
+          name: 'ordersType',
+          type: {
+            defined: 'PlaceOrderType',
+          },
+        },
         {
-          name: 'systemProgram',
+          name: 'bids',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
+          },
+        },
+        {
+          name: 'asks',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
+          },
+        },
+        {
+          name: 'limit',
+          type: 'u8',
+        },
+      ],
+      returns: {
+        vec: {
+          option: 'u128',
+        },
+      },
+    },
+    {
+      name: 'cancelAllAndPlaceOrders',
+      docs: ['Cancel orders and place multiple orders.'],
+      accounts: [
+        {
+          name: 'signer',
           isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'tokenProgram',
+          name: 'openOrdersAdmin',
           isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: 'userQuoteAccount',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'associatedTokenProgram',
-          isMut: false,
+          name: 'userBaseAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'market',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'bids',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'marketBaseVault',
+          isMut: true,
           isSigner: false,
         },
         {
@@ -3792,82 +6523,142 @@ export const IDL: OpenbookV2 = {
           isOptional: true,
         },
         {
-          name: 'collectFeeAdmin',
+          name: 'tokenProgram',
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'openOrdersAdmin',
+          name: 'ordersType',
+          type: {
+            defined: 'PlaceOrderType',
+          },
+        },
+        {
+          name: 'bids',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
+          },
+        },
+        {
+          name: 'asks',
+          type: {
+            vec: {
+              defined: 'PlaceMultipleOrdersArgs',
+            },
+          },
+        },
+        {
+          name: 'limit',
+          type: 'u8',
+        },
+      ],
+      returns: {
+        vec: {
+          option: 'u128',
+        },
+      },
+    },
+    {
+      name: 'placeOrderPegged',
+      docs: ['Place an oracle-peg order.'],
+      accounts: [
+        {
+          name: 'signer',
           isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'consumeEventsAdmin',
+          name: 'openOrdersAdmin',
           isMut: false,
-          isSigner: false,
+          isSigner: true,
           isOptional: true,
         },
         {
-          name: 'closeMarketAdmin',
-          isMut: false,
+          name: 'userTokenAccount',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'eventAuthority',
-          isMut: false,
+          name: 'market',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'program',
-          isMut: false,
+          name: 'bids',
+          isMut: true,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'name',
-          type: 'string',
+          name: 'asks',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'oracleConfig',
-          type: {
-            defined: 'OracleConfigParams',
-          },
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'quoteLotSize',
-          type: 'i64',
+          name: 'marketVault',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'baseLotSize',
-          type: 'i64',
+          name: 'oracleA',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'makerFee',
-          type: 'i64',
+          name: 'oracleB',
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'takerFee',
-          type: 'i64',
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'timeExpiry',
-          type: 'i64',
+          name: 'args',
+          type: {
+            defined: 'PlaceOrderPeggedArgs',
+          },
         },
       ],
+      returns: {
+        option: 'u128',
+      },
     },
     {
-      name: 'closeMarket',
+      name: 'placeTakeOrder',
       docs: [
-        'Close a [`Market`](crate::state::Market) (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        'Place an order that shall take existing liquidity off of the book, not',
+        'add a new order off the book.',
+        '',
+        'This type of order allows for instant token settlement for the taker.',
       ],
       accounts: [
         {
-          name: 'closeMarketAdmin',
-          isMut: false,
+          name: 'signer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'penaltyPayer',
+          isMut: true,
           isSigner: true,
         },
         {
@@ -3875,6 +6666,11 @@ export const IDL: OpenbookV2 = {
           isMut: true,
           isSigner: false,
         },
+        {
+          name: 'marketAuthority',
+          isMut: false,
+          isSigner: false,
+        },
         {
           name: 'bids',
           isMut: true,
@@ -3886,194 +6682,153 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'marketBaseVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'solDestination',
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'tokenProgram',
-          isMut: false,
+          name: 'eventHeap',
+          isMut: true,
           isSigner: false,
         },
-      ],
-      args: [],
-    },
-    {
-      name: 'createOpenOrdersIndexer',
-      docs: [
-        'Create an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
-      ],
-      accounts: [
         {
-          name: 'payer',
+          name: 'userBaseAccount',
           isMut: true,
-          isSigner: true,
-        },
-        {
-          name: 'owner',
-          isMut: false,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: 'openOrdersIndexer',
+          name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'systemProgram',
+          name: 'oracleA',
           isMut: false,
           isSigner: false,
+          isOptional: true,
         },
-      ],
-      args: [],
-    },
-    {
-      name: 'closeOpenOrdersIndexer',
-      docs: [
-        'Close an [`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) account.',
-      ],
-      accounts: [
         {
-          name: 'owner',
+          name: 'oracleB',
           isMut: false,
-          isSigner: true,
+          isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'openOrdersIndexer',
-          isMut: true,
+          name: 'tokenProgram',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'solDestination',
-          isMut: true,
+          name: 'systemProgram',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'tokenProgram',
+          name: 'openOrdersAdmin',
           isMut: false,
-          isSigner: false,
+          isSigner: true,
+          isOptional: true,
+        },
+      ],
+      args: [
+        {
+          name: 'args',
+          type: {
+            defined: 'PlaceTakeOrderArgs',
+          },
         },
       ],
-      args: [],
     },
     {
-      name: 'createOpenOrdersAccount',
+      name: 'placeTakeOrderExactQuoteIn',
       docs: [
-        'Create an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+        'Place a take order buying as much base as possible for `quote_amount_native`,',
+        'taker fees included.',
+        '',
+        'Only the spent quote is transferred from the user, the unspent remainder (e.g. lot',
+        "rounding dust) stays in the user's token account.",
       ],
       accounts: [
         {
-          name: 'payer',
+          name: 'signer',
           isMut: true,
           isSigner: true,
         },
         {
-          name: 'owner',
-          isMut: false,
+          name: 'penaltyPayer',
+          isMut: true,
           isSigner: true,
         },
         {
-          name: 'delegateAccount',
-          isMut: false,
+          name: 'market',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'openOrdersIndexer',
-          isMut: true,
+          name: 'marketAuthority',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'bids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'market',
-          isMut: false,
+          name: 'asks',
+          isMut: true,
           isSigner: false,
         },
         {
-          name: 'systemProgram',
-          isMut: false,
+          name: 'marketBaseVault',
+          isMut: true,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'name',
-          type: 'string',
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
         },
-      ],
-    },
-    {
-      name: 'closeOpenOrdersAccount',
-      docs: [
-        'Close an [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
-      ],
-      accounts: [
         {
-          name: 'owner',
-          isMut: false,
-          isSigner: true,
+          name: 'eventHeap',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'openOrdersIndexer',
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'solDestination',
-          isMut: true,
+          name: 'oracleA',
+          isMut: false,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'systemProgram',
+          name: 'oracleB',
           isMut: false,
           isSigner: false,
+          isOptional: true,
         },
-      ],
-      args: [],
-    },
-    {
-      name: 'placeOrder',
-      docs: [
-        'Place an order.',
-        '',
-        'Different types of orders have different effects on the order book,',
-        'as described in [`PlaceOrderType`](crate::state::PlaceOrderType).',
-        '',
-        '`price_lots` refers to the price in lots: the number of quote lots',
-        'per base lot. It is ignored for `PlaceOrderType::Market` orders.',
-        '',
-        '`expiry_timestamp` is a unix timestamp for when this order should',
-        'expire. If 0 is passed in, the order will never expire. If the time',
-        'is in the past, the instruction is skipped. Timestamps in the future',
-        'are reduced to now + 65,535s.',
-        '',
-        '`limit` determines the maximum number of orders from the book to fill,',
-        'and can be used to limit CU spent. When the limit is reached, processing',
-        'stops and the instruction succeeds.',
-      ],
-      accounts: [
         {
-          name: 'signer',
+          name: 'tokenProgram',
           isMut: false,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: 'openOrdersAccount',
-          isMut: true,
+          name: 'systemProgram',
+          isMut: false,
           isSigner: false,
         },
         {
@@ -4082,50 +6837,178 @@ export const IDL: OpenbookV2 = {
           isSigner: true,
           isOptional: true,
         },
+      ],
+      args: [
         {
-          name: 'userTokenAccount',
-          isMut: true,
-          isSigner: false,
+          name: 'args',
+          type: {
+            defined: 'PlaceTakeOrderExactQuoteInArgs',
+          },
         },
+      ],
+    },
+    {
+      name: 'placeTakeOrderTwoHop',
+      docs: [
+        'Swap through two markets sharing a token in a single instruction, e.g. A->USDC->B.',
+        '',
+        'The tokens received from a take order on the first market are used as input of a take',
+        'order on the second one. Fails if less than `min_amount_out_native` would be received.',
+      ],
+      accounts: [
         {
-          name: 'market',
+          name: 'signer',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'bids',
+          name: 'penaltyPayer',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'asks',
-          isMut: true,
-          isSigner: false,
+          name: 'hop1',
+          accounts: [
+            {
+              name: 'market',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'marketAuthority',
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: 'bids',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'asks',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'marketBaseVault',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'marketQuoteVault',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'eventHeap',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'oracleA',
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+            },
+            {
+              name: 'oracleB',
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+            },
+            {
+              name: 'openOrdersAdmin',
+              isMut: false,
+              isSigner: true,
+              isOptional: true,
+            },
+          ],
         },
         {
-          name: 'eventHeap',
+          name: 'hop2',
+          accounts: [
+            {
+              name: 'market',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'marketAuthority',
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: 'bids',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'asks',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'marketBaseVault',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'marketQuoteVault',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'eventHeap',
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: 'oracleA',
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+            },
+            {
+              name: 'oracleB',
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+            },
+            {
+              name: 'openOrdersAdmin',
+              isMut: false,
+              isSigner: true,
+              isOptional: true,
+            },
+          ],
+        },
+        {
+          name: 'userInputAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketVault',
+          name: 'userIntermediateAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
+          docs: [
+            "Receives the part of the first hop's output that couldn't be used in the second hop,",
+            'only required if there is any',
+          ],
         },
         {
-          name: 'oracleA',
-          isMut: false,
+          name: 'userOutputAccount',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'oracleB',
+          name: 'tokenProgram',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'tokenProgram',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
         },
@@ -4134,105 +7017,126 @@ export const IDL: OpenbookV2 = {
         {
           name: 'args',
           type: {
-            defined: 'PlaceOrderArgs',
+            defined: 'PlaceTakeOrderTwoHopArgs',
           },
         },
       ],
-      returns: {
-        option: 'u128',
-      },
     },
     {
-      name: 'editOrder',
-      docs: ['Edit an order.'],
+      name: 'consumeEvents',
+      docs: [
+        'Process up to `limit` [events](crate::state::AnyEvent).',
+        '',
+        "When a user places a 'take' order, they do not know beforehand which",
+        "market maker will have placed the 'make' order that they get executed",
+        "against. This prevents them from passing in a market maker's",
+        '[`OpenOrdersAccount`](crate::state::OpenOrdersAccount), which is needed',
+        'to credit/debit the relevant tokens to/from the maker. As such, Openbook',
+        "uses a 'crank' system, where `place_order` only emits events, and",
+        '`consume_events` handles token settlement.',
+        '',
+        'Currently, there are two types of events: [`FillEvent`](crate::state::FillEvent)s',
+        'and [`OutEvent`](crate::state::OutEvent)s.',
+        '',
+        'A `FillEvent` is emitted when an order is filled, and it is handled by',
+        'debiting whatever the taker is selling from the taker and crediting',
+        'it to the maker, and debiting whatever the taker is buying from the',
+        'maker and crediting it to the taker. Note that *no tokens are moved*,',
+        "these are just debits and credits to each party's [`Position`](crate::state::Position).",
+        'Makers that opted into [`auto_settle`](crate::state::Position::auto_settle) are paid the',
+        'funds credited by the fill directly if the market authority, the vaults, the token program',
+        "and the associated token accounts of the maker's owner are passed as remaining accounts. The same holds",
+        'for fills executed right away while a taker order is matched.',
+        '',
+        'An `OutEvent` is emitted when a limit order needs to be removed from',
+        'the book during a `place_order` invocation, and it is handled by',
+        'crediting whatever the maker would have sold (quote token in a bid,',
+        'base token in an ask) back to the maker.',
+        '',
+        'The `crank_reward_receiver` is paid [`crank_reward`](crate::state::Market::crank_reward)',
+        "lamports for every processed event, as long as the market's",
+        '[`penalty_pool`](crate::state::Market::penalty_pool) covers it. The reward stays in the',
+        'pool if it would leave the receiver below rent exemption.',
+      ],
       accounts: [
         {
-          name: 'signer',
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: 'openOrdersAccount',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'openOrdersAdmin',
+          name: 'consumeEventsAdmin',
           isMut: false,
           isSigner: true,
           isOptional: true,
         },
         {
-          name: 'userTokenAccount',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'market',
+          name: 'eventHeap',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'bids',
+          name: 'crankRewardReceiver',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
+      ],
+      args: [
         {
-          name: 'asks',
-          isMut: true,
-          isSigner: false,
+          name: 'limit',
+          type: 'u64',
         },
+      ],
+    },
+    {
+      name: 'consumeGivenEvents',
+      docs: [
+        'Process the [events](crate::state::AnyEvent) at the given positions.',
+        '',
+        "All positions must be below the capacity of the market's event heap.",
+      ],
+      accounts: [
         {
-          name: 'eventHeap',
-          isMut: true,
-          isSigner: false,
+          name: 'consumeEventsAdmin',
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
         },
         {
-          name: 'marketVault',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
-          isMut: false,
+          name: 'eventHeap',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'oracleB',
-          isMut: false,
+          name: 'crankRewardReceiver',
+          isMut: true,
           isSigner: false,
           isOptional: true,
         },
-        {
-          name: 'tokenProgram',
-          isMut: false,
-          isSigner: false,
-        },
       ],
       args: [
         {
-          name: 'clientOrderId',
-          type: 'u64',
-        },
-        {
-          name: 'expectedCancelSize',
-          type: 'i64',
-        },
-        {
-          name: 'placeOrder',
+          name: 'slots',
           type: {
-            defined: 'PlaceOrderArgs',
+            vec: 'u64',
           },
         },
       ],
-      returns: {
-        option: 'u128',
-      },
     },
     {
-      name: 'editOrderPegged',
-      docs: ['Edit an order pegged.'],
+      name: 'cancelOrder',
+      docs: [
+        'Cancel an order by its `order_id`.',
+        '',
+        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
+        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ],
       accounts: [
         {
           name: 'signer',
@@ -4244,20 +7148,9 @@ export const IDL: OpenbookV2 = {
           isMut: true,
           isSigner: false,
         },
-        {
-          name: 'openOrdersAdmin',
-          isMut: false,
-          isSigner: true,
-          isOptional: true,
-        },
-        {
-          name: 'userTokenAccount',
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: 'market',
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
@@ -4270,31 +7163,46 @@ export const IDL: OpenbookV2 = {
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'eventHeap',
-          isMut: true,
-          isSigner: false,
+          name: 'orderId',
+          type: 'u128',
+        },
+      ],
+    },
+    {
+      name: 'cancelOrderByClientOrderId',
+      docs: [
+        'Cancel an order by its `client_order_id`.',
+        '',
+        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
+        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+      ],
+      accounts: [
+        {
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
         },
         {
-          name: 'marketVault',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
+          name: 'market',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'oracleB',
-          isMut: false,
+          name: 'bids',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'tokenProgram',
-          isMut: false,
+          name: 'asks',
+          isMut: true,
           isSigner: false,
         },
       ],
@@ -4303,24 +7211,12 @@ export const IDL: OpenbookV2 = {
           name: 'clientOrderId',
           type: 'u64',
         },
-        {
-          name: 'expectedCancelSize',
-          type: 'i64',
-        },
-        {
-          name: 'placeOrder',
-          type: {
-            defined: 'PlaceOrderPeggedArgs',
-          },
-        },
       ],
-      returns: {
-        option: 'u128',
-      },
+      returns: 'i64',
     },
     {
-      name: 'placeOrders',
-      docs: ['Place multiple orders'],
+      name: 'cancelAllOrders',
+      docs: ['Cancel up to `limit` orders, optionally filtering by side'],
       accounts: [
         {
           name: 'signer',
@@ -4332,25 +7228,9 @@ export const IDL: OpenbookV2 = {
           isMut: true,
           isSigner: false,
         },
-        {
-          name: 'openOrdersAdmin',
-          isMut: false,
-          isSigner: true,
-          isOptional: true,
-        },
-        {
-          name: 'userQuoteAccount',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'userBaseAccount',
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: 'market',
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
@@ -4363,144 +7243,164 @@ export const IDL: OpenbookV2 = {
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'eventHeap',
-          isMut: true,
-          isSigner: false,
+          name: 'sideOption',
+          type: {
+            option: {
+              defined: 'Side',
+            },
+          },
         },
         {
-          name: 'marketQuoteVault',
-          isMut: true,
-          isSigner: false,
+          name: 'limit',
+          type: 'u8',
         },
+      ],
+    },
+    {
+      name: 'cancelOnMissedHeartbeat',
+      docs: [
+        'Cancel up to `limit` orders of an open orders account whose',
+        '[`heartbeat_timeout_seconds`](crate::state::Position::heartbeat_timeout_seconds) elapsed',
+        'without a heartbeat. Permissionless.',
+      ],
+      accounts: [
         {
-          name: 'marketBaseVault',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
+          name: 'market',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'oracleB',
-          isMut: false,
+          name: 'bids',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'tokenProgram',
-          isMut: false,
+          name: 'asks',
+          isMut: true,
           isSigner: false,
         },
       ],
       args: [
-        {
-          name: 'ordersType',
-          type: {
-            defined: 'PlaceOrderType',
-          },
-        },
-        {
-          name: 'bids',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
-        },
-        {
-          name: 'asks',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
-        },
         {
           name: 'limit',
           type: 'u8',
         },
       ],
-      returns: {
-        vec: {
-          option: 'u128',
-        },
-      },
     },
     {
-      name: 'cancelAllAndPlaceOrders',
-      docs: ['Cancel orders and place multiple orders.'],
+      name: 'deposit',
+      docs: [
+        "Deposit a certain amount of `base` and `quote` lamports into one's",
+        '[`Position`](crate::state::Position).',
+        '',
+        'Makers might wish to `deposit`, rather than have actual tokens moved for',
+        'each trade, in order to reduce CUs.',
+      ],
       accounts: [
         {
-          name: 'signer',
+          name: 'owner',
           isMut: false,
           isSigner: true,
         },
+        {
+          name: 'userBaseAccount',
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: 'userQuoteAccount',
+          isMut: true,
+          isSigner: false,
+        },
         {
           name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'openOrdersAdmin',
-          isMut: false,
-          isSigner: true,
-          isOptional: true,
-        },
-        {
-          name: 'userQuoteAccount',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'userBaseAccount',
+          name: 'marketBaseVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'market',
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'bids',
-          isMut: true,
+          name: 'tokenProgram',
+          isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'asks',
+          name: 'baseAmount',
+          type: 'u64',
+        },
+        {
+          name: 'quoteAmount',
+          type: 'u64',
+        },
+      ],
+    },
+    {
+      name: 'refill',
+      docs: [
+        'Refill a certain amount of `base` and `quote` lamports. The amount being passed is the',
+        'total lamports that the [`Position`](crate::state::Position) will have.',
+        '',
+        'Makers might wish to `refill`, rather than have actual tokens moved for',
+        'each trade, in order to reduce CUs.',
+      ],
+      accounts: [
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketBaseVault',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
-          isMut: false,
+          name: 'marketBaseVault',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'oracleB',
-          isMut: false,
+          name: 'marketQuoteVault',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
           name: 'tokenProgram',
@@ -4510,60 +7410,38 @@ export const IDL: OpenbookV2 = {
       ],
       args: [
         {
-          name: 'ordersType',
-          type: {
-            defined: 'PlaceOrderType',
-          },
-        },
-        {
-          name: 'bids',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
-        },
-        {
-          name: 'asks',
-          type: {
-            vec: {
-              defined: 'PlaceMultipleOrdersArgs',
-            },
-          },
+          name: 'baseAmount',
+          type: 'u64',
         },
         {
-          name: 'limit',
-          type: 'u8',
+          name: 'quoteAmount',
+          type: 'u64',
         },
       ],
-      returns: {
-        vec: {
-          option: 'u128',
-        },
-      },
     },
     {
-      name: 'placeOrderPegged',
-      docs: ['Place an oracle-peg order.'],
+      name: 'withdraw',
+      docs: [
+        "Withdraw a certain amount of `base` and `quote` lamports from one's",
+        '[`Position`](crate::state::Position), leaving the rest available for placing orders.',
+        '',
+        "The amounts can't exceed the free funds of the position. Use `settle_funds` to also",
+        'claim referrer rebates. Outstanding event heap penalties are collected from the',
+        '`penalty_payer` like in `settle_funds`.',
+      ],
       accounts: [
         {
-          name: 'signer',
+          name: 'owner',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'penaltyPayer',
           isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'openOrdersAdmin',
-          isMut: false,
           isSigner: true,
-          isOptional: true,
         },
         {
-          name: 'userTokenAccount',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
@@ -4573,66 +7451,58 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'bids',
-          isMut: true,
+          name: 'marketAuthority',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'marketBaseVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketVault',
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'oracleA',
-          isMut: false,
+          name: 'userQuoteAccount',
+          isMut: true,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'oracleB',
+          name: 'tokenProgram',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'tokenProgram',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: 'args',
-          type: {
-            defined: 'PlaceOrderPeggedArgs',
-          },
+          name: 'baseAmount',
+          type: 'u64',
+        },
+        {
+          name: 'quoteAmount',
+          type: 'u64',
         },
       ],
-      returns: {
-        option: 'u128',
-      },
     },
     {
-      name: 'placeTakeOrder',
-      docs: [
-        'Place an order that shall take existing liquidity off of the book, not',
-        'add a new order off the book.',
-        '',
-        'This type of order allows for instant token settlement for the taker.',
-      ],
+      name: 'settleFunds',
+      docs: ['Withdraw any available tokens.'],
       accounts: [
         {
-          name: 'signer',
+          name: 'owner',
           isMut: true,
           isSigner: true,
         },
@@ -4642,23 +7512,18 @@ export const IDL: OpenbookV2 = {
           isSigner: true,
         },
         {
-          name: 'market',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketAuthority',
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: 'bids',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'asks',
-          isMut: true,
+          name: 'marketAuthority',
+          isMut: false,
           isSigner: false,
         },
         {
@@ -4672,91 +7537,100 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'userBaseAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'userBaseAccount',
+          name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'userQuoteAccount',
+          name: 'referrerAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'oracleA',
+          name: 'tokenProgram',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
         {
-          name: 'oracleB',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
-          isOptional: true,
         },
+      ],
+      args: [],
+    },
+    {
+      name: 'settleFundsBatch',
+      docs: [
+        'Withdraw any available tokens of several open orders accounts across markets.',
+        '',
+        "Every open orders account must be listed in the owner's",
+        '[`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer) and is passed as a group of',
+        '[`SETTLE_FUNDS_BATCH_GROUP_LEN`](crate::accounts_ix::SETTLE_FUNDS_BATCH_GROUP_LEN)',
+        'remaining accounts: the open orders account, its market, the market authority, the base',
+        'and quote vaults and the base and quote token accounts receiving the funds.',
+      ],
+      accounts: [
         {
-          name: 'tokenProgram',
+          name: 'owner',
           isMut: false,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'systemProgram',
+          name: 'penaltyPayer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersIndexer',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'openOrdersAdmin',
+          name: 'tokenProgram',
           isMut: false,
-          isSigner: true,
-          isOptional: true,
+          isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'args',
-          type: {
-            defined: 'PlaceTakeOrderArgs',
-          },
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
         },
       ],
+      args: [],
     },
     {
-      name: 'consumeEvents',
+      name: 'settleFundsExpired',
       docs: [
-        'Process up to `limit` [events](crate::state::AnyEvent).',
-        '',
-        "When a user places a 'take' order, they do not know beforehand which",
-        "market maker will have placed the 'make' order that they get executed",
-        "against. This prevents them from passing in a market maker's",
-        '[`OpenOrdersAccount`](crate::state::OpenOrdersAccount), which is needed',
-        'to credit/debit the relevant tokens to/from the maker. As such, Openbook',
-        "uses a 'crank' system, where `place_order` only emits events, and",
-        '`consume_events` handles token settlement.',
-        '',
-        'Currently, there are two types of events: [`FillEvent`](crate::state::FillEvent)s',
-        'and [`OutEvent`](crate::state::OutEvent)s.',
-        '',
-        'A `FillEvent` is emitted when an order is filled, and it is handled by',
-        'debiting whatever the taker is selling from the taker and crediting',
-        'it to the maker, and debiting whatever the taker is buying from the',
-        'maker and crediting it to the taker. Note that *no tokens are moved*,',
-        "these are just debits and credits to each party's [`Position`](crate::state::Position).",
-        '',
-        'An `OutEvent` is emitted when a limit order needs to be removed from',
-        'the book during a `place_order` invocation, and it is handled by',
-        'crediting whatever the maker would have sold (quote token in a bid,',
-        'base token in an ask) back to the maker.',
+        'Withdraw any available tokens when the market is expired (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
       ],
       accounts: [
         {
-          name: 'consumeEventsAdmin',
+          name: 'closeMarketAdmin',
           isMut: false,
           isSigner: true,
-          isOptional: true,
+        },
+        {
+          name: 'owner',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'penaltyPayer',
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
           name: 'market',
@@ -4764,250 +7638,275 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'marketAuthority',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'marketBaseVault',
           isMut: true,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'limit',
-          type: 'u64',
+          name: 'marketQuoteVault',
+          isMut: true,
+          isSigner: false,
         },
-      ],
-    },
-    {
-      name: 'consumeGivenEvents',
-      docs: [
-        'Process the [events](crate::state::AnyEvent) at the given positions.',
-      ],
-      accounts: [
         {
-          name: 'consumeEventsAdmin',
-          isMut: false,
-          isSigner: true,
-          isOptional: true,
+          name: 'userBaseAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'market',
+          name: 'userQuoteAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'eventHeap',
+          name: 'referrerAccount',
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
-      ],
-      args: [
         {
-          name: 'slots',
-          type: {
-            vec: 'u64',
-          },
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
         },
       ],
+      args: [],
     },
     {
-      name: 'cancelOrder',
-      docs: [
-        'Cancel an order by its `order_id`.',
-        '',
-        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
-        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
-      ],
+      name: 'sweepFees',
+      docs: ["Sweep fees, as a [`Market`](crate::state::Market)'s admin."],
       accounts: [
         {
-          name: 'signer',
+          name: 'collectFeeAdmin',
           isMut: false,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'market',
+          name: 'marketAuthority',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'bids',
+          name: 'marketQuoteVault',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'tokenReceiverAccount',
           isMut: true,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'orderId',
-          type: 'u128',
+          name: 'tokenProgram',
+          isMut: false,
+          isSigner: false,
         },
       ],
+      args: [],
     },
     {
-      name: 'cancelOrderByClientOrderId',
+      name: 'setCrankReward',
       docs: [
-        'Cancel an order by its `client_order_id`.',
-        '',
-        "Note that this doesn't emit an [`OutEvent`](crate::state::OutEvent) because a",
-        'maker knows that they will be passing in their own [`OpenOrdersAccount`](crate::state::OpenOrdersAccount).',
+        'Set the [`crank_reward`](crate::state::Market::crank_reward) paid for every processed',
+        "event, as the market's collect fee admin. It can't exceed the event heap penalty.",
       ],
       accounts: [
         {
-          name: 'signer',
+          name: 'collectFeeAdmin',
           isMut: false,
           isSigner: true,
         },
-        {
-          name: 'openOrdersAccount',
-          isMut: true,
-          isSigner: false,
-        },
         {
           name: 'market',
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: 'bids',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'asks',
           isMut: true,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: 'clientOrderId',
+          name: 'crankReward',
           type: 'u64',
         },
       ],
-      returns: 'i64',
     },
     {
-      name: 'cancelAllOrders',
-      docs: ['Cancel up to `limit` orders, optionally filtering by side'],
+      name: 'transferOpenOrdersAccount',
+      docs: [
+        'Move an open orders account, including its resting orders, to a new owner.',
+        '',
+        'Both owners sign, the account moves between their',
+        "[`OpenOrdersIndexer`](crate::state::OpenOrdersIndexer)s. The new owner's indexer is",
+        "created, paid by the `payer`, if it doesn't exist yet. All delegates, including session",
+        'delegates, are removed and the [`stp_group`](crate::state::OpenOrdersAccount::stp_group)',
+        'is reset to none. The balances, auto settle, risk limits and heartbeat timeout carry over',
+        'and can be changed by the new owner.',
+      ],
       accounts: [
         {
-          name: 'signer',
-          isMut: false,
+          name: 'payer',
+          isMut: true,
           isSigner: true,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'owner',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'market',
+          name: 'newOwner',
           isMut: false,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'bids',
+          name: 'openOrdersIndexer',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'asks',
+          name: 'newOpenOrdersIndexer',
           isMut: true,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: 'sideOption',
-          type: {
-            option: {
-              defined: 'Side',
-            },
-          },
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: 'limit',
-          type: 'u8',
+          name: 'systemProgram',
+          isMut: false,
+          isSigner: false,
         },
       ],
+      args: [],
     },
     {
-      name: 'deposit',
+      name: 'setDelegate',
       docs: [
-        "Deposit a certain amount of `base` and `quote` lamports into one's",
-        '[`Position`](crate::state::Position).',
+        'Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.',
         '',
-        'Makers might wish to `deposit`, rather than have actual tokens moved for',
-        'each trade, in order to reduce CUs.',
+        'A new delegate starts with all permissions and no order limit. Changing or removing the',
+        'delegate revokes all its session delegates.',
       ],
       accounts: [
         {
           name: 'owner',
-          isMut: false,
+          isMut: true,
           isSigner: true,
         },
         {
-          name: 'userBaseAccount',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'userQuoteAccount',
-          isMut: true,
+          name: 'delegateAccount',
+          isMut: false,
           isSigner: false,
+          isOptional: true,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: 'setDelegatePermissions',
+      docs: [
+        'Restrict the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders',
+        'account.',
+        '',
+        '`permissions` is a bitmask of the `DELEGATE_*` actions the delegate may take, 0 for none',
+        'and `DELEGATE_ALL_PERMISSIONS` for all of them. A non-zero `max_order_quote_native`',
+        "limits the value of the delegate's orders. Both are reset when the delegate changes.",
+      ],
+      accounts: [
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
         },
         {
           name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'market',
-          isMut: true,
-          isSigner: false,
+          name: 'permissions',
+          type: 'u8',
         },
         {
-          name: 'marketBaseVault',
+          name: 'maxOrderQuoteNative',
+          type: 'u64',
+        },
+      ],
+    },
+    {
+      name: 'setSessionDelegate',
+      docs: [
+        'Add, renew or revoke a session delegate of an open orders account, as the owner or a',
+        '[`delegate`](crate::state::OpenOrdersAccount::delegate) with all permissions.',
+        '',
+        'Session delegates have the permissions of the delegate until `expiry_timestamp`, which',
+        'can be at most [`MAX_SESSION_LIFETIME_SECONDS`](crate::state::MAX_SESSION_LIFETIME_SECONDS)',
+        'ahead, 0 revokes the key. They lose their authority when the delegate changes or is',
+        'removed. The account is grown by a slot if needed, paid by the `payer`.',
+      ],
+      accounts: [
+        {
+          name: 'payer',
           isMut: true,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'authority',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'tokenProgram',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
         },
       ],
       args: [
         {
-          name: 'baseAmount',
-          type: 'u64',
+          name: 'sessionKey',
+          type: 'publicKey',
         },
         {
-          name: 'quoteAmount',
+          name: 'expiryTimestamp',
           type: 'u64',
         },
       ],
     },
     {
-      name: 'refill',
+      name: 'setStpGroup',
       docs: [
-        'Refill a certain amount of `base` and `quote` lamports. The amount being passed is the',
-        'total lamports that the [`Position`](crate::state::Position) will have.',
+        'Update the [`stp_group`](crate::state::OpenOrdersAccount::stp_group) of an open orders',
+        'account. Use 0 to leave the group.',
         '',
-        'Makers might wish to `refill`, rather than have actual tokens moved for',
-        'each trade, in order to reduce CUs.',
+        'Orders of the other accounts in the group are only recognized when those accounts are',
+        'passed as remaining accounts of the taking instruction, otherwise they match normally.',
       ],
       accounts: [
         {
@@ -5016,64 +7915,110 @@ export const IDL: OpenbookV2 = {
           isSigner: true,
         },
         {
-          name: 'userBaseAccount',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
+        {
+          name: 'stpGroup',
+          type: 'u16',
+        },
+      ],
+    },
+    {
+      name: 'setAutoSettle',
+      docs: [
+        'Opt in or out of having maker fills of an open orders account transferred directly to the',
+        "owner's associated token accounts during [`consume_events`](crate::openbook_v2::consume_events).",
+      ],
+      accounts: [
         {
-          name: 'userQuoteAccount',
-          isMut: true,
-          isSigner: false,
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
         },
         {
           name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'market',
-          isMut: true,
-          isSigner: false,
+          name: 'enabled',
+          type: 'bool',
         },
+      ],
+    },
+    {
+      name: 'setHeartbeatTimeout',
+      docs: [
+        'Let anyone cancel the orders of an open orders account once no heartbeat was recorded',
+        'for `timeout_seconds`, 0 disables it.',
+        '',
+        'Placing, cancelling, settling and withdrawing record heartbeats, as does',
+        '[`heartbeat`](crate::openbook_v2::heartbeat).',
+      ],
+      accounts: [
         {
-          name: 'marketBaseVault',
-          isMut: true,
-          isSigner: false,
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'openOrdersAccount',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'tokenProgram',
-          isMut: false,
-          isSigner: false,
+          name: 'timeoutSeconds',
+          type: 'u16',
         },
       ],
-      args: [
+    },
+    {
+      name: 'heartbeat',
+      docs: [
+        'Record a heartbeat of the owner or a delegate, keeping the orders of the account on the',
+        'book for another heartbeat timeout.',
+      ],
+      accounts: [
         {
-          name: 'baseAmount',
-          type: 'u64',
+          name: 'signer',
+          isMut: false,
+          isSigner: true,
         },
         {
-          name: 'quoteAmount',
-          type: 'u64',
+          name: 'openOrdersAccount',
+          isMut: true,
+          isSigner: false,
         },
       ],
+      args: [],
     },
     {
-      name: 'settleFunds',
-      docs: ['Withdraw any available tokens.'],
+      name: 'setRiskLimits',
+      docs: [
+        "Set the owner's risk limits of an open orders account, enforced whenever an order is",
+        'placed. A limit of 0 disables it.',
+        '',
+        '`max_taker_quote_native_per_window` caps the taker volume within windows of',
+        "`window_seconds`. The account is grown to store the limits the first time they're set,",
+        'paid by the `payer`.',
+      ],
       accounts: [
         {
-          name: 'owner',
+          name: 'payer',
           isMut: true,
           isSigner: true,
         },
         {
-          name: 'penaltyPayer',
-          isMut: true,
+          name: 'owner',
+          isMut: false,
           isSigner: true,
         },
         {
@@ -5082,58 +8027,54 @@ export const IDL: OpenbookV2 = {
           isSigner: false,
         },
         {
-          name: 'market',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'marketAuthority',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'marketBaseVault',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'marketQuoteVault',
-          isMut: true,
-          isSigner: false,
+          name: 'maxBaseLotsPerSide',
+          type: 'i64',
         },
         {
-          name: 'userBaseAccount',
-          isMut: true,
-          isSigner: false,
+          name: 'maxOrderQuoteNative',
+          type: 'u64',
         },
         {
-          name: 'userQuoteAccount',
-          isMut: true,
-          isSigner: false,
+          name: 'maxTakerQuoteNativePerWindow',
+          type: 'u64',
         },
         {
-          name: 'referrerAccount',
-          isMut: true,
-          isSigner: false,
-          isOptional: true,
+          name: 'windowSeconds',
+          type: 'u32',
         },
+      ],
+    },
+    {
+      name: 'setMarketExpired',
+      docs: [
+        'Set market to expired before pruning orders and closing the market (only',
+        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+      ],
+      accounts: [
         {
-          name: 'tokenProgram',
+          name: 'closeMarketAdmin',
           isMut: false,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: 'systemProgram',
-          isMut: false,
+          name: 'market',
+          isMut: true,
           isSigner: false,
         },
       ],
       args: [],
     },
     {
-      name: 'settleFundsExpired',
+      name: 'pruneOrders',
       docs: [
-        'Withdraw any available tokens when the market is expired (only',
+        'Remove orders from the book when the market is expired (only',
         '[`close_market_admin`](crate::state::Market::close_market_admin)).',
       ],
       accounts: [
@@ -5143,187 +8084,189 @@ export const IDL: OpenbookV2 = {
           isSigner: true,
         },
         {
-          name: 'owner',
+          name: 'openOrdersAccount',
           isMut: true,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: 'penaltyPayer',
-          isMut: true,
-          isSigner: true,
+          name: 'market',
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: 'openOrdersAccount',
+          name: 'bids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'market',
+          name: 'asks',
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'marketAuthority',
-          isMut: false,
-          isSigner: false,
+          name: 'limit',
+          type: 'u8',
+        },
+      ],
+    },
+    {
+      name: 'pruneExpiredOrders',
+      docs: [
+        'Remove up to `limit` expired orders from the book, earliest expiry first on each side.',
+        '',
+        'Makers whose [`OpenOrdersAccount`](crate::state::OpenOrdersAccount) is passed as a',
+        'remaining account are refunded right away. The other makers are refunded through an',
+        '[`OutEvent`](crate::state::OutEvent), for which the `penalty_payer` pays the event heap',
+        'penalty like a taker would. Pruning stops at the first order that needs an event once the',
+        'heap is full.',
+        '',
+        "The `crank_reward_receiver` is paid the market's",
+        '[`crank_reward`](crate::state::Market::crank_reward) for every removed order.',
+      ],
+      accounts: [
+        {
+          name: 'penaltyPayer',
+          isMut: true,
+          isSigner: true,
+          docs: [
+            'Pays the event heap penalty for the orders refunded through the event heap',
+          ],
         },
         {
-          name: 'marketBaseVault',
+          name: 'market',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
+          name: 'bids',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'userBaseAccount',
+          name: 'asks',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'userQuoteAccount',
+          name: 'eventHeap',
           isMut: true,
           isSigner: false,
         },
         {
-          name: 'referrerAccount',
+          name: 'crankRewardReceiver',
           isMut: true,
           isSigner: false,
           isOptional: true,
         },
         {
-          name: 'tokenProgram',
+          name: 'systemProgram',
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: 'systemProgram',
-          isMut: false,
-          isSigner: false,
+          name: 'limit',
+          type: 'u8',
         },
       ],
-      args: [],
     },
     {
-      name: 'sweepFees',
-      docs: ["Sweep fees, as a [`Market`](crate::state::Market)'s admin."],
+      name: 'quoteTakeOrder',
+      docs: [
+        'Match a take order against the book without changing it and return the expected',
+        '[`TakeQuote`](crate::state::TakeQuote) through the return data.',
+        '',
+        "Fails like `place_take_order` would, e.g. for fill or kill orders that can't be filled",
+        "or when the event heap is full. Pass the makers' open orders accounts as remaining",
+        'accounts to quote with the same accounts as the take order.',
+      ],
       accounts: [
-        {
-          name: 'collectFeeAdmin',
-          isMut: false,
-          isSigner: true,
-        },
         {
           name: 'market',
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: 'marketAuthority',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'marketQuoteVault',
-          isMut: true,
+          name: 'bids',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'tokenReceiverAccount',
-          isMut: true,
+          name: 'asks',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'tokenProgram',
+          name: 'eventHeap',
           isMut: false,
           isSigner: false,
         },
-      ],
-      args: [],
-    },
-    {
-      name: 'setDelegate',
-      docs: [
-        'Update the [`delegate`](crate::state::OpenOrdersAccount::delegate) of an open orders account.',
-      ],
-      accounts: [
-        {
-          name: 'owner',
-          isMut: true,
-          isSigner: true,
-        },
         {
-          name: 'openOrdersAccount',
-          isMut: true,
+          name: 'oracleA',
+          isMut: false,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'delegateAccount',
+          name: 'oracleB',
           isMut: false,
           isSigner: false,
           isOptional: true,
         },
       ],
-      args: [],
-    },
-    {
-      name: 'setMarketExpired',
-      docs: [
-        'Set market to expired before pruning orders and closing the market (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
-      ],
-      accounts: [
-        {
-          name: 'closeMarketAdmin',
-          isMut: false,
-          isSigner: true,
-        },
+      args: [
         {
-          name: 'market',
-          isMut: true,
-          isSigner: false,
+          name: 'args',
+          type: {
+            defined: 'PlaceTakeOrderArgs',
+          },
         },
       ],
-      args: [],
     },
     {
-      name: 'pruneOrders',
+      name: 'getL2Depth',
       docs: [
-        'Remove orders from the book when the market is expired (only',
-        '[`close_market_admin`](crate::state::Market::close_market_admin)).',
+        'Return the aggregated price levels of the book as [`L2Depth`] through the return data.',
+        '',
+        'Up to `max_levels` levels per side, best price first. Expired orders are skipped and',
+        'oracle pegged orders are resolved at the current oracle price, or skipped when the',
+        'oracle is not usable.',
       ],
       accounts: [
         {
-          name: 'closeMarketAdmin',
+          name: 'market',
           isMut: false,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: 'openOrdersAccount',
-          isMut: true,
+          name: 'bids',
+          isMut: false,
           isSigner: false,
         },
         {
-          name: 'market',
+          name: 'asks',
           isMut: false,
           isSigner: false,
         },
         {
-          name: 'bids',
-          isMut: true,
+          name: 'oracleA',
+          isMut: false,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: 'asks',
-          isMut: true,
+          name: 'oracleB',
+          isMut: false,
           isSigner: false,
+          isOptional: true,
         },
       ],
       args: [
         {
-          name: 'limit',
+          name: 'maxLevels',
           type: 'u8',
         },
       ],
@@ -5392,6 +8335,35 @@ export const IDL: OpenbookV2 = {
     },
     {
       name: 'stubOracleSet',
+      docs: [
+        'Set the price of a stub oracle, last updated at the current slot and time with no',
+        'deviation.',
+      ],
+      accounts: [
+        {
+          name: 'owner',
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: 'oracle',
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: 'price',
+          type: 'f64',
+        },
+      ],
+    },
+    {
+      name: 'stubOracleSetTest',
+      docs: [
+        'Set every field of a stub oracle read by the price checks, for tests of stale',
+        '(`last_update_slot`, `last_update_ts`) and low confidence (`deviation`) oracles.',
+      ],
       accounts: [
         {
           name: 'owner',
@@ -5409,6 +8381,18 @@ export const IDL: OpenbookV2 = {
           name: 'price',
           type: 'f64',
         },
+        {
+          name: 'lastUpdateSlot',
+          type: 'u64',
+        },
+        {
+          name: 'lastUpdateTs',
+          type: 'i64',
+        },
+        {
+          name: 'deviation',
+          type: 'f64',
+        },
       ],
     },
   ],
@@ -5512,6 +8496,11 @@ export const IDL: OpenbookV2 = {
           },
           {
             name: 'oracleB',
+            docs: [
+              'Divides the price of oracle_a, e.g. BTC/USD by USDC/USD for a BTC/USDC market. With',
+              '`oracle_config.fallback_to_oracle_b` it is instead a price of the base in quote on its own,',
+              'only used when oracle_a is stale or fails the confidence filter.',
+            ],
             type: {
               defined: 'NonZeroPubkeyOption',
             },
@@ -5629,10 +8618,49 @@ export const IDL: OpenbookV2 = {
             name: 'quoteDepositTotal',
             type: 'u64',
           },
+          {
+            name: 'penaltyEventHeap',
+            docs: [
+              'Lamports a taker pays for every event it adds to the event heap. 0 stands for',
+              '`PENALTY_EVENT_HEAP`, as on markets created before this was configurable.',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'crankReward',
+            docs: [
+              'Lamports paid out of the penalty pool for every event processed by consume_events,',
+              'at most the heap penalty per event',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'penaltyPool',
+            docs: [
+              "Penalty lamports held by the market account that weren't paid out as crank rewards yet",
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'oracleAFeedId',
+            docs: [
+              'Pyth pull feeds the price updates of oracle_a and oracle_b must be for, zeroed for',
+              'other oracle types',
+            ],
+            type: {
+              array: ['u8', 32],
+            },
+          },
+          {
+            name: 'oracleBFeedId',
+            type: {
+              array: ['u8', 32],
+            },
+          },
           {
             name: 'reserved',
             type: {
-              array: ['u8', 128],
+              array: ['u8', 40],
             },
           },
         ],
@@ -5676,10 +8704,12 @@ export const IDL: OpenbookV2 = {
             type: 'u8',
           },
           {
-            name: 'padding',
-            type: {
-              array: ['u8', 2],
-            },
+            name: 'stpGroup',
+            docs: [
+              'Self-trade prevention group, 0 means none. Orders of accounts of the same owner',
+              "sharing a group are subject to the taker's SelfTradeBehavior.",
+            ],
+            type: 'u16',
           },
           {
             name: 'position',
@@ -5720,6 +8750,15 @@ export const IDL: OpenbookV2 = {
               vec: 'publicKey',
             },
           },
+          {
+            name: 'markets',
+            docs: [
+              'Market of the open orders account at the same index of `addresses`',
+            ],
+            type: {
+              defined: 'IndexedMarkets',
+            },
+          },
         ],
       },
     },
@@ -5808,9 +8847,12 @@ export const IDL: OpenbookV2 = {
       docs: [
         'Container for the different EventTypes.',
         '',
-        'Events are stored in a fixed-array of nodes. Free nodes are connected by a single-linked list',
+        'Events are stored in an array of nodes. Free nodes are connected by a single-linked list',
         'starting at free_head while used nodes form a circular doubly-linked list starting at',
         'used_head.',
+        '',
+        'This is the layout at the default capacity of `MAX_NUM_EVENTS` nodes. Accounts can be',
+        'created with other capacities and are accessed through `EventHeapView`.',
       ],
       type: {
         kind: 'struct',
@@ -5835,166 +8877,448 @@ export const IDL: OpenbookV2 = {
           {
             name: 'reserved',
             type: {
-              array: ['u8', 64],
+              array: ['u8', 64],
+            },
+          },
+        ],
+      },
+    },
+  ],
+  types: [
+    {
+      name: 'NonZeroPubkeyOption',
+      docs: [
+        'Like `Option`, but implemented for `Pubkey` to be used with `zero_copy`',
+      ],
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'key',
+            type: 'publicKey',
+          },
+        ],
+      },
+    },
+    {
+      name: 'Position',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'bidsBaseLots',
+            docs: ['Base lots in open bids'],
+            type: 'i64',
+          },
+          {
+            name: 'asksBaseLots',
+            docs: ['Base lots in open asks'],
+            type: 'i64',
+          },
+          {
+            name: 'baseFreeNative',
+            type: 'u64',
+          },
+          {
+            name: 'quoteFreeNative',
+            type: 'u64',
+          },
+          {
+            name: 'lockedMakerFees',
+            type: 'u64',
+          },
+          {
+            name: 'referrerRebatesAvailable',
+            type: 'u64',
+          },
+          {
+            name: 'penaltyHeapCount',
+            docs: [
+              "Count of events added to the heap, each paying the market's heap penalty on settlement",
+              'To avoid this, send remaining accounts in order to process the events',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'makerVolume',
+            docs: [
+              'Cumulative maker volume in quote native units (display only)',
+            ],
+            type: 'u128',
+          },
+          {
+            name: 'takerVolume',
+            docs: [
+              'Cumulative taker volume in quote native units (display only)',
+            ],
+            type: 'u128',
+          },
+          {
+            name: 'bidsQuoteLots',
+            docs: ['Quote lots in open bids'],
+            type: 'i64',
+          },
+          {
+            name: 'autoSettle',
+            docs: [
+              "Opt-in to have the funds credited by a fill transferred to the owner's associated token",
+              'accounts right when the fill is executed, if the crank or taker provides them. 0 means',
+              'disabled.',
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'delegateRevokedPermissions',
+            docs: [
+              'Bitmask of the `DELEGATE_*` actions the delegate may not take. Stored inverted so',
+              'delegates set before permissions existed keep all of them.',
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'sessionDelegateSlots',
+            docs: [
+              'Number of session delegate slots stored at the end of the account',
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'riskLimitsSlots',
+            docs: [
+              'Number of [`RiskLimits`] stored before the session delegates, 0 or 1',
+            ],
+            type: 'u8',
+          },
+          {
+            name: 'padding',
+            type: {
+              array: ['u8', 2],
+            },
+          },
+          {
+            name: 'heartbeatTimeoutSeconds',
+            docs: [
+              'Orders can be cancelled by anyone once no heartbeat was recorded for this long.',
+              '0 means disabled.',
+            ],
+            type: 'u16',
+          },
+          {
+            name: 'delegateMaxOrderQuoteNative',
+            docs: [
+              'Largest quote native value, taken plus posted, of an order placed by the delegate.',
+              '0 means unlimited.',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'heartbeatTimestamp',
+            docs: ['Last time the owner or a delegate used the account'],
+            type: 'u64',
+          },
+          {
+            name: 'reserved',
+            type: {
+              array: ['u8', 40],
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: 'OpenOrder',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'id',
+            type: 'u128',
+          },
+          {
+            name: 'clientId',
+            type: 'u64',
+          },
+          {
+            name: 'lockedPrice',
+            docs: ["Price at which user's assets were locked"],
+            type: 'i64',
+          },
+          {
+            name: 'isFree',
+            type: 'u8',
+          },
+          {
+            name: 'sideAndTree',
+            type: 'u8',
+          },
+          {
+            name: 'padding',
+            type: {
+              array: ['u8', 6],
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: 'SessionDelegate',
+      docs: [
+        'Short-lived delegate, e.g. a hot key of a trading bot, with the permissions of the delegate',
+      ],
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'key',
+            type: 'publicKey',
+          },
+          {
+            name: 'expiryTimestamp',
+            docs: [
+              'Unix timestamp from which on the key has no authority anymore',
+            ],
+            type: 'u64',
+          },
+        ],
+      },
+    },
+    {
+      name: 'RiskLimits',
+      docs: [
+        'Owner-set limits on the orders of an account, stored after the open order slots once set',
+      ],
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'maxBaseLotsPerSide',
+            docs: [
+              'Cap on base lots in open orders on each side. 0 means unlimited.',
+            ],
+            type: 'i64',
+          },
+          {
+            name: 'maxOrderQuoteNative',
+            docs: [
+              'Cap on the quote native value, taken plus posted, of a single order. 0 means unlimited.',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'maxTakerQuoteNativePerWindow',
+            docs: [
+              'Cap on quote native taker volume per window. 0 means unlimited.',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'windowSeconds',
+            docs: [
+              'Length of the window `max_taker_quote_native_per_window` applies to',
+            ],
+            type: 'u32',
+          },
+          {
+            name: 'padding',
+            type: {
+              array: ['u8', 4],
+            },
+          },
+          {
+            name: 'windowStartTimestamp',
+            docs: ['Start of the current window'],
+            type: 'u64',
+          },
+          {
+            name: 'windowTakerQuoteNative',
+            docs: [
+              'Taker volume in quote native units since the start of the current window',
+            ],
+            type: 'u64',
+          },
+          {
+            name: 'reserved',
+            type: {
+              array: ['u8', 8],
             },
           },
         ],
       },
     },
-  ],
-  types: [
     {
-      name: 'NonZeroPubkeyOption',
+      name: 'IndexedMarkets',
       docs: [
-        'Like `Option`, but implemented for `Pubkey` to be used with `zero_copy`',
+        'Trailing section of [`OpenOrdersIndexer`], missing from indexers created before markets',
+        'were recorded.',
+        '',
+        'Such indexers hold no markets until the next open orders account is added, after which the',
+        'markets of the older accounts are the default pubkey until `migrate_open_orders_indexer`',
+        'backfills them.',
       ],
       type: {
         kind: 'struct',
         fields: [
           {
-            name: 'key',
-            type: 'publicKey',
+            name: 'keys',
+            type: {
+              vec: 'publicKey',
+            },
           },
         ],
       },
     },
     {
-      name: 'Position',
+      name: 'OracleConfig',
       type: {
         kind: 'struct',
         fields: [
           {
-            name: 'bidsBaseLots',
-            docs: ['Base lots in open bids'],
-            type: 'i64',
+            name: 'confFilter',
+            type: 'f64',
           },
           {
-            name: 'asksBaseLots',
-            docs: ['Base lots in open asks'],
+            name: 'maxStalenessSlots',
             type: 'i64',
           },
           {
-            name: 'baseFreeNative',
-            type: 'u64',
-          },
-          {
-            name: 'quoteFreeNative',
-            type: 'u64',
-          },
-          {
-            name: 'lockedMakerFees',
-            type: 'u64',
-          },
-          {
-            name: 'referrerRebatesAvailable',
+            name: 'maxStalenessSeconds',
+            docs: [
+              "Maximum age of the oracle's publish time, 0 disables the check. Markets created before",
+              'the field existed have it zeroed.',
+            ],
             type: 'u64',
           },
           {
-            name: 'penaltyHeapCount',
+            name: 'minLiquidity',
             docs: [
-              'Count of ixs when events are added to the heap',
-              'To avoid this, send remaining accounts in order to process the events',
+              'Minimum in range liquidity of AMM pool oracles, 0 disables the check',
             ],
             type: 'u64',
           },
           {
-            name: 'makerVolume',
+            name: 'useEmaPrice',
             docs: [
-              'Cumulative maker volume in quote native units (display only)',
+              "Use the oracle's EMA price instead of its spot price when 1",
             ],
-            type: 'u128',
+            type: 'u8',
           },
           {
-            name: 'takerVolume',
+            name: 'fallbackToOracleB',
             docs: [
-              'Cumulative taker volume in quote native units (display only)',
+              'Use oracle_b on its own when oracle_a is invalid instead of dividing by it when 1',
             ],
-            type: 'u128',
-          },
-          {
-            name: 'bidsQuoteLots',
-            docs: ['Quote lots in open bids'],
-            type: 'i64',
+            type: 'u8',
           },
           {
             name: 'reserved',
             type: {
-              array: ['u8', 64],
+              array: ['u8', 54],
             },
           },
         ],
       },
     },
     {
-      name: 'OpenOrder',
+      name: 'OracleConfigParams',
       type: {
         kind: 'struct',
         fields: [
           {
-            name: 'id',
-            type: 'u128',
+            name: 'confFilter',
+            type: 'f32',
           },
           {
-            name: 'clientId',
-            type: 'u64',
+            name: 'maxStalenessSlots',
+            type: {
+              option: 'u32',
+            },
           },
           {
-            name: 'lockedPrice',
-            docs: ["Price at which user's assets were locked"],
-            type: 'i64',
+            name: 'maxStalenessSeconds',
+            type: {
+              option: 'u32',
+            },
           },
           {
-            name: 'isFree',
-            type: 'u8',
+            name: 'minLiquidity',
+            type: {
+              option: 'u64',
+            },
           },
           {
-            name: 'sideAndTree',
-            type: 'u8',
+            name: 'useEmaPrice',
+            type: 'bool',
           },
           {
-            name: 'padding',
-            type: {
-              array: ['u8', 6],
-            },
+            name: 'fallbackToOracleB',
+            type: 'bool',
           },
         ],
       },
     },
     {
-      name: 'OracleConfig',
+      name: 'TakeQuote',
+      docs: ['Expected outcome of a take order, see `Orderbook::quote_order`'],
       type: {
         kind: 'struct',
         fields: [
           {
-            name: 'confFilter',
-            type: 'f64',
+            name: 'totalBaseTakenNative',
+            type: 'u64',
           },
           {
-            name: 'maxStalenessSlots',
-            type: 'i64',
+            name: 'totalQuoteTakenNative',
+            type: 'u64',
           },
           {
-            name: 'reserved',
-            type: {
-              array: ['u8', 72],
-            },
+            name: 'takerFeesNative',
+            type: 'u64',
+          },
+          {
+            name: 'notEnoughLiquidity',
+            type: 'bool',
           },
         ],
       },
     },
     {
-      name: 'OracleConfigParams',
+      name: 'BookSideHeader',
+      docs: ['The fields of a BookSide account in front of its nodes'],
       type: {
         kind: 'struct',
         fields: [
           {
-            name: 'confFilter',
-            type: 'f32',
+            name: 'roots',
+            type: {
+              array: [
+                {
+                  defined: 'OrderTreeRoot',
+                },
+                2,
+              ],
+            },
           },
           {
-            name: 'maxStalenessSlots',
+            name: 'reservedRoots',
             type: {
-              option: 'u32',
+              array: [
+                {
+                  defined: 'OrderTreeRoot',
+                },
+                4,
+              ],
+            },
+          },
+          {
+            name: 'reserved',
+            type: {
+              array: ['u8', 256],
+            },
+          },
+          {
+            name: 'orderTree',
+            type: {
+              defined: 'OrderTreeHeader',
             },
           },
         ],
@@ -6095,10 +9419,14 @@ export const IDL: OpenbookV2 = {
             name: 'makerSlot',
             type: 'u8',
           },
+          {
+            name: 'makerSlotHi',
+            type: 'u8',
+          },
           {
             name: 'padding',
             type: {
-              array: ['u8', 4],
+              array: ['u8', 3],
             },
           },
           {
@@ -6106,7 +9434,7 @@ export const IDL: OpenbookV2 = {
             type: 'u64',
           },
           {
-            name: 'seqNum',
+            name: 'marketSeqNum',
             type: 'u64',
           },
           {
@@ -6167,10 +9495,14 @@ export const IDL: OpenbookV2 = {
             name: 'ownerSlot',
             type: 'u8',
           },
+          {
+            name: 'ownerSlotHi',
+            type: 'u8',
+          },
           {
             name: 'padding0',
             type: {
-              array: ['u8', 5],
+              array: ['u8', 4],
             },
           },
           {
@@ -6274,7 +9606,9 @@ export const IDL: OpenbookV2 = {
           },
           {
             name: 'ownerSlot',
-            docs: ["Index into the owning OpenOrdersAccount's OpenOrders"],
+            docs: [
+              "Index into the owning OpenOrdersAccount's OpenOrders, low byte",
+            ],
             type: 'u8',
           },
           {
@@ -6285,10 +9619,18 @@ export const IDL: OpenbookV2 = {
             ],
             type: 'u16',
           },
+          {
+            name: 'ownerSlotHi',
+            docs: [
+              "High byte of the index into the owning OpenOrdersAccount's OpenOrders,",
+              'see owner_slot_index()',
+            ],
+            type: 'u8',
+          },
           {
             name: 'padding',
             type: {
-              array: ['u8', 4],
+              array: ['u8', 3],
             },
           },
           {
@@ -6366,9 +9708,9 @@ export const IDL: OpenbookV2 = {
     {
       name: 'OrderTreeNodes',
       docs: [
-        'A binary tree on AnyNode::key()',
+        'Layout of the nodes of a BookSide at the default capacity',
         '',
-        'The key encodes the price in the top 64 bits.',
+        'BookSide accounts can hold any number of nodes, see OrderTreeHeader and OrderTreeRef.',
       ],
       type: {
         kind: 'struct',
@@ -6415,6 +9757,43 @@ export const IDL: OpenbookV2 = {
         ],
       },
     },
+    {
+      name: 'OrderTreeHeader',
+      docs: ['The fields of OrderTreeNodes in front of the nodes'],
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'orderTreeType',
+            type: 'u8',
+          },
+          {
+            name: 'padding',
+            type: {
+              array: ['u8', 3],
+            },
+          },
+          {
+            name: 'bumpIndex',
+            type: 'u32',
+          },
+          {
+            name: 'freeListLen',
+            type: 'u32',
+          },
+          {
+            name: 'freeListHead',
+            type: 'u32',
+          },
+          {
+            name: 'reserved',
+            type: {
+              array: ['u8', 512],
+            },
+          },
+        ],
+      },
+    },
     {
       name: 'I80F48',
       docs: [
@@ -6575,18 +9954,105 @@ export const IDL: OpenbookV2 = {
             type: 'i64',
           },
           {
-            name: 'maxQuoteLotsIncludingFees',
+            name: 'maxQuoteLotsIncludingFees',
+            type: 'i64',
+          },
+          {
+            name: 'orderType',
+            type: {
+              defined: 'PlaceOrderType',
+            },
+          },
+          {
+            name: 'limit',
+            type: 'u8',
+          },
+        ],
+      },
+    },
+    {
+      name: 'PlaceTakeOrderExactQuoteInArgs',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'priceLots',
+            type: 'i64',
+          },
+          {
+            name: 'quoteAmountNative',
+            type: 'u64',
+          },
+          {
+            name: 'orderType',
+            type: {
+              defined: 'PlaceOrderType',
+            },
+          },
+          {
+            name: 'limit',
+            type: 'u8',
+          },
+        ],
+      },
+    },
+    {
+      name: 'PlaceTakeOrderTwoHopArgs',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'amountInNative',
+            type: 'u64',
+          },
+          {
+            name: 'minAmountOutNative',
+            type: 'u64',
+          },
+          {
+            name: 'limit',
+            type: 'u8',
+          },
+        ],
+      },
+    },
+    {
+      name: 'L2Level',
+      type: {
+        kind: 'struct',
+        fields: [
+          {
+            name: 'priceLots',
+            type: 'i64',
+          },
+          {
+            name: 'baseLots',
             type: 'i64',
           },
+        ],
+      },
+    },
+    {
+      name: 'L2Depth',
+      docs: ['Return data of `get_l2_depth`'],
+      type: {
+        kind: 'struct',
+        fields: [
           {
-            name: 'orderType',
+            name: 'bids',
             type: {
-              defined: 'PlaceOrderType',
+              vec: {
+                defined: 'L2Level',
+              },
             },
           },
           {
-            name: 'limit',
-            type: 'u8',
+            name: 'asks',
+            type: {
+              vec: {
+                defined: 'L2Level',
+              },
+            },
           },
         ],
       },
@@ -6611,6 +10077,15 @@ export const IDL: OpenbookV2 = {
           {
             name: 'RaydiumCLMM',
           },
+          {
+            name: 'PythPull',
+          },
+          {
+            name: 'SwitchboardOnDemand',
+          },
+          {
+            name: 'OrcaWhirlpool',
+          },
         ],
       },
     },
@@ -6728,7 +10203,8 @@ export const IDL: OpenbookV2 = {
     {
       name: 'SelfTradeBehavior',
       docs: [
-        'Self trade behavior controls how taker orders interact with resting limit orders of the same account.',
+        'Self trade behavior controls how taker orders interact with resting limit orders of the same account,',
+        'or of another account of the same owner sharing a non-zero `stp_group`.',
         'This setting has no influence on placing a resting or oracle pegged limit order that does not match',
         "immediately, instead it's the responsibility of the user to correctly configure his taker orders.",
       ],
@@ -6744,6 +10220,12 @@ export const IDL: OpenbookV2 = {
           {
             name: 'AbortTransaction',
           },
+          {
+            name: 'CancelTake',
+          },
+          {
+            name: 'CancelBoth',
+          },
         ],
       },
     },
@@ -6888,6 +10370,31 @@ export const IDL: OpenbookV2 = {
         },
       ],
     },
+    {
+      name: 'WithdrawLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'signer',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'baseAmount',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'quoteAmount',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
     {
       name: 'FillLog',
       fields: [
@@ -6903,7 +10410,7 @@ export const IDL: OpenbookV2 = {
         },
         {
           name: 'makerSlot',
-          type: 'u8',
+          type: 'u16',
           index: false,
         },
         {
@@ -7011,6 +10518,16 @@ export const IDL: OpenbookV2 = {
           type: 'i64',
           index: false,
         },
+        {
+          name: 'useEmaPrice',
+          type: 'bool',
+          index: false,
+        },
+        {
+          name: 'fallbackToOracleB',
+          type: 'bool',
+          index: false,
+        },
       ],
     },
     {
@@ -7060,6 +10577,171 @@ export const IDL: OpenbookV2 = {
         },
       ],
     },
+    {
+      name: 'SetDelegatePermissionsLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'permissions',
+          type: 'u8',
+          index: false,
+        },
+        {
+          name: 'maxOrderQuoteNative',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'TransferOpenOrdersAccountLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'owner',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'newOwner',
+          type: 'publicKey',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'SetSessionDelegateLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'sessionKey',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'expiryTimestamp',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'SetAutoSettleLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'enabled',
+          type: 'bool',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'SetHeartbeatTimeoutLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'timeoutSeconds',
+          type: 'u16',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'CancelOnMissedHeartbeatLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'heartbeatTimestamp',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'SetRiskLimitsLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'maxBaseLotsPerSide',
+          type: 'i64',
+          index: false,
+        },
+        {
+          name: 'maxOrderQuoteNative',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'maxTakerQuoteNativePerWindow',
+          type: 'u64',
+          index: false,
+        },
+        {
+          name: 'windowSeconds',
+          type: 'u32',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'SetStpGroupLog',
+      fields: [
+        {
+          name: 'openOrdersAccount',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'stpGroup',
+          type: 'u16',
+          index: false,
+        },
+      ],
+    },
+    {
+      name: 'SetCrankRewardLog',
+      fields: [
+        {
+          name: 'market',
+          type: 'publicKey',
+          index: false,
+        },
+        {
+          name: 'crankReward',
+          type: 'u64',
+          index: false,
+        },
+      ],
+    },
     {
       name: 'SettleFundsLog',
       fields: [
@@ -7399,5 +11081,139 @@ export const IDL: OpenbookV2 = {
       name: 'WouldExecutePartially',
       msg: 'Fill-Or-Kill order would generate a partial execution',
     },
+    {
+      code: 6044,
+      name: 'InvalidTwoHopRoute',
+      msg: "The markets and token accounts don't form a valid two hop route",
+    },
+    {
+      code: 6045,
+      name: 'TwoHopAmountOutBelowMinimum',
+      msg: 'The two hop swap would receive less than the minimum amount out',
+    },
+    {
+      code: 6046,
+      name: 'InvalidOpenOrdersCapacity',
+      msg: 'Invalid open orders account capacity',
+    },
+    {
+      code: 6047,
+      name: 'InvalidBookSideCapacity',
+      msg: 'Invalid book side capacity',
+    },
+    {
+      code: 6048,
+      name: 'InvalidEventHeapCapacity',
+      msg: 'Invalid event heap capacity',
+    },
+    {
+      code: 6049,
+      name: 'InvalidInputDepthLevels',
+      msg: 'Depth levels above return data limit',
+    },
+    {
+      code: 6050,
+      name: 'InsufficientFreeFunds',
+      msg: 'Amount exceeds the free funds of the position',
+    },
+    {
+      code: 6051,
+      name: 'InvalidSettleBatchAccounts',
+      msg: "The remaining accounts don't describe open orders accounts of the owner's indexer",
+    },
+    {
+      code: 6052,
+      name: 'EventHeapFull',
+      msg: "The event heap is full and the maker's open orders account wasn't passed",
+    },
+    {
+      code: 6053,
+      name: 'InvalidDelegatePermissions',
+      msg: 'Delegate permissions have unknown bits set',
+    },
+    {
+      code: 6054,
+      name: 'DelegateOrderNotionalExceeded',
+      msg: "Order value exceeds the delegate's limit",
+    },
+    {
+      code: 6055,
+      name: 'InvalidInputSessionExpiry',
+    },
+    {
+      code: 6056,
+      name: 'SessionDelegatesFull',
+      msg: 'No session delegate slot left',
+    },
+    {
+      code: 6057,
+      name: 'InvalidInputRiskLimits',
+      msg: "Risk limits can't be negative and a taker volume limit needs a window",
+    },
+    {
+      code: 6058,
+      name: 'RiskLimitOpenBaseLotsExceeded',
+      msg: "Open orders on this side exceed the account's base lots limit",
+    },
+    {
+      code: 6059,
+      name: 'RiskLimitOrderNotionalExceeded',
+      msg: "Order value exceeds the account's limit",
+    },
+    {
+      code: 6060,
+      name: 'RiskLimitTakerVolumeExceeded',
+      msg: "Taker volume exceeds the account's limit for the current window",
+    },
+    {
+      code: 6061,
+      name: 'HeartbeatNotMissed',
+      msg: "The account's heartbeat timeout hasn't elapsed",
+    },
+    {
+      code: 6062,
+      name: 'InvalidOracleVerificationLevel',
+      msg: "The Pyth price update isn't fully verified",
+    },
+    {
+      code: 6063,
+      name: 'OracleEmaPriceUnavailable',
+      msg: "The oracle doesn't provide an EMA price",
+    },
+    {
+      code: 6064,
+      name: 'InvalidOracleFallback',
+      msg: 'Falling back to oracle b requires a second oracle',
+    },
+    {
+      code: 6065,
+      name: 'InvalidCrankReward',
+      msg: "The crank reward can't exceed the event heap penalty",
+    },
+    {
+      code: 6066,
+      name: 'InvalidOpenOrdersIndexerMigration',
+      msg: "The open orders account isn't part of the indexer",
+    },
+    {
+      code: 6067,
+      name: 'InvalidOraclePoolMints',
+      msg: "The tokens of the oracle pool don't match the market",
+    },
+    {
+      code: 6068,
+      name: 'NoDelegate',
+      msg: 'The open orders account has no delegate',
+    },
+    {
+      code: 6069,
+      name: 'OpenOrdersIndexerFull',
+      msg: 'The open orders indexer holds the maximum number of accounts',
+    },
+    {
+      code: 6070,
+      name: 'InvalidOracleFeed',
+      msg: 'The Pyth price update is for a different feed',
+    },
   ],
 };
@@ -27,6 +27,8 @@ export const SelfTradeBehaviorUtils = {
   DecrementTake: { decrementTake: {} },
   CancelProvide: { cancelProvide: {} },
   AbortTransaction: { abortTransaction: {} },
+  CancelTake: { cancelTake: {} },
+  CancelBoth: { cancelBoth: {} },
 };
 
 ///